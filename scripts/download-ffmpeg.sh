@@ -1,5 +1,5 @@
 #!/bin/bash
-# Download ffmpeg binary for the current platform (or all platforms with --all)
+# Download ffmpeg and ffprobe binaries for the current platform (or all platforms with --all)
 
 set -e
 
@@ -17,6 +17,8 @@ download_linux() {
     tar -xf "$TEMP_DIR/ffmpeg.tar.xz" -C "$TEMP_DIR"
     cp "$TEMP_DIR"/ffmpeg-master-latest-linux64-gpl/bin/ffmpeg "$BINARIES_DIR/ffmpeg-x86_64-unknown-linux-gnu"
     chmod +x "$BINARIES_DIR/ffmpeg-x86_64-unknown-linux-gnu"
+    cp "$TEMP_DIR"/ffmpeg-master-latest-linux64-gpl/bin/ffprobe "$BINARIES_DIR/ffprobe-x86_64-unknown-linux-gnu"
+    chmod +x "$BINARIES_DIR/ffprobe-x86_64-unknown-linux-gnu"
     rm -rf "$TEMP_DIR"
 }
 
@@ -29,6 +31,11 @@ download_macos_x64() {
     unzip -q "$TEMP_DIR/ffmpeg.zip" -d "$TEMP_DIR"
     cp "$TEMP_DIR/ffmpeg" "$BINARIES_DIR/ffmpeg-x86_64-apple-darwin"
     chmod +x "$BINARIES_DIR/ffmpeg-x86_64-apple-darwin"
+    curl -L -s "https://evermeet.cx/ffmpeg/getrelease/ffprobe/zip" \
+        -o "$TEMP_DIR/ffprobe.zip"
+    unzip -q "$TEMP_DIR/ffprobe.zip" -d "$TEMP_DIR"
+    cp "$TEMP_DIR/ffprobe" "$BINARIES_DIR/ffprobe-x86_64-apple-darwin"
+    chmod +x "$BINARIES_DIR/ffprobe-x86_64-apple-darwin"
     rm -rf "$TEMP_DIR"
 }
 
@@ -41,6 +48,11 @@ download_macos_arm() {
     unzip -q "$TEMP_DIR/ffmpeg.zip" -d "$TEMP_DIR"
     cp "$TEMP_DIR/ffmpeg" "$BINARIES_DIR/ffmpeg-aarch64-apple-darwin"
     chmod +x "$BINARIES_DIR/ffmpeg-aarch64-apple-darwin"
+    curl -L -s "https://evermeet.cx/ffmpeg/getrelease/ffprobe/zip" \
+        -o "$TEMP_DIR/ffprobe.zip"
+    unzip -q "$TEMP_DIR/ffprobe.zip" -d "$TEMP_DIR"
+    cp "$TEMP_DIR/ffprobe" "$BINARIES_DIR/ffprobe-aarch64-apple-darwin"
+    chmod +x "$BINARIES_DIR/ffprobe-aarch64-apple-darwin"
     rm -rf "$TEMP_DIR"
 }
 
@@ -51,6 +63,7 @@ download_windows() {
         -o "$TEMP_DIR/ffmpeg.zip"
     unzip -q "$TEMP_DIR/ffmpeg.zip" -d "$TEMP_DIR"
     cp "$TEMP_DIR"/ffmpeg-master-latest-win64-gpl/bin/ffmpeg.exe "$BINARIES_DIR/ffmpeg-x86_64-pc-windows-msvc.exe"
+    cp "$TEMP_DIR"/ffmpeg-master-latest-win64-gpl/bin/ffprobe.exe "$BINARIES_DIR/ffprobe-x86_64-pc-windows-msvc.exe"
     rm -rf "$TEMP_DIR"
 }
 
@@ -0,0 +1,35 @@
+use crate::db::{database_path, Database};
+use crate::services::backup::{self, BackupManifest};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Write a backup archive of the library database, settings and cookie files to `path`
+#[tauri::command]
+pub async fn create_backup(app_handle: AppHandle, path: String, include_thumbnails: bool) -> Result<BackupManifest, String> {
+    let db_path = database_path(&app_handle.path().app_data_dir().map_err(|e| e.to_string())?);
+    tokio::task::spawn_blocking(move || backup::create_backup(&db_path, &PathBuf::from(path), include_thumbnails))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Read a backup's manifest, e.g. to confirm its date before restoring it.
+/// Fails for archives this version can't restore.
+#[tauri::command]
+pub async fn inspect_backup(path: String) -> Result<BackupManifest, String> {
+    tokio::task::spawn_blocking(move || backup::read_manifest(&PathBuf::from(path)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Replace the library database with the one in a backup archive
+#[tauri::command]
+pub async fn restore_backup(app_handle: AppHandle, path: String) -> Result<BackupManifest, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    tokio::task::spawn_blocking(move || {
+        let db = app_handle.state::<Database>();
+        let mut conn = db.conn.lock().map_err(|e| e.to_string())?;
+        backup::restore_backup(&mut conn, &PathBuf::from(path), &app_data_dir)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,6 +1,8 @@
 use crate::db::Database;
 use crate::models::credential::{CreateCredentialRequest, Credential, UpdateCredentialRequest};
+use crate::workers::SyncManager;
 use chrono::Utc;
+use rusqlite::Connection;
 use tauri::State;
 use uuid::Uuid;
 
@@ -139,6 +141,7 @@ pub fn create_credential(
 #[tauri::command]
 pub fn update_credential(
     db: State<Database>,
+    sync_manager: State<SyncManager>,
     id: String,
     request: UpdateCredentialRequest,
 ) -> Result<Credential, String> {
@@ -198,9 +201,38 @@ pub fn update_credential(
     )
     .map_err(|e| e.to_string())?;
 
+    // A new cookie file may come with a different pledge, so sources with locked
+    // posts are synced again to re-check them. The full listing is needed because
+    // the locked posts may be older than a quick listing reaches.
+    for source_id in sources_with_locked_items(&conn, &credential)? {
+        sync_manager.sync_source_full(source_id);
+    }
+
     Ok(credential)
 }
 
+/// Sources fetched with a credential (their own, or the platform default) that have locked items
+fn sources_with_locked_items(conn: &Connection, credential: &Credential) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id FROM sources s
+             WHERE (s.credential_id = ?1 OR (s.credential_id IS NULL AND s.platform = ?2 AND ?3))
+               AND EXISTS (SELECT 1 FROM feed_items fi WHERE fi.source_id = s.id AND fi.locked = 1)",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let source_ids = stmt
+        .query_map(
+            rusqlite::params![credential.id, credential.platform, credential.is_default],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(source_ids)
+}
+
 #[tauri::command]
 pub fn delete_credential(db: State<Database>, id: String) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
@@ -239,3 +271,23 @@ pub fn get_default_credential(
         Err(e) => Err(e.to_string()),
     }
 }
+
+/// Cookie file for a fetch: the source's own credential, or the platform's default
+pub fn get_cookie_path(conn: &Connection, platform: &str, credential_id: Option<&str>) -> Option<String> {
+    match credential_id {
+        Some(cred_id) => conn
+            .query_row(
+                "SELECT cookie_path FROM credentials WHERE id = ?",
+                [cred_id],
+                |row| row.get(0),
+            )
+            .ok(),
+        None => conn
+            .query_row(
+                "SELECT cookie_path FROM credentials WHERE platform = ? AND is_default = 1",
+                [platform],
+                |row| row.get(0),
+            )
+            .ok(),
+    }
+}
@@ -0,0 +1,388 @@
+use crate::db::Database;
+use crate::services::nfo::escape_xml;
+use chrono::Utc;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportRequest {
+    pub path: String,
+    /// "opml", "json" or "csv"
+    pub format: String,
+    #[serde(default)]
+    pub include_feed_items: bool,
+    #[serde(default)]
+    pub include_warehouse_items: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedCreator {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedSource {
+    pub id: String,
+    pub creator_id: String,
+    pub creator_name: String,
+    pub platform: String,
+    pub channel_url: String,
+    pub channel_name: Option<String>,
+    pub channel_id: Option<String>,
+    pub status: String,
+    /// The credential the source uses, by reference; cookie files are never exported
+    pub credential_id: Option<String>,
+    pub credential_label: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedCredential {
+    pub id: String,
+    pub label: String,
+    pub platform: String,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedFeedItem {
+    pub id: String,
+    pub source_id: String,
+    pub external_id: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub published_at: Option<String>,
+    pub duration: Option<i64>,
+    pub content_kind: Option<String>,
+    pub download_status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedWarehouseItem {
+    pub id: String,
+    pub creator_id: String,
+    pub feed_item_id: Option<String>,
+    pub title: String,
+    pub platform: Option<String>,
+    pub original_url: Option<String>,
+    pub published_at: Option<String>,
+    pub duration: Option<i64>,
+    pub file_path: String,
+    pub file_size: i64,
+    pub imported_at: String,
+}
+
+/// The JSON export: one flat list per table, sorted so two exports diff cleanly
+#[derive(Debug, Serialize)]
+struct LibraryExport {
+    app_version: &'static str,
+    exported_at: String,
+    creators: Vec<ExportedCreator>,
+    sources: Vec<ExportedSource>,
+    credentials: Vec<ExportedCredential>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_items: Option<Vec<ExportedFeedItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warehouse_items: Option<Vec<ExportedWarehouseItem>>,
+}
+
+/// Export creators, their sources and credential references as OPML, JSON or CSV,
+/// optionally with the feed and warehouse item lists. CSV writes the sources to
+/// `path` and every other list next to it with a `.creators`, `.credentials`,
+/// `.feed_items` or `.warehouse_items` suffix. Returns the paths written.
+#[tauri::command]
+pub fn export_library(db: State<Database>, request: ExportRequest) -> Result<Vec<String>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let creators = query_all(
+        &conn,
+        "SELECT id, name, created_at FROM creators ORDER BY name COLLATE NOCASE, id",
+        |row| {
+            Ok(ExportedCreator {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        },
+    )?;
+    let sources = query_all(
+        &conn,
+        "SELECT s.id, s.creator_id, c.name, s.platform, s.channel_url, s.channel_name, s.channel_id, s.status,
+                s.credential_id, cr.label
+         FROM sources s
+         JOIN creators c ON c.id = s.creator_id
+         LEFT JOIN credentials cr ON cr.id = s.credential_id
+         ORDER BY c.name COLLATE NOCASE, s.creator_id, s.channel_url",
+        |row| {
+            Ok(ExportedSource {
+                id: row.get(0)?,
+                creator_id: row.get(1)?,
+                creator_name: row.get(2)?,
+                platform: row.get(3)?,
+                channel_url: row.get(4)?,
+                channel_name: row.get(5)?,
+                channel_id: row.get(6)?,
+                status: row.get(7)?,
+                credential_id: row.get(8)?,
+                credential_label: row.get(9)?,
+            })
+        },
+    )?;
+    let credentials = query_all(
+        &conn,
+        "SELECT id, label, platform, is_default FROM credentials ORDER BY platform, label, id",
+        |row| {
+            Ok(ExportedCredential {
+                id: row.get(0)?,
+                label: row.get(1)?,
+                platform: row.get(2)?,
+                is_default: row.get::<_, i32>(3)? != 0,
+            })
+        },
+    )?;
+
+    let feed_items = if request.include_feed_items {
+        Some(query_all(
+            &conn,
+            "SELECT id, source_id, external_id, title, webpage_url, published_at, duration, content_kind,
+                    download_status
+             FROM feed_items ORDER BY source_id, published_at, external_id",
+            |row| {
+                Ok(ExportedFeedItem {
+                    id: row.get(0)?,
+                    source_id: row.get(1)?,
+                    external_id: row.get(2)?,
+                    title: row.get(3)?,
+                    url: row.get(4)?,
+                    published_at: row.get(5)?,
+                    duration: row.get(6)?,
+                    content_kind: row.get(7)?,
+                    download_status: row.get(8)?,
+                })
+            },
+        )?)
+    } else {
+        None
+    };
+    let warehouse_items = if request.include_warehouse_items {
+        Some(query_all(
+            &conn,
+            "SELECT id, creator_id, feed_item_id, title, platform, original_url, published_at, duration,
+                    file_path, file_size, imported_at
+             FROM warehouse_items ORDER BY creator_id, imported_at, id",
+            |row| {
+                Ok(ExportedWarehouseItem {
+                    id: row.get(0)?,
+                    creator_id: row.get(1)?,
+                    feed_item_id: row.get(2)?,
+                    title: row.get(3)?,
+                    platform: row.get(4)?,
+                    original_url: row.get(5)?,
+                    published_at: row.get(6)?,
+                    duration: row.get(7)?,
+                    file_path: row.get(8)?,
+                    file_size: row.get(9)?,
+                    imported_at: row.get(10)?,
+                })
+            },
+        )?)
+    } else {
+        None
+    };
+
+    match request.format.as_str() {
+        "opml" => {
+            if feed_items.is_some() || warehouse_items.is_some() {
+                return Err("Feed and warehouse lists can only be exported as JSON or CSV".to_string());
+            }
+            std::fs::write(&request.path, opml(&creators, &sources))
+                .map_err(|e| format!("Failed to write {}: {}", request.path, e))?;
+            Ok(vec![request.path])
+        }
+        "json" => {
+            let export = LibraryExport {
+                app_version: env!("CARGO_PKG_VERSION"),
+                exported_at: Utc::now().to_rfc3339(),
+                creators,
+                sources,
+                credentials,
+                feed_items,
+                warehouse_items,
+            };
+            let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+            std::fs::write(&request.path, json).map_err(|e| format!("Failed to write {}: {}", request.path, e))?;
+            Ok(vec![request.path])
+        }
+        "csv" => {
+            let mut written = vec![request.path.clone()];
+            write_csv(&request.path, &sources)?;
+            let path = sibling_path(&request.path, "creators");
+            write_csv(&path, &creators)?;
+            written.push(path);
+            let path = sibling_path(&request.path, "credentials");
+            write_csv(&path, &credentials)?;
+            written.push(path);
+            if let Some(feed_items) = feed_items {
+                let path = sibling_path(&request.path, "feed_items");
+                write_csv(&path, &feed_items)?;
+                written.push(path);
+            }
+            if let Some(warehouse_items) = warehouse_items {
+                let path = sibling_path(&request.path, "warehouse_items");
+                write_csv(&path, &warehouse_items)?;
+                written.push(path);
+            }
+            Ok(written)
+        }
+        other => Err(format!("Unknown export format: {}", other)),
+    }
+}
+
+fn query_all<T>(
+    conn: &Connection,
+    sql: &str,
+    map: impl FnMut(&Row) -> rusqlite::Result<T>,
+) -> Result<Vec<T>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], map)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(rows)
+}
+
+/// One outline per creator holding an outline per source. Source outlines carry
+/// the creator's name as their title, so importing the file regroups them.
+fn opml(creators: &[ExportedCreator], sources: &[ExportedSource]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+    xml.push_str("  <head>\n    <title>N3Ms Media Library</title>\n");
+    xml.push_str(&format!("    <dateCreated>{}</dateCreated>\n  </head>\n  <body>\n", Utc::now().to_rfc2822()));
+
+    for creator in creators {
+        xml.push_str(&format!("    <outline text=\"{}\">\n", escape_xml(&creator.name)));
+        for source in sources.iter().filter(|s| s.creator_id == creator.id) {
+            // Feed readers can follow RSS sources and validated YouTube channels
+            let feed_url = match (source.platform.as_str(), &source.channel_id) {
+                ("rss", _) => Some(source.channel_url.clone()),
+                ("youtube", Some(channel_id)) if channel_id.starts_with("UC") => {
+                    Some(format!("https://www.youtube.com/feeds/videos.xml?channel_id={}", channel_id))
+                }
+                _ => None,
+            };
+
+            let name = escape_xml(&creator.name);
+            xml.push_str(&format!("      <outline text=\"{}\" title=\"{}\"", name, name));
+            match feed_url {
+                Some(feed_url) => xml.push_str(&format!(" type=\"rss\" xmlUrl=\"{}\"", escape_xml(&feed_url))),
+                None => xml.push_str(" type=\"link\""),
+            }
+            if source.platform != "rss" {
+                xml.push_str(&format!(" htmlUrl=\"{}\"", escape_xml(&source.channel_url)));
+            }
+            xml.push_str(&format!(" platform=\"{}\"", escape_xml(&source.platform)));
+            if let Some(channel_name) = &source.channel_name {
+                xml.push_str(&format!(" channelName=\"{}\"", escape_xml(channel_name)));
+            }
+            if let Some(label) = &source.credential_label {
+                xml.push_str(&format!(" credential=\"{}\"", escape_xml(label)));
+            }
+            xml.push_str("/>\n");
+        }
+        xml.push_str("    </outline>\n");
+    }
+
+    xml.push_str("  </body>\n</opml>\n");
+    xml
+}
+
+fn write_csv<T: Serialize>(path: &str, rows: &[T]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    for row in rows {
+        writer.serialize(row).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// `library.csv` -> `library.feed_items.csv`
+fn sibling_path(path: &str, suffix: &str) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!("{}.{}.csv", stem, suffix))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::subscriptions::{parse_subscriptions, SubscriptionFormat};
+    use uuid::Uuid;
+
+    fn source(
+        creator: &ExportedCreator,
+        platform: &str,
+        channel_url: &str,
+        channel_id: Option<&str>,
+    ) -> ExportedSource {
+        ExportedSource {
+            id: Uuid::new_v4().to_string(),
+            creator_id: creator.id.clone(),
+            creator_name: creator.name.clone(),
+            platform: platform.to_string(),
+            channel_url: channel_url.to_string(),
+            channel_name: None,
+            channel_id: channel_id.map(str::to_string),
+            status: "active".to_string(),
+            credential_id: None,
+            credential_label: None,
+        }
+    }
+
+    #[test]
+    fn opml_export_imports_back() {
+        let creator = |name: &str| ExportedCreator {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let creators = vec![creator("Tom & Jerry"), creator("Zed")];
+        let sources = vec![
+            source(
+                &creators[0],
+                "youtube",
+                "https://www.youtube.com/channel/UCabc123/shorts",
+                Some("UCabc123"),
+            ),
+            source(
+                &creators[0],
+                "youtube",
+                "https://www.youtube.com/playlist?list=PLxyz&si=1",
+                Some("UCabc123"),
+            ),
+            source(&creators[0], "patreon", "https://www.patreon.com/tomandjerry", None),
+            source(&creators[1], "rss", "https://zed.example/feed.xml", None),
+            source(&creators[1], "generic", "https://zed.example/videos", None),
+        ];
+
+        let imported = parse_subscriptions(&opml(&creators, &sources), SubscriptionFormat::Opml).unwrap();
+
+        let imported: Vec<_> = imported
+            .iter()
+            .map(|s| (s.name.as_deref(), s.platform, s.channel_url.as_str()))
+            .collect();
+        assert_eq!(
+            imported,
+            vec![
+                (Some("Tom & Jerry"), "youtube", "https://www.youtube.com/channel/UCabc123/shorts"),
+                (Some("Tom & Jerry"), "youtube", "https://www.youtube.com/playlist?list=PLxyz&si=1"),
+                (Some("Tom & Jerry"), "patreon", "https://www.patreon.com/tomandjerry"),
+                (Some("Zed"), "rss", "https://zed.example/feed.xml"),
+                (Some("Zed"), "generic", "https://zed.example/videos"),
+            ]
+        );
+    }
+}
@@ -1,36 +1,74 @@
 use crate::db::Database;
-use crate::models::feed_item::{CreateFeedItemRequest, FeedItem, FeedItemCounts, UpdateFeedItemRequest};
+use crate::models::feed_item::{
+    CreateFeedItemRequest, FeedItem, FeedItemChapter, FeedItemCounts, UpdateFeedItemRequest,
+};
 use chrono::Utc;
 use tauri::State;
 use uuid::Uuid;
 
+/// Columns read by `feed_item_from_row`, in order. Queries alias feed_items as `fi`.
+pub const FEED_ITEM_COLUMNS: &str = "fi.id, fi.source_id, fi.external_id, fi.title, fi.thumbnail_url, fi.published_at, fi.duration, fi.download_status, fi.warehouse_item_id, fi.metadata_complete, fi.created_at,
+     fi.metadata_attempts, fi.metadata_last_error, fi.metadata_next_attempt_at, fi.metadata_unavailable,
+     fi.description, fi.tags, fi.categories, fi.view_count, fi.like_count, fi.resolutions, fi.is_live, fi.is_short,
+     fi.metadata_fetched_at, fi.webpage_url, fi.content_kind, fi.game, fi.expires_at, fi.media,
+     fi.locked, fi.required_tier";
+
+pub fn feed_item_from_row(row: &rusqlite::Row) -> rusqlite::Result<FeedItem> {
+    Ok(FeedItem {
+        id: row.get(0)?,
+        source_id: row.get(1)?,
+        external_id: row.get(2)?,
+        title: row.get(3)?,
+        thumbnail_url: row.get(4)?,
+        published_at: row.get(5)?,
+        duration: row.get(6)?,
+        download_status: row.get(7)?,
+        warehouse_item_id: row.get(8)?,
+        metadata_complete: row.get(9)?,
+        created_at: row.get(10)?,
+        metadata_attempts: row.get(11)?,
+        metadata_last_error: row.get(12)?,
+        metadata_next_attempt_at: row.get(13)?,
+        metadata_unavailable: row.get(14)?,
+        description: row.get(15)?,
+        tags: json_list(row.get(16)?),
+        categories: json_list(row.get(17)?),
+        view_count: row.get(18)?,
+        like_count: row.get(19)?,
+        resolutions: json_list(row.get(20)?),
+        is_live: row.get(21)?,
+        is_short: row.get(22)?,
+        metadata_fetched_at: row.get(23)?,
+        webpage_url: row.get(24)?,
+        content_kind: row.get(25)?,
+        game: row.get(26)?,
+        expires_at: row.get(27)?,
+        media: json_list(row.get(28)?),
+        locked: row.get(29)?,
+        required_tier: row.get(30)?,
+    })
+}
+
+/// Parse a JSON array column; missing or malformed values read as empty
+fn json_list<T: serde::de::DeserializeOwned>(value: Option<String>) -> Vec<T> {
+    value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
 #[tauri::command]
 pub fn get_feed_items_by_source(db: State<Database>, source_id: String) -> Result<Vec<FeedItem>, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, source_id, external_id, title, thumbnail_url, published_at, duration, download_status, warehouse_item_id, metadata_complete, created_at
-             FROM feed_items WHERE source_id = ? ORDER BY published_at DESC"
-        )
+        .prepare(&format!(
+            "SELECT {} FROM feed_items fi WHERE fi.source_id = ? ORDER BY fi.published_at DESC",
+            FEED_ITEM_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let items = stmt
-        .query_map([&source_id], |row| {
-            Ok(FeedItem {
-                id: row.get(0)?,
-                source_id: row.get(1)?,
-                external_id: row.get(2)?,
-                title: row.get(3)?,
-                thumbnail_url: row.get(4)?,
-                published_at: row.get(5)?,
-                duration: row.get(6)?,
-                download_status: row.get(7)?,
-                warehouse_item_id: row.get(8)?,
-                metadata_complete: row.get(9)?,
-                created_at: row.get(10)?,
-            })
-        })
+        .query_map([&source_id], feed_item_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -43,31 +81,18 @@ pub fn get_feed_items_by_creator(db: State<Database>, creator_id: String) -> Res
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT fi.id, fi.source_id, fi.external_id, fi.title, fi.thumbnail_url, fi.published_at, fi.duration, fi.download_status, fi.warehouse_item_id, fi.metadata_complete, fi.created_at
+        .prepare(&format!(
+            "SELECT {}
              FROM feed_items fi
              JOIN sources s ON fi.source_id = s.id
              WHERE s.creator_id = ?
-             ORDER BY fi.published_at DESC"
-        )
+             ORDER BY fi.published_at DESC",
+            FEED_ITEM_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let items = stmt
-        .query_map([&creator_id], |row| {
-            Ok(FeedItem {
-                id: row.get(0)?,
-                source_id: row.get(1)?,
-                external_id: row.get(2)?,
-                title: row.get(3)?,
-                thumbnail_url: row.get(4)?,
-                published_at: row.get(5)?,
-                duration: row.get(6)?,
-                download_status: row.get(7)?,
-                warehouse_item_id: row.get(8)?,
-                metadata_complete: row.get(9)?,
-                created_at: row.get(10)?,
-            })
-        })
+        .query_map([&creator_id], feed_item_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -101,6 +126,26 @@ pub fn create_feed_item(db: State<Database>, request: CreateFeedItemRequest) ->
         warehouse_item_id: None,
         metadata_complete: false,
         created_at: now,
+        metadata_attempts: 0,
+        metadata_last_error: None,
+        metadata_next_attempt_at: None,
+        metadata_unavailable: false,
+        description: None,
+        tags: Vec::new(),
+        categories: Vec::new(),
+        view_count: None,
+        like_count: None,
+        resolutions: Vec::new(),
+        is_live: false,
+        is_short: false,
+        metadata_fetched_at: None,
+        webpage_url: None,
+        content_kind: None,
+        game: None,
+        expires_at: None,
+        media: Vec::new(),
+        locked: false,
+        required_tier: None,
     })
 }
 
@@ -135,24 +180,9 @@ pub fn update_feed_item(db: State<Database>, id: String, request: UpdateFeedItem
     // Get current feed item
     let mut item = conn
         .query_row(
-            "SELECT id, source_id, external_id, title, thumbnail_url, published_at, duration, download_status, warehouse_item_id, metadata_complete, created_at
-             FROM feed_items WHERE id = ?",
+            &format!("SELECT {} FROM feed_items fi WHERE fi.id = ?", FEED_ITEM_COLUMNS),
             [&id],
-            |row| {
-                Ok(FeedItem {
-                    id: row.get(0)?,
-                    source_id: row.get(1)?,
-                    external_id: row.get(2)?,
-                    title: row.get(3)?,
-                    thumbnail_url: row.get(4)?,
-                    published_at: row.get(5)?,
-                    duration: row.get(6)?,
-                    download_status: row.get(7)?,
-                    warehouse_item_id: row.get(8)?,
-                    metadata_complete: row.get(9)?,
-                    created_at: row.get(10)?,
-                })
-            },
+            feed_item_from_row,
         )
         .map_err(|e| e.to_string())?;
 
@@ -199,3 +229,33 @@ pub fn get_feed_item_counts(db: State<Database>, creator_id: String) -> Result<F
         not_downloaded: (total - downloaded) as i32,
     })
 }
+
+/// Get the chapters of a feed item in playback order
+#[tauri::command]
+pub fn get_feed_item_chapters(db: State<Database>, feed_item_id: String) -> Result<Vec<FeedItemChapter>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, feed_item_id, position, title, start_time, end_time
+             FROM feed_item_chapters WHERE feed_item_id = ? ORDER BY position",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let chapters = stmt
+        .query_map([&feed_item_id], |row| {
+            Ok(FeedItemChapter {
+                id: row.get(0)?,
+                feed_item_id: row.get(1)?,
+                position: row.get(2)?,
+                title: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(chapters)
+}
@@ -0,0 +1,310 @@
+use crate::commands::notifications::notify_hook_failed;
+use crate::commands::settings::load_settings;
+use crate::commands::warehouse::{apply_verification, store_verification, verify_file};
+use crate::db::Database;
+use crate::models::hook::{CreateHookRequest, Hook, HookRun, UpdateHookRequest};
+use crate::models::WarehouseItem;
+use crate::services::get_ffmpeg_path;
+use crate::services::hooks::run_hook;
+use chrono::Utc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+const HOOK_TYPES: &[&str] = &["command", "ffmpeg"];
+
+fn hook_from_row(row: &rusqlite::Row) -> rusqlite::Result<Hook> {
+    Ok(Hook {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        hook_type: row.get(2)?,
+        command: row.get(3)?,
+        run_on_download: row.get::<_, i32>(4)? != 0,
+        run_on_import: row.get::<_, i32>(5)? != 0,
+        enabled: row.get::<_, i32>(6)? != 0,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn validate_hook(hook: &Hook) -> Result<(), String> {
+    if !HOOK_TYPES.contains(&hook.hook_type.as_str()) {
+        return Err(format!("Unknown hook type: {}", hook.hook_type));
+    }
+    if hook.name.trim().is_empty() {
+        return Err("Hook name is required".to_string());
+    }
+    if hook.command.trim().is_empty() {
+        return Err("Hook command is required".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_hooks(db: State<Database>) -> Result<Vec<Hook>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, hook_type, command, run_on_download, run_on_import, enabled, created_at, updated_at
+             FROM hooks ORDER BY created_at",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let hooks = stmt
+        .query_map([], hook_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(hooks)
+}
+
+#[tauri::command]
+pub fn create_hook(db: State<Database>, request: CreateHookRequest) -> Result<Hook, String> {
+    let now = Utc::now().to_rfc3339();
+    let hook = Hook {
+        id: Uuid::new_v4().to_string(),
+        name: request.name.trim().to_string(),
+        hook_type: request.hook_type,
+        command: request.command.trim().to_string(),
+        run_on_download: request.run_on_download.unwrap_or(true),
+        run_on_import: request.run_on_import.unwrap_or(false),
+        enabled: request.enabled.unwrap_or(true),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    validate_hook(&hook)?;
+
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO hooks (id, name, hook_type, command, run_on_download, run_on_import, enabled, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        (
+            &hook.id,
+            &hook.name,
+            &hook.hook_type,
+            &hook.command,
+            hook.run_on_download as i32,
+            hook.run_on_import as i32,
+            hook.enabled as i32,
+            &hook.created_at,
+            &hook.updated_at,
+        ),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(hook)
+}
+
+#[tauri::command]
+pub fn update_hook(db: State<Database>, id: String, request: UpdateHookRequest) -> Result<Hook, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut hook = conn
+        .query_row(
+            "SELECT id, name, hook_type, command, run_on_download, run_on_import, enabled, created_at, updated_at
+             FROM hooks WHERE id = ?",
+            [&id],
+            hook_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    if let Some(name) = request.name {
+        hook.name = name.trim().to_string();
+    }
+    if let Some(hook_type) = request.hook_type {
+        hook.hook_type = hook_type;
+    }
+    if let Some(command) = request.command {
+        hook.command = command.trim().to_string();
+    }
+    if let Some(run_on_download) = request.run_on_download {
+        hook.run_on_download = run_on_download;
+    }
+    if let Some(run_on_import) = request.run_on_import {
+        hook.run_on_import = run_on_import;
+    }
+    if let Some(enabled) = request.enabled {
+        hook.enabled = enabled;
+    }
+    validate_hook(&hook)?;
+
+    hook.updated_at = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE hooks SET name = ?, hook_type = ?, command = ?, run_on_download = ?, run_on_import = ?, enabled = ?, updated_at = ?
+         WHERE id = ?",
+        (
+            &hook.name,
+            &hook.hook_type,
+            &hook.command,
+            hook.run_on_download as i32,
+            hook.run_on_import as i32,
+            hook.enabled as i32,
+            &hook.updated_at,
+            &id,
+        ),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(hook)
+}
+
+#[tauri::command]
+pub fn delete_hook(db: State<Database>, id: String) -> Result<(), String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM hook_runs WHERE hook_id = ?", [&id])
+        .map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM hooks WHERE id = ?", [&id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Get the hook runs recorded for a warehouse item, newest first
+#[tauri::command]
+pub fn get_hook_runs(db: State<Database>, warehouse_item_id: String) -> Result<Vec<HookRun>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, hook_id, hook_name, warehouse_item_id, trigger_event, success, exit_code, output, started_at, finished_at
+             FROM hook_runs WHERE warehouse_item_id = ? ORDER BY started_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let runs = stmt
+        .query_map([&warehouse_item_id], |row| {
+            Ok(HookRun {
+                id: row.get(0)?,
+                hook_id: row.get(1)?,
+                hook_name: row.get(2)?,
+                warehouse_item_id: row.get(3)?,
+                trigger: row.get(4)?,
+                success: row.get::<_, i32>(5)? != 0,
+                exit_code: row.get(6)?,
+                output: row.get(7)?,
+                started_at: row.get(8)?,
+                finished_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(runs)
+}
+
+/// Run the enabled hooks for a trigger ("download" or "import") in the background.
+/// A failing hook is recorded and reported as a warning; the item is kept either way.
+pub fn spawn_item_hooks(app_handle: &AppHandle, item: &WarehouseItem, trigger: &'static str) {
+    let app_handle = app_handle.clone();
+    let item = item.clone();
+    tauri::async_runtime::spawn_blocking(move || run_item_hooks(&app_handle, item, trigger));
+}
+
+fn run_item_hooks(app_handle: &AppHandle, mut item: WarehouseItem, trigger: &str) {
+    let hooks = {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let column = if trigger == "import" { "run_on_import" } else { "run_on_download" };
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT id, name, hook_type, command, run_on_download, run_on_import, enabled, created_at, updated_at
+             FROM hooks WHERE enabled = 1 AND {} = 1 ORDER BY created_at",
+            column
+        )) {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+
+        let hooks: Vec<Hook> = stmt
+            .query_map([], hook_from_row)
+            .ok()
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default();
+        hooks
+    };
+
+    if hooks.is_empty() {
+        return;
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(app_handle).ok();
+    let timeout = {
+        let db = app_handle.state::<Database>();
+        let minutes = db
+            .conn
+            .lock()
+            .ok()
+            .and_then(|conn| load_settings(&conn).ok())
+            .map(|settings| settings.hook_timeout_minutes)
+            .unwrap_or(0);
+        (minutes > 0).then(|| Duration::from_secs(minutes as u64 * 60))
+    };
+
+    // Hooks run in order, so a later hook sees the file an earlier one produced
+    for hook in hooks {
+        let started_at = Utc::now().to_rfc3339();
+        let outcome = run_hook(&hook, &item, trigger, ffmpeg_path.as_deref(), timeout);
+
+        // A replaced file may have a new codec, resolution or length, so probe it
+        // again (outside the database lock)
+        if let Some(file_size) = outcome.new_file_size {
+            item.file_size = file_size;
+            let verification = verify_file(app_handle, &item.file_path);
+            apply_verification(&mut item, verification);
+        }
+
+        let run = HookRun {
+            id: Uuid::new_v4().to_string(),
+            hook_id: hook.id.clone(),
+            hook_name: hook.name.clone(),
+            warehouse_item_id: item.id.clone(),
+            trigger: trigger.to_string(),
+            success: outcome.success,
+            exit_code: outcome.exit_code,
+            output: outcome.output,
+            started_at,
+            finished_at: Utc::now().to_rfc3339(),
+        };
+
+        {
+            let db = app_handle.state::<Database>();
+            let conn = db.conn.lock();
+            if let Ok(conn) = conn {
+                let _ = conn.execute(
+                    "INSERT INTO hook_runs (id, hook_id, hook_name, warehouse_item_id, trigger_event, success, exit_code, output, started_at, finished_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    (
+                        &run.id,
+                        &run.hook_id,
+                        &run.hook_name,
+                        &run.warehouse_item_id,
+                        &run.trigger,
+                        run.success as i32,
+                        &run.exit_code,
+                        &run.output,
+                        &run.started_at,
+                        &run.finished_at,
+                    ),
+                );
+
+                if outcome.new_file_size.is_some() {
+                    let _ = store_verification(&conn, &item);
+                }
+            }
+        }
+
+        if !run.success {
+            notify_hook_failed(app_handle, &hook.name, &item.title);
+            let _ = app_handle.emit("hook_failed", run);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::commands::sources::spawn_validation;
+use crate::db::Database;
+use crate::services::subscriptions::{check_url, name_from_url, parse_subscriptions, url_key, youtube_channel_id, SubscriptionFormat};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// What an import does (or would do) with one entry of the list
+#[derive(Debug, Serialize)]
+pub struct ImportEntry {
+    pub name: String,
+    pub platform: String,
+    pub channel_url: String,
+    /// "create", "duplicate" or "invalid"
+    pub action: String,
+    /// Existing creator with the same name, which the source is added to
+    pub existing_creator_id: Option<String>,
+    /// Why the entry is skipped
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    /// "opml", "takeout_csv" or "url_list"
+    pub format: String,
+    /// True when nothing was written and the counts are what an import would create
+    pub dry_run: bool,
+    pub entries: Vec<ImportEntry>,
+    pub creators_created: usize,
+    pub sources_created: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+}
+
+/// Import creators and sources from an OPML file, a Takeout `subscriptions.csv`
+/// or a list of URLs. Sources whose channel URL (or YouTube channel ID) is already
+/// in the library are skipped, and a creator is reused when one has the same name.
+/// With `dry_run` the report is built without writing anything. Created sources
+/// are validated in the background once the import is saved.
+#[tauri::command]
+pub fn import_subscriptions(
+    app_handle: AppHandle,
+    db: State<Database>,
+    path: String,
+    dry_run: bool,
+) -> Result<ImportReport, String> {
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let format = SubscriptionFormat::detect(&path, &content);
+    let subscriptions = parse_subscriptions(&content, format)?;
+
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut known_urls = HashSet::new();
+    let mut known_channel_ids = HashSet::new();
+    {
+        let mut stmt = conn
+            .prepare("SELECT channel_url, channel_id FROM sources")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))
+            .map_err(|e| e.to_string())?;
+        for (channel_url, channel_id) in rows.flatten() {
+            known_channel_ids.extend(youtube_channel_id(&channel_url).map(str::to_string));
+            known_channel_ids.extend(channel_id);
+            known_urls.insert(url_key(&channel_url));
+        }
+    }
+
+    let mut creators_by_name: HashMap<String, String> = HashMap::new();
+    {
+        let mut stmt = conn.prepare("SELECT id, name FROM creators").map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| e.to_string())?;
+        for (id, name) in rows.flatten() {
+            creators_by_name.entry(name.to_lowercase()).or_insert(id);
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut to_create = Vec::new();
+    for subscription in subscriptions {
+        let name = subscription
+            .name
+            .clone()
+            .unwrap_or_else(|| name_from_url(&subscription.channel_url));
+        let channel_id = youtube_channel_id(&subscription.channel_url).map(str::to_string);
+
+        let (action, message) = if let Some(reason) = check_url(&subscription) {
+            ("invalid", Some(reason))
+        } else if !known_urls.insert(url_key(&subscription.channel_url))
+            || channel_id.as_ref().is_some_and(|id| known_channel_ids.contains(id))
+        {
+            ("duplicate", Some("Already a source".to_string()))
+        } else {
+            known_channel_ids.extend(channel_id);
+            to_create.push(entries.len());
+            ("create", None)
+        };
+
+        entries.push(ImportEntry {
+            existing_creator_id: creators_by_name.get(&name.to_lowercase()).cloned(),
+            name,
+            platform: subscription.platform.to_string(),
+            channel_url: subscription.channel_url,
+            action: action.to_string(),
+            message,
+        });
+    }
+
+    // Entries sharing a name that no creator has yet get one new creator
+    let new_creators: HashSet<String> = to_create
+        .iter()
+        .map(|&i| &entries[i])
+        .filter(|entry| entry.existing_creator_id.is_none())
+        .map(|entry| entry.name.to_lowercase())
+        .collect();
+
+    let report = ImportReport {
+        format: format.as_str().to_string(),
+        dry_run,
+        creators_created: new_creators.len(),
+        sources_created: to_create.len(),
+        duplicates: entries.iter().filter(|e| e.action == "duplicate").count(),
+        invalid: entries.iter().filter(|e| e.action == "invalid").count(),
+        entries,
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    // All or nothing, so a failed import can simply be run again
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let now = Utc::now().to_rfc3339();
+    let mut source_ids = Vec::new();
+    for &i in &to_create {
+        let entry = &report.entries[i];
+        let creator_id = match creators_by_name.get(&entry.name.to_lowercase()) {
+            Some(id) => id.clone(),
+            None => {
+                let id = Uuid::new_v4().to_string();
+                tx.execute(
+                    "INSERT INTO creators (id, name, photo_path, created_at, updated_at) VALUES (?, ?, NULL, ?, ?)",
+                    (&id, &entry.name, &now, &now),
+                )
+                .map_err(|e| e.to_string())?;
+                creators_by_name.insert(entry.name.to_lowercase(), id.clone());
+                id
+            }
+        };
+
+        let source_id = Uuid::new_v4().to_string();
+        tx.execute(
+            "INSERT INTO sources (id, creator_id, platform, channel_url, channel_name, status, created_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', ?)",
+            (&source_id, &creator_id, &entry.platform, &entry.channel_url, &entry.name, &now),
+        )
+        .map_err(|e| e.to_string())?;
+        source_ids.push(source_id);
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    drop(conn);
+
+    spawn_validation(&app_handle, source_ids);
+
+    Ok(report)
+}
@@ -1,13 +1,33 @@
+use crate::workers::metadata_queue::{MetadataPriority, MetadataQueueStatus};
 use crate::workers::MetadataWorker;
 use tauri::{AppHandle, State};
 
-/// Fetch metadata for specific feed items immediately (high priority)
+/// Queue metadata fetches for specific feed items ahead of the background scan.
+/// Defaults to the user-requested tier; pass "visible" for items on screen.
 #[tauri::command]
 pub fn fetch_feed_items_metadata(
     metadata_worker: State<MetadataWorker>,
     feed_item_ids: Vec<String>,
+    priority: Option<MetadataPriority>,
 ) -> Result<(), String> {
-    metadata_worker.fetch_immediate(feed_item_ids)
+    metadata_worker.enqueue(feed_item_ids, priority.unwrap_or(MetadataPriority::Requested))
+}
+
+/// Clear the retry state of feed items (including metadata_unavailable ones) and fetch them again
+#[tauri::command]
+pub fn retry_feed_items_metadata(
+    app_handle: AppHandle,
+    metadata_worker: State<MetadataWorker>,
+    feed_item_ids: Vec<String>,
+) -> Result<(), String> {
+    MetadataWorker::reset_failures(&app_handle, &feed_item_ids)?;
+    metadata_worker.enqueue(feed_item_ids, MetadataPriority::Requested)
+}
+
+/// Report the items waiting in each tier of the metadata queue
+#[tauri::command]
+pub fn get_metadata_queue_status(metadata_worker: State<MetadataWorker>) -> Result<MetadataQueueStatus, String> {
+    metadata_worker.queue_status()
 }
 
 /// Get feed items with incomplete metadata for a specific creator
@@ -25,7 +45,7 @@ pub fn get_incomplete_metadata_items(
     ))
 }
 
-/// Pause the background metadata worker
+/// Pause background metadata fetching (visible and requested items still run)
 #[tauri::command]
 pub fn pause_metadata_worker(metadata_worker: State<MetadataWorker>) -> Result<(), String> {
     metadata_worker.pause()
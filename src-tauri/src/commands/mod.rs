@@ -1,7 +1,11 @@
+pub mod backup;
 pub mod creators;
 pub mod credentials;
 pub mod download;
+pub mod export;
 pub mod feed_items;
+pub mod hooks;
+pub mod import;
 pub mod metadata;
 pub mod notifications;
 pub mod search;
@@ -11,10 +15,14 @@ pub mod sources;
 pub mod sync;
 pub mod warehouse;
 
+pub use backup::*;
 pub use creators::*;
 pub use credentials::*;
 pub use download::*;
+pub use export::*;
 pub use feed_items::*;
+pub use hooks::*;
+pub use import::*;
 pub use metadata::*;
 pub use notifications::*;
 pub use search::*;
@@ -82,6 +82,25 @@ pub fn notify_download_failed(app: &AppHandle, video_title: &str, error: &str) {
         .show();
 }
 
+/// Send a notification for a post-processing hook that failed
+pub fn notify_hook_failed(app: &AppHandle, hook_name: &str, video_title: &str) {
+    let db = match app.try_state::<Database>() {
+        Some(db) => db,
+        None => return,
+    };
+
+    if !notifications_enabled(&db) {
+        return;
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("Hook Failed: {}", hook_name))
+        .body(video_title)
+        .show();
+}
+
 /// Tauri command to check if notification permissions are granted
 #[tauri::command]
 pub async fn check_notification_permission(app: AppHandle) -> Result<String, String> {
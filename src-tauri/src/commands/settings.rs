@@ -1,6 +1,7 @@
 use crate::db::Database;
 use crate::models::app_settings::{AppSettings, UpdateAppSettingsRequest};
-use rusqlite::OptionalExtension;
+use crate::services::{rate_limit, refresh_policy, sponsorblock};
+use rusqlite::{Connection, OptionalExtension};
 use std::path::PathBuf;
 use tauri::State;
 
@@ -29,12 +30,12 @@ pub fn initialize_settings(db: &Database, library_path: PathBuf) -> Result<(), S
     Ok(())
 }
 
-#[tauri::command]
-pub fn get_app_settings(db: State<Database>) -> Result<AppSettings, String> {
-    let conn = db.conn.lock().map_err(|e| e.to_string())?;
-
+/// Read the settings row
+pub fn load_settings(conn: &Connection) -> Result<AppSettings, rusqlite::Error> {
     conn.query_row(
-        "SELECT library_path, default_quality, sync_interval_seconds, theme, first_run_completed, notifications_enabled, bass_boost_preset, bass_boost_custom_gain FROM app_settings WHERE id = 1",
+        "SELECT library_path, default_quality, sync_interval_seconds, theme, first_run_completed, notifications_enabled, bass_boost_preset, bass_boost_custom_gain,
+                sponsorblock_enabled, sponsorblock_mark_categories, sponsorblock_remove_categories, sponsorblock_api_url, embed_metadata, write_nfo_files, write_info_json, partial_download_max_age_days, metadata_requests_per_minute, metadata_refresh_policy, twitch_vod_retention_days, backup_interval_hours, backup_keep_count, backup_include_thumbnails, backup_directory, hook_timeout_minutes
+         FROM app_settings WHERE id = 1",
         [],
         |row| {
             Ok(AppSettings {
@@ -46,10 +47,32 @@ pub fn get_app_settings(db: State<Database>) -> Result<AppSettings, String> {
                 notifications_enabled: row.get::<_, i64>(5)? != 0,
                 bass_boost_preset: row.get(6)?,
                 bass_boost_custom_gain: row.get(7)?,
+                sponsorblock_enabled: row.get::<_, i64>(8)? != 0,
+                sponsorblock_mark_categories: row.get(9)?,
+                sponsorblock_remove_categories: row.get(10)?,
+                sponsorblock_api_url: row.get(11)?,
+                embed_metadata: row.get::<_, i64>(12)? != 0,
+                write_nfo_files: row.get::<_, i64>(13)? != 0,
+                write_info_json: row.get::<_, i64>(14)? != 0,
+                partial_download_max_age_days: row.get(15)?,
+                metadata_requests_per_minute: row.get(16)?,
+                metadata_refresh_policy: row.get(17)?,
+                twitch_vod_retention_days: row.get(18)?,
+                backup_interval_hours: row.get(19)?,
+                backup_keep_count: row.get(20)?,
+                backup_include_thumbnails: row.get::<_, i64>(21)? != 0,
+                backup_directory: row.get(22)?,
+                hook_timeout_minutes: row.get(23)?,
             })
         },
     )
-    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_app_settings(db: State<Database>) -> Result<AppSettings, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    load_settings(&conn).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -60,24 +83,7 @@ pub fn update_app_settings(
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     // Get current settings
-    let mut settings = conn
-        .query_row(
-            "SELECT library_path, default_quality, sync_interval_seconds, theme, first_run_completed, notifications_enabled, bass_boost_preset, bass_boost_custom_gain FROM app_settings WHERE id = 1",
-            [],
-            |row| {
-                Ok(AppSettings {
-                    library_path: row.get(0)?,
-                    default_quality: row.get(1)?,
-                    sync_interval_seconds: row.get(2)?,
-                    theme: row.get(3)?,
-                    first_run_completed: row.get::<_, i64>(4)? != 0,
-                    notifications_enabled: row.get::<_, i64>(5)? != 0,
-                    bass_boost_preset: row.get(6)?,
-                    bass_boost_custom_gain: row.get(7)?,
-                })
-            },
-        )
-        .map_err(|e| e.to_string())?;
+    let mut settings = load_settings(&conn).map_err(|e| e.to_string())?;
 
     // Update fields if provided
     if let Some(library_path) = request.library_path {
@@ -104,11 +110,62 @@ pub fn update_app_settings(
     if let Some(bass_boost_custom_gain) = request.bass_boost_custom_gain {
         settings.bass_boost_custom_gain = bass_boost_custom_gain;
     }
+    if let Some(sponsorblock_enabled) = request.sponsorblock_enabled {
+        settings.sponsorblock_enabled = sponsorblock_enabled;
+    }
+    if let Some(categories) = request.sponsorblock_mark_categories {
+        settings.sponsorblock_mark_categories = sponsorblock::normalize_categories(&categories, sponsorblock::CATEGORIES)?;
+    }
+    if let Some(categories) = request.sponsorblock_remove_categories {
+        settings.sponsorblock_remove_categories =
+            sponsorblock::normalize_categories(&categories, sponsorblock::REMOVABLE_CATEGORIES)?;
+    }
+    if let Some(api_url) = request.sponsorblock_api_url {
+        settings.sponsorblock_api_url = api_url.trim().trim_end_matches('/').to_string();
+    }
+    if let Some(embed_metadata) = request.embed_metadata {
+        settings.embed_metadata = embed_metadata;
+    }
+    if let Some(write_nfo_files) = request.write_nfo_files {
+        settings.write_nfo_files = write_nfo_files;
+    }
+    if let Some(write_info_json) = request.write_info_json {
+        settings.write_info_json = write_info_json;
+    }
+    if let Some(partial_download_max_age_days) = request.partial_download_max_age_days {
+        settings.partial_download_max_age_days = partial_download_max_age_days.max(0);
+    }
+    if let Some(limits) = request.metadata_requests_per_minute {
+        settings.metadata_requests_per_minute = rate_limit::normalize_rate_limits(&limits)?;
+    }
+    if let Some(policy) = request.metadata_refresh_policy {
+        settings.metadata_refresh_policy = refresh_policy::normalize_refresh_policy(&policy)?;
+    }
+    if let Some(twitch_vod_retention_days) = request.twitch_vod_retention_days {
+        settings.twitch_vod_retention_days = twitch_vod_retention_days.max(1);
+    }
+    if let Some(backup_interval_hours) = request.backup_interval_hours {
+        settings.backup_interval_hours = backup_interval_hours;
+    }
+    if let Some(backup_keep_count) = request.backup_keep_count {
+        settings.backup_keep_count = backup_keep_count;
+    }
+    if let Some(backup_include_thumbnails) = request.backup_include_thumbnails {
+        settings.backup_include_thumbnails = backup_include_thumbnails;
+    }
+    if let Some(backup_directory) = request.backup_directory {
+        settings.backup_directory = backup_directory;
+    }
+    if let Some(hook_timeout_minutes) = request.hook_timeout_minutes {
+        settings.hook_timeout_minutes = hook_timeout_minutes.max(0);
+    }
 
     // Save to database
     conn.execute(
-        "UPDATE app_settings SET library_path = ?, default_quality = ?, sync_interval_seconds = ?, theme = ?, first_run_completed = ?, notifications_enabled = ?, bass_boost_preset = ?, bass_boost_custom_gain = ? WHERE id = 1",
-        (
+        "UPDATE app_settings SET library_path = ?, default_quality = ?, sync_interval_seconds = ?, theme = ?, first_run_completed = ?, notifications_enabled = ?, bass_boost_preset = ?, bass_boost_custom_gain = ?,
+                sponsorblock_enabled = ?, sponsorblock_mark_categories = ?, sponsorblock_remove_categories = ?, sponsorblock_api_url = ?, embed_metadata = ?, write_nfo_files = ?, write_info_json = ?, partial_download_max_age_days = ?, metadata_requests_per_minute = ?, metadata_refresh_policy = ?, twitch_vod_retention_days = ?, backup_interval_hours = ?, backup_keep_count = ?, backup_include_thumbnails = ?, backup_directory = ?, hook_timeout_minutes = ?
+         WHERE id = 1",
+        rusqlite::params![
             &settings.library_path,
             &settings.default_quality,
             &settings.sync_interval_seconds,
@@ -117,7 +174,23 @@ pub fn update_app_settings(
             if settings.notifications_enabled { 1 } else { 0 },
             &settings.bass_boost_preset,
             &settings.bass_boost_custom_gain,
-        ),
+            if settings.sponsorblock_enabled { 1 } else { 0 },
+            &settings.sponsorblock_mark_categories,
+            &settings.sponsorblock_remove_categories,
+            &settings.sponsorblock_api_url,
+            if settings.embed_metadata { 1 } else { 0 },
+            if settings.write_nfo_files { 1 } else { 0 },
+            if settings.write_info_json { 1 } else { 0 },
+            &settings.partial_download_max_age_days,
+            &settings.metadata_requests_per_minute,
+            &settings.metadata_refresh_policy,
+            &settings.twitch_vod_retention_days,
+            &settings.backup_interval_hours,
+            &settings.backup_keep_count,
+            if settings.backup_include_thumbnails { 1 } else { 0 },
+            &settings.backup_directory,
+            &settings.hook_timeout_minutes,
+        ],
     )
     .map_err(|e| e.to_string())?;
 
@@ -1,34 +1,57 @@
+use crate::commands::credentials::get_cookie_path;
 use crate::db::Database;
 use crate::models::source::{CreateSourceRequest, Source, UpdateSourceRequest};
+use crate::services::platform::{require_cookies, ChannelInfo};
+use crate::services::{get_fetcher, get_ytdlp_path, http};
 use chrono::Utc;
-use tauri::State;
+use rusqlite::{Connection, Row};
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
+const SOURCE_COLUMNS: &str = "id, creator_id, platform, channel_url, channel_name, channel_id, avatar_url, banner_url, \
+     subscriber_count, credential_id, status, status_reason, last_synced_at, created_at";
+
+fn source_from_row(row: &Row) -> rusqlite::Result<Source> {
+    Ok(Source {
+        id: row.get(0)?,
+        creator_id: row.get(1)?,
+        platform: row.get(2)?,
+        channel_url: row.get(3)?,
+        channel_name: row.get(4)?,
+        channel_id: row.get(5)?,
+        avatar_url: row.get(6)?,
+        banner_url: row.get(7)?,
+        subscriber_count: row.get(8)?,
+        credential_id: row.get(9)?,
+        status: row.get(10)?,
+        status_reason: row.get(11)?,
+        last_synced_at: row.get(12)?,
+        created_at: row.get(13)?,
+    })
+}
+
+fn get_source(conn: &Connection, id: &str) -> Result<Source, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM sources WHERE id = ?", SOURCE_COLUMNS),
+        [id],
+        source_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_sources_by_creator(db: State<Database>, creator_id: String) -> Result<Vec<Source>, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, creator_id, platform, channel_url, channel_name, credential_id, status, last_synced_at, created_at
-             FROM sources WHERE creator_id = ? ORDER BY created_at DESC"
-        )
+        .prepare(&format!(
+            "SELECT {} FROM sources WHERE creator_id = ? ORDER BY created_at DESC",
+            SOURCE_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let sources = stmt
-        .query_map([&creator_id], |row| {
-            Ok(Source {
-                id: row.get(0)?,
-                creator_id: row.get(1)?,
-                platform: row.get(2)?,
-                channel_url: row.get(3)?,
-                channel_name: row.get(4)?,
-                credential_id: row.get(5)?,
-                status: row.get(6)?,
-                last_synced_at: row.get(7)?,
-                created_at: row.get(8)?,
-            })
-        })
+        .query_map([&creator_id], source_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -36,31 +59,143 @@ pub fn get_sources_by_creator(db: State<Database>, creator_id: String) -> Result
     Ok(sources)
 }
 
+/// Add a source and validate it right away; validation failures end up in the
+/// returned source's status rather than failing the command
 #[tauri::command]
-pub fn create_source(db: State<Database>, request: CreateSourceRequest) -> Result<Source, String> {
-    let conn = db.conn.lock().map_err(|e| e.to_string())?;
-
+pub async fn create_source(app_handle: AppHandle, request: CreateSourceRequest) -> Result<Source, String> {
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
+    {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT INTO sources (id, creator_id, platform, channel_url, credential_id, status, created_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', ?)",
+            (&id, &request.creator_id, &request.platform, &request.channel_url, &request.credential_id, &now),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    validate(&app_handle, &id).await
+}
+
+/// Resolve a source's channel details again, e.g. after fixing its URL or credential
+#[tauri::command]
+pub async fn validate_source(app_handle: AppHandle, id: String) -> Result<Source, String> {
+    validate(&app_handle, &id).await
+}
+
+/// Validate sources one after another in the background, e.g. after an import
+pub fn spawn_validation(app_handle: &AppHandle, ids: Vec<String>) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        for id in ids {
+            // Failures are stored on the source itself
+            let _ = validate(&app_handle, &id).await;
+        }
+    });
+}
+
+/// Look up the channel behind a source and store its details, moving the source
+/// to "validated", or to "error" with the reason. The channel avatar becomes the
+/// creator's photo when the creator has none.
+async fn validate(app_handle: &AppHandle, id: &str) -> Result<Source, String> {
+    let (source, cookie_path) = {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        let source = get_source(&conn, id)?;
+        let cookie_path = get_cookie_path(&conn, &source.platform, source.credential_id.as_deref());
+        (source, cookie_path)
+    };
+    let ytdlp_path = get_ytdlp_path(app_handle)?;
+
+    let platform = source.platform.clone();
+    let channel_url = source.channel_url.clone();
+    let info = tokio::task::spawn_blocking(move || {
+        let fetcher = get_fetcher(&platform)?;
+        let cookie_path = require_cookies(fetcher, cookie_path)?;
+        fetcher.channel_info(&channel_url, cookie_path.as_deref(), &ytdlp_path)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        match &info {
+            Ok(info) => conn.execute(
+                "UPDATE sources SET channel_id = ?, channel_name = COALESCE(?, channel_name), avatar_url = ?,
+                 banner_url = ?, subscriber_count = ?, status = 'validated', status_reason = NULL
+                 WHERE id = ?",
+                (&info.channel_id, &info.name, &info.avatar_url, &info.banner_url, &info.subscriber_count, id),
+            ),
+            Err(reason) => conn.execute(
+                "UPDATE sources SET status = 'error', status_reason = ? WHERE id = ?",
+                (reason, id),
+            ),
+        }
+        .map_err(|e| e.to_string())?;
+    }
+
+    if let Ok(ChannelInfo { avatar_url: Some(avatar_url), .. }) = info {
+        // A missing photo isn't worth failing validation over
+        let _ = set_creator_photo(app_handle, &source.creator_id, avatar_url).await;
+    }
+
+    let db = app_handle.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    get_source(&conn, id)
+}
+
+/// Download a channel avatar to `{app data}/avatars` and make it the creator's
+/// photo, unless the creator already has one
+async fn set_creator_photo(app_handle: &AppHandle, creator_id: &str, avatar_url: String) -> Result<(), String> {
+    {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        let photo_path: Option<String> = conn
+            .query_row("SELECT photo_path FROM creators WHERE id = ?", [creator_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if photo_path.is_some() {
+            return Ok(());
+        }
+    }
+
+    let avatars_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("avatars");
+    std::fs::create_dir_all(&avatars_dir).map_err(|e| e.to_string())?;
+
+    // YouTube avatar URLs have no extension; browsers sniff the image type anyway
+    let extension = avatar_url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_lowercase())
+        .filter(|ext| matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "webp" | "gif"))
+        .unwrap_or_else(|| "jpg".to_string());
+    let photo_path = avatars_dir
+        .join(format!("{}.{}", creator_id, extension))
+        .to_string_lossy()
+        .to_string();
+
+    let download_path = photo_path.clone();
+    tokio::task::spawn_blocking(move || http::download_file(&avatar_url, None, &download_path, |_, _| true))
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let db = app_handle.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
     conn.execute(
-        "INSERT INTO sources (id, creator_id, platform, channel_url, credential_id, status, created_at)
-         VALUES (?, ?, ?, ?, ?, 'pending', ?)",
-        (&id, &request.creator_id, &request.platform, &request.channel_url, &request.credential_id, &now),
+        "UPDATE creators SET photo_path = ?, updated_at = ? WHERE id = ? AND photo_path IS NULL",
+        (&photo_path, Utc::now().to_rfc3339(), creator_id),
     )
     .map_err(|e| e.to_string())?;
 
-    Ok(Source {
-        id,
-        creator_id: request.creator_id,
-        platform: request.platform,
-        channel_url: request.channel_url,
-        channel_name: None,
-        credential_id: request.credential_id,
-        status: "pending".to_string(),
-        last_synced_at: None,
-        created_at: now,
-    })
+    Ok(())
 }
 
 #[tauri::command]
@@ -68,26 +203,7 @@ pub fn update_source(db: State<Database>, id: String, request: UpdateSourceReque
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     // Get current source
-    let mut source = conn
-        .query_row(
-            "SELECT id, creator_id, platform, channel_url, channel_name, credential_id, status, last_synced_at, created_at
-             FROM sources WHERE id = ?",
-            [&id],
-            |row| {
-                Ok(Source {
-                    id: row.get(0)?,
-                    creator_id: row.get(1)?,
-                    platform: row.get(2)?,
-                    channel_url: row.get(3)?,
-                    channel_name: row.get(4)?,
-                    credential_id: row.get(5)?,
-                    status: row.get(6)?,
-                    last_synced_at: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            },
-        )
-        .map_err(|e| e.to_string())?;
+    let mut source = get_source(&conn, &id)?;
 
     // Update fields
     if let Some(channel_url) = request.channel_url {
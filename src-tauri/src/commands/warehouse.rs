@@ -1,9 +1,15 @@
+use crate::commands::hooks::spawn_item_hooks;
+use crate::commands::settings::load_settings;
 use crate::db::Database;
-use crate::models::warehouse_item::{CreateWarehouseItemRequest, WarehouseItem};
+use crate::models::{Creator, SponsorSegment};
+use crate::models::warehouse_item::{CreateWarehouseItemRequest, WarehouseItem, WarehouseItemFile};
+use crate::services::ffprobe::{self, MediaProbe};
+use crate::services::{get_ffprobe_path, nfo};
 use chrono::Utc;
+use rusqlite::Connection;
 use serde::Deserialize;
-use std::path::Path;
-use tauri::State;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -14,35 +20,49 @@ pub struct ImportVideoRequest {
     pub platform: Option<String>, // "youtube", "patreon", "other"
 }
 
+/// Columns read by `warehouse_item_from_row`, in order
+pub const WAREHOUSE_ITEM_COLUMNS: &str = "id, creator_id, feed_item_id, title, file_path, thumbnail_path, platform, original_url, published_at, duration, file_size, imported_at, is_manual_import,
+     width, height, video_codec, audio_codec, bitrate, fps, verification_status, verification_error";
+
+pub fn warehouse_item_from_row(row: &rusqlite::Row) -> rusqlite::Result<WarehouseItem> {
+    Ok(WarehouseItem {
+        id: row.get(0)?,
+        creator_id: row.get(1)?,
+        feed_item_id: row.get(2)?,
+        title: row.get(3)?,
+        file_path: row.get(4)?,
+        thumbnail_path: row.get(5)?,
+        platform: row.get(6)?,
+        original_url: row.get(7)?,
+        published_at: row.get(8)?,
+        duration: row.get(9)?,
+        file_size: row.get(10)?,
+        imported_at: row.get(11)?,
+        is_manual_import: row.get::<_, i32>(12)? != 0,
+        width: row.get(13)?,
+        height: row.get(14)?,
+        video_codec: row.get(15)?,
+        audio_codec: row.get(16)?,
+        bitrate: row.get(17)?,
+        fps: row.get(18)?,
+        verification_status: row.get(19)?,
+        verification_error: row.get(20)?,
+    })
+}
+
 #[tauri::command]
 pub fn get_warehouse_items_by_creator(db: State<Database>, creator_id: String) -> Result<Vec<WarehouseItem>, String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, creator_id, feed_item_id, title, file_path, thumbnail_path, platform, original_url, published_at, duration, file_size, imported_at, is_manual_import
-             FROM warehouse_items WHERE creator_id = ? ORDER BY imported_at DESC"
-        )
+        .prepare(&format!(
+            "SELECT {} FROM warehouse_items WHERE creator_id = ? ORDER BY imported_at DESC",
+            WAREHOUSE_ITEM_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let items = stmt
-        .query_map([&creator_id], |row| {
-            Ok(WarehouseItem {
-                id: row.get(0)?,
-                creator_id: row.get(1)?,
-                feed_item_id: row.get(2)?,
-                title: row.get(3)?,
-                file_path: row.get(4)?,
-                thumbnail_path: row.get(5)?,
-                platform: row.get(6)?,
-                original_url: row.get(7)?,
-                published_at: row.get(8)?,
-                duration: row.get(9)?,
-                file_size: row.get(10)?,
-                imported_at: row.get(11)?,
-                is_manual_import: row.get::<_, i32>(12)? != 0,
-            })
-        })
+        .query_map([&creator_id], warehouse_item_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -93,17 +113,93 @@ pub fn create_warehouse_item(db: State<Database>, request: CreateWarehouseItemRe
         file_size: request.file_size,
         imported_at: now,
         is_manual_import: request.is_manual_import,
+        width: None,
+        height: None,
+        video_codec: None,
+        audio_codec: None,
+        bitrate: None,
+        fps: None,
+        verification_status: "unverified".to_string(),
+        verification_error: None,
     })
 }
 
 #[tauri::command]
 pub fn delete_warehouse_item(db: State<Database>, id: String) -> Result<(), String> {
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    remove_warehouse_item(&conn, &id).map_err(|e| e.to_string())
+}
+
+/// Delete a warehouse item and the rows that belong to it; its files are left alone
+pub fn remove_warehouse_item(conn: &Connection, id: &str) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM sponsor_segments WHERE warehouse_item_id = ?", [id])?;
+    conn.execute("DELETE FROM download_archive WHERE warehouse_item_id = ?", [id])?;
+    conn.execute("DELETE FROM hook_runs WHERE warehouse_item_id = ?", [id])?;
+    conn.execute("DELETE FROM warehouse_item_files WHERE warehouse_item_id = ?", [id])?;
+    conn.execute("DELETE FROM warehouse_items WHERE id = ?", [id])?;
+    Ok(())
+}
+
+/// Get the files of an item downloaded from a post with several files.
+/// Empty for items that are a single file.
+#[tauri::command]
+pub fn get_warehouse_item_files(db: State<Database>, warehouse_item_id: String) -> Result<Vec<WarehouseItemFile>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-    conn.execute("DELETE FROM warehouse_items WHERE id = ?", [&id])
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, warehouse_item_id, position, file_path, kind, mime_type, file_size
+             FROM warehouse_item_files WHERE warehouse_item_id = ? ORDER BY position"
+        )
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    let files = stmt
+        .query_map([&warehouse_item_id], |row| {
+            Ok(WarehouseItemFile {
+                id: row.get(0)?,
+                warehouse_item_id: row.get(1)?,
+                position: row.get(2)?,
+                file_path: row.get(3)?,
+                kind: row.get(4)?,
+                mime_type: row.get(5)?,
+                file_size: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(files)
+}
+
+/// Get the SponsorBlock segments that are still present in a downloaded file
+#[tauri::command]
+pub fn get_sponsor_segments(db: State<Database>, warehouse_item_id: String) -> Result<Vec<SponsorSegment>, String> {
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, warehouse_item_id, category, action_type, start_time, end_time
+             FROM sponsor_segments WHERE warehouse_item_id = ? ORDER BY start_time"
+        )
+        .map_err(|e| e.to_string())?;
+
+    let segments = stmt
+        .query_map([&warehouse_item_id], |row| {
+            Ok(SponsorSegment {
+                id: row.get(0)?,
+                warehouse_item_id: row.get(1)?,
+                category: row.get(2)?,
+                action_type: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(segments)
 }
 
 /// Get the library path from app_settings
@@ -150,7 +246,7 @@ fn sanitize_filename(name: &str) -> String {
 }
 
 #[tauri::command]
-pub fn import_video(db: State<Database>, request: ImportVideoRequest) -> Result<WarehouseItem, String> {
+pub fn import_video(app_handle: AppHandle, db: State<Database>, request: ImportVideoRequest) -> Result<WarehouseItem, String> {
     // 1. Get creator name for destination path
     let creator_name = get_creator_name(&db, &request.creator_id)?;
 
@@ -196,7 +292,7 @@ pub fn import_video(db: State<Database>, request: ImportVideoRequest) -> Result<
     // 9. Get file size from metadata
     let file_size = std::fs::metadata(&dest_path)
         .map(|m| m.len() as i64)
-        .unwrap_or(0);
+        .map_err(|e| format!("Failed to read copied file: {}", e))?;
 
     // 10. Create WarehouseItem with is_manual_import = true
     let id = Uuid::new_v4().to_string();
@@ -206,31 +302,7 @@ pub fn import_video(db: State<Database>, request: ImportVideoRequest) -> Result<
         .ok_or("Failed to convert path to string")?
         .to_string();
 
-    let conn = db.conn.lock().map_err(|e| e.to_string())?;
-
-    conn.execute(
-        "INSERT INTO warehouse_items (id, creator_id, feed_item_id, title, file_path, thumbnail_path, platform, original_url, published_at, duration, file_size, imported_at, is_manual_import)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        (
-            &id,
-            &request.creator_id,
-            &Option::<String>::None, // feed_item_id
-            &request.title,
-            &file_path_str,
-            &Option::<String>::None, // thumbnail_path
-            &Some(platform.to_string()),
-            &Option::<String>::None, // original_url
-            &Option::<String>::None, // published_at
-            &Option::<i64>::None, // duration
-            &file_size,
-            &now,
-            &1i32, // is_manual_import = true
-        ),
-    )
-    .map_err(|e| e.to_string())?;
-
-    // 11. Return the WarehouseItem
-    Ok(WarehouseItem {
+    let mut item = WarehouseItem {
         id,
         creator_id: request.creator_id,
         feed_item_id: None,
@@ -244,5 +316,241 @@ pub fn import_video(db: State<Database>, request: ImportVideoRequest) -> Result<
         file_size,
         imported_at: now,
         is_manual_import: true,
-    })
+        width: None,
+        height: None,
+        video_codec: None,
+        audio_codec: None,
+        bitrate: None,
+        fps: None,
+        verification_status: "unverified".to_string(),
+        verification_error: None,
+    };
+
+    // 11. Check the file with ffprobe; a corrupt file is still imported but flagged
+    let verification = verify_file(&app_handle, &item.file_path);
+    apply_verification(&mut item, verification);
+
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO warehouse_items (id, creator_id, feed_item_id, title, file_path, thumbnail_path, platform, original_url, published_at, duration, file_size, imported_at, is_manual_import,
+                                      width, height, video_codec, audio_codec, bitrate, fps, verification_status, verification_error)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            item.id,
+            item.creator_id,
+            item.feed_item_id,
+            item.title,
+            item.file_path,
+            item.thumbnail_path,
+            item.platform,
+            item.original_url,
+            item.published_at,
+            item.duration,
+            item.file_size,
+            item.imported_at,
+            item.width,
+            item.height,
+            item.video_codec,
+            item.audio_codec,
+            item.bitrate,
+            item.fps,
+            item.verification_status,
+            item.verification_error,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    drop(conn);
+
+    // 12. Write media server sidecar files if enabled (best effort)
+    let _ = write_item_sidecars(&db, &item, Some(&Path::new(&library_path).join(&sanitized_creator)));
+
+    // 13. Run post-processing hooks in the background
+    spawn_item_hooks(&app_handle, &item, "import");
+
+    // 14. Return the WarehouseItem
+    Ok(item)
+}
+
+/// Verify a media file with ffprobe.
+/// Returns None if ffprobe is not available or fails to run, so the file can't be checked.
+pub fn verify_file(app_handle: &AppHandle, file_path: &str) -> Option<Result<MediaProbe, String>> {
+    let ffprobe_path = get_ffprobe_path(app_handle).ok()?;
+    ffprobe::verify_media(&ffprobe_path, file_path)
+}
+
+/// Copy an ffprobe result onto a warehouse item
+pub fn apply_verification(item: &mut WarehouseItem, verification: Option<Result<MediaProbe, String>>) {
+    match verification {
+        Some(Ok(probe)) => {
+            item.duration = probe.duration.or(item.duration);
+            item.width = probe.width;
+            item.height = probe.height;
+            item.video_codec = probe.video_codec;
+            item.audio_codec = probe.audio_codec;
+            item.bitrate = probe.bitrate;
+            item.fps = probe.fps;
+            item.verification_status = "verified".to_string();
+            item.verification_error = None;
+        }
+        Some(Err(reason)) => {
+            item.verification_status = "corrupt".to_string();
+            item.verification_error = Some(reason);
+        }
+        None => {
+            item.verification_status = "unverified".to_string();
+            item.verification_error = None;
+        }
+    }
+}
+
+/// Re-run ffprobe verification for a warehouse item and store the result
+#[tauri::command]
+pub fn verify_warehouse_item(app_handle: AppHandle, db: State<Database>, id: String) -> Result<WarehouseItem, String> {
+    let mut item = {
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        conn.query_row(
+            &format!("SELECT {} FROM warehouse_items WHERE id = ?", WAREHOUSE_ITEM_COLUMNS),
+            [&id],
+            warehouse_item_from_row,
+        )
+        .map_err(|e| format!("Warehouse item not found: {}", e))?
+    };
+
+    if !Path::new(&item.file_path).exists() {
+        return Err(format!("Media file does not exist: {}", item.file_path));
+    }
+
+    if get_ffprobe_path(&app_handle).is_err() {
+        return Err("ffprobe not found. Please ensure ffprobe is bundled with the app or installed on your system.".to_string());
+    }
+
+    // Probe without holding the database lock
+    let verification = verify_file(&app_handle, &item.file_path);
+    apply_verification(&mut item, verification);
+    if let Ok(metadata) = std::fs::metadata(&item.file_path) {
+        item.file_size = metadata.len() as i64;
+    }
+
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+    store_verification(&conn, &item).map_err(|e| e.to_string())?;
+
+    Ok(item)
+}
+
+/// Save an item's file size and verification fields
+pub fn store_verification(conn: &Connection, item: &WarehouseItem) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE warehouse_items SET duration = ?, file_size = ?, width = ?, height = ?, video_codec = ?, audio_codec = ?,
+                bitrate = ?, fps = ?, verification_status = ?, verification_error = ?
+         WHERE id = ?",
+        rusqlite::params![
+            item.duration,
+            item.file_size,
+            item.width,
+            item.height,
+            item.video_codec,
+            item.audio_codec,
+            item.bitrate,
+            item.fps,
+            item.verification_status,
+            item.verification_error,
+            item.id,
+        ],
+    )
+}
+
+/// Write NFO/info.json sidecar files for a warehouse item, each if enabled in settings.
+/// `creator_dir` is the creator's library folder, for `tvshow.nfo`.
+/// Returns true if files were written.
+pub fn write_item_sidecars(db: &Database, item: &WarehouseItem, creator_dir: Option<&Path>) -> Result<bool, String> {
+    let (settings, creator, external_id) = {
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        let settings = load_settings(&conn).map_err(|e| e.to_string())?;
+        let creator = get_creator_by_id(&conn, &item.creator_id)?;
+        let external_id: Option<String> = item.feed_item_id.as_ref().and_then(|feed_item_id| {
+            conn.query_row("SELECT external_id FROM feed_items WHERE id = ?", [feed_item_id], |row| row.get(0))
+                .ok()
+        });
+        (settings, creator, external_id)
+    };
+
+    if !settings.write_nfo_files && !settings.write_info_json {
+        return Ok(false);
+    }
+
+    nfo::write_sidecars(
+        item,
+        &creator,
+        external_id.as_deref(),
+        creator_dir,
+        settings.write_nfo_files,
+        settings.write_info_json,
+    )?;
+    Ok(true)
+}
+
+/// The creator folder holding a library file: the first folder below the library
+fn creator_dir_of(library_path: &str, file_path: &str) -> Option<PathBuf> {
+    let relative = Path::new(file_path).strip_prefix(library_path).ok()?;
+    let creator_folder = relative.components().next()?;
+    // A file directly in the library has no creator folder
+    (relative.components().count() > 1).then(|| Path::new(library_path).join(creator_folder))
+}
+
+fn get_creator_by_id(conn: &rusqlite::Connection, creator_id: &str) -> Result<Creator, String> {
+    conn.query_row(
+        "SELECT id, name, photo_path, created_at, updated_at FROM creators WHERE id = ?",
+        [creator_id],
+        |row| {
+            Ok(Creator {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                photo_path: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        },
+    )
+    .map_err(|e| format!("Creator not found: {}", e))
+}
+
+/// Write sidecar files for existing warehouse items (all, or one creator's).
+/// Returns the number of items that got sidecar files.
+#[tauri::command]
+pub fn backfill_sidecar_files(db: State<Database>, creator_id: Option<String>) -> Result<i32, String> {
+    let (library_path, items) = {
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+        let settings = load_settings(&conn).map_err(|e| e.to_string())?;
+        if !settings.write_nfo_files && !settings.write_info_json {
+            return Err("NFO and info.json files are disabled. Enable them in Settings first.".to_string());
+        }
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM warehouse_items WHERE ?1 IS NULL OR creator_id = ?1",
+                WAREHOUSE_ITEM_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let items = stmt
+            .query_map([&creator_id], warehouse_item_from_row)
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        (settings.library_path, items)
+    };
+
+    let mut written = 0;
+    for item in items {
+        // Skip items whose file is missing instead of failing the whole backfill
+        let creator_dir = creator_dir_of(&library_path, &item.file_path);
+        if let Ok(true) = write_item_sidecars(&db, &item, creator_dir.as_deref()) {
+            written += 1;
+        }
+    }
+
+    Ok(written)
 }
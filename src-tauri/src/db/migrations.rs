@@ -1,33 +1,119 @@
 use rusqlite::Connection;
 
-pub fn run_all(conn: &Connection) -> Result<(), rusqlite::Error> {
-    run_schema(conn)?;
-    run_migrations(conn)?;
-    rebuild_fts_indexes(conn)?;
-    Ok(())
+/// feed_items_fts and its sync triggers. Kept apart from the main schema so
+/// the table can be recreated when its columns change.
+const FEED_ITEMS_FTS_SCHEMA: &str = "
+    CREATE VIRTUAL TABLE IF NOT EXISTS feed_items_fts USING fts5(
+        id UNINDEXED,
+        title,
+        description
+    );
+
+    CREATE TRIGGER IF NOT EXISTS feed_items_fts_ai AFTER INSERT ON feed_items BEGIN
+        INSERT INTO feed_items_fts(id, title, description) VALUES (NEW.id, NEW.title, NEW.description);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS feed_items_fts_ad AFTER DELETE ON feed_items BEGIN
+        DELETE FROM feed_items_fts WHERE id = OLD.id;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS feed_items_fts_au AFTER UPDATE ON feed_items BEGIN
+        DELETE FROM feed_items_fts WHERE id = OLD.id;
+        INSERT INTO feed_items_fts(id, title, description) VALUES (NEW.id, NEW.title, NEW.description);
+    END;
+";
+
+/// A numbered schema change. Pending migrations are applied in order, each in
+/// its own transaction together with its `schema_migrations` row and the
+/// `PRAGMA user_version` bump, so a failed migration leaves the database at the
+/// previous version.
+struct Migration {
+    version: i64,
+    description: &'static str,
+    up: fn(&Connection) -> Result<(), rusqlite::Error>,
 }
 
-/// Run incremental migrations for existing databases
-fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
-    // Add metadata_complete column if it doesn't exist (for existing databases)
-    // Use PRAGMA table_info directly and check results
-    let mut has_metadata_complete = false;
-    let mut stmt = conn.prepare("PRAGMA table_info(feed_items)")?;
-    let column_iter = stmt.query_map([], |row| {
-        row.get::<_, String>(1) // column name is at index 1
-    })?;
+/// Every migration, oldest first. Add changes as a new migration with the next
+/// version; never edit one that has shipped, as existing databases won't rerun it.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Schema up to scheduled backups",
+        up: baseline,
+    },
+    Migration {
+        version: 2,
+        description: "Hook timeout setting",
+        up: hook_timeout,
+    },
+];
 
-    for column_result in column_iter {
-        if let Ok(column_name) = column_result {
-            if column_name == "metadata_complete" {
-                has_metadata_complete = true;
-                break;
-            }
-        }
+/// Version of the schema `run_all` produces, stored in `PRAGMA user_version`.
+/// Backups record it so a restore can tell whether this build understands them.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Ledger of applied migrations. `applied_at` is NULL for versions a database
+/// reached before the ledger existed.
+const LEDGER_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        applied_at TEXT
+    );
+";
+
+pub fn run_all(conn: &Connection) -> Result<(), String> {
+    migrate(conn, MIGRATIONS)?;
+    rebuild_fts_indexes(conn).map_err(|e| format!("Failed to rebuild search indexes: {}", e))
+}
+
+/// Apply the migrations newer than the database's version. Databases from a
+/// newer build are refused before anything is written.
+fn migrate(conn: &Connection, migrations: &[Migration]) -> Result<(), String> {
+    let current = schema_version(conn).map_err(|e| e.to_string())?;
+    let latest = migrations.last().map_or(0, |m| m.version);
+    if current > latest {
+        return Err(format!(
+            "The library database has schema version {}, but this version of the app only knows up to {}. Please update the app to open it.",
+            current, latest
+        ));
     }
-    drop(stmt);
 
-    if !has_metadata_complete {
+    conn.execute_batch(LEDGER_SCHEMA).map_err(|e| e.to_string())?;
+    for migration in migrations.iter().filter(|m| m.version <= current) {
+        conn.execute(
+            "INSERT OR IGNORE INTO schema_migrations (version, description, applied_at) VALUES (?, ?, NULL)",
+            (migration.version, migration.description),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        apply(conn, migration)
+            .map_err(|e| format!("Migration {} ({}) failed: {}", migration.version, migration.description, e))?;
+    }
+    Ok(())
+}
+
+fn apply(conn: &Connection, migration: &Migration) -> Result<(), rusqlite::Error> {
+    let tx = conn.unchecked_transaction()?;
+    (migration.up)(&tx)?;
+    tx.execute(
+        "INSERT OR REPLACE INTO schema_migrations (version, description, applied_at) VALUES (?, ?, ?)",
+        (migration.version, migration.description, chrono::Utc::now().to_rfc3339()),
+    )?;
+    tx.pragma_update(None, "user_version", migration.version)?;
+    tx.commit()
+}
+
+/// Version 1: the schema as it stood when versioning was introduced. Databases
+/// from before then can be at any point of the old ad-hoc migrations, so every
+/// step checks what is already there.
+fn baseline(conn: &Connection) -> Result<(), rusqlite::Error> {
+    run_schema(conn)?;
+
+    // Add metadata_complete column if it doesn't exist (for existing databases)
+    if !has_column(conn, "feed_items", "metadata_complete")? {
         conn.execute_batch(
             "ALTER TABLE feed_items ADD COLUMN metadata_complete INTEGER NOT NULL DEFAULT 0;
              CREATE INDEX IF NOT EXISTS idx_feed_items_metadata_complete ON feed_items(metadata_complete);
@@ -35,9 +121,186 @@ fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
         )?;
     }
 
+    // SponsorBlock settings
+    add_column_if_missing(conn, "app_settings", "sponsorblock_enabled", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "sponsorblock_mark_categories",
+        "TEXT NOT NULL DEFAULT 'sponsor,selfpromo,interaction'",
+    )?;
+    add_column_if_missing(conn, "app_settings", "sponsorblock_remove_categories", "TEXT NOT NULL DEFAULT ''")?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "sponsorblock_api_url",
+        "TEXT NOT NULL DEFAULT 'https://sponsor.ajay.app'",
+    )?;
+
+    // Metadata embedding setting
+    add_column_if_missing(conn, "app_settings", "embed_metadata", "INTEGER NOT NULL DEFAULT 1")?;
+
+    // Seed the download archive from items downloaded before it existed
+    conn.execute(
+        "INSERT OR IGNORE INTO download_archive (platform, external_id, warehouse_item_id, created_at)
+         SELECT s.platform, fi.external_id, fi.warehouse_item_id, w.imported_at
+         FROM feed_items fi
+         JOIN sources s ON fi.source_id = s.id
+         JOIN warehouse_items w ON fi.warehouse_item_id = w.id
+         WHERE fi.download_status = 'downloaded'",
+        [],
+    )?;
+
+    // Media server sidecar settings
+    add_column_if_missing(conn, "app_settings", "write_nfo_files", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "app_settings", "write_info_json", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Resumable downloads
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "partial_download_max_age_days",
+        "INTEGER NOT NULL DEFAULT 7",
+    )?;
+
+    // Media details and ffprobe verification of library files
+    add_column_if_missing(conn, "warehouse_items", "width", "INTEGER")?;
+    add_column_if_missing(conn, "warehouse_items", "height", "INTEGER")?;
+    add_column_if_missing(conn, "warehouse_items", "video_codec", "TEXT")?;
+    add_column_if_missing(conn, "warehouse_items", "audio_codec", "TEXT")?;
+    add_column_if_missing(conn, "warehouse_items", "bitrate", "INTEGER")?;
+    add_column_if_missing(conn, "warehouse_items", "fps", "REAL")?;
+    add_column_if_missing(
+        conn,
+        "warehouse_items",
+        "verification_status",
+        "TEXT NOT NULL DEFAULT 'unverified'",
+    )?;
+    add_column_if_missing(conn, "warehouse_items", "verification_error", "TEXT")?;
+
+    // Metadata retry tracking
+    add_column_if_missing(conn, "feed_items", "metadata_attempts", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "feed_items", "metadata_last_error", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "metadata_next_attempt_at", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "metadata_unavailable", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // Richer metadata (tags, categories and resolutions are JSON arrays)
+    add_column_if_missing(conn, "feed_items", "description", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "tags", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "categories", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "view_count", "INTEGER")?;
+    add_column_if_missing(conn, "feed_items", "like_count", "INTEGER")?;
+    add_column_if_missing(conn, "feed_items", "resolutions", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "is_live", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "feed_items", "is_short", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // FTS5 tables can't gain columns, so recreate feed_items_fts with description.
+    // rebuild_fts_indexes repopulates it afterwards.
+    if !has_column(conn, "feed_items_fts", "description")? {
+        conn.execute_batch(
+            "DROP TRIGGER IF EXISTS feed_items_fts_ai;
+             DROP TRIGGER IF EXISTS feed_items_fts_ad;
+             DROP TRIGGER IF EXISTS feed_items_fts_au;
+             DROP TABLE IF EXISTS feed_items_fts;",
+        )?;
+        conn.execute_batch(FEED_ITEMS_FTS_SCHEMA)?;
+    }
+
+    // Per-platform metadata rate limits
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "metadata_requests_per_minute",
+        "TEXT NOT NULL DEFAULT 'youtube=120,patreon=60'",
+    )?;
+
+    // Periodic metadata refresh
+    add_column_if_missing(conn, "feed_items", "metadata_fetched_at", "TEXT")?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "metadata_refresh_policy",
+        "TEXT NOT NULL DEFAULT '7:1,30:7'",
+    )?;
+
+    // Generic yt-dlp sources record item URLs at sync time
+    add_column_if_missing(conn, "feed_items", "webpage_url", "TEXT")?;
+
+    // Twitch VODs, highlights and clips
+    add_column_if_missing(conn, "feed_items", "content_kind", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "game", "TEXT")?;
+    add_column_if_missing(conn, "feed_items", "expires_at", "TEXT")?;
+    add_column_if_missing(conn, "app_settings", "twitch_vod_retention_days", "INTEGER NOT NULL DEFAULT 14")?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_feed_items_expires_at ON feed_items(expires_at)",
+        [],
+    )?;
+
+    // Patreon posts with audio, images and attachments (a JSON array of PostMedia)
+    add_column_if_missing(conn, "feed_items", "media", "TEXT")?;
+
+    // Patreon posts the credential's tier doesn't unlock
+    add_column_if_missing(conn, "feed_items", "locked", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "feed_items", "required_tier", "TEXT")?;
+
+    // Channel details resolved by source validation, and why a source is in error
+    add_column_if_missing(conn, "sources", "channel_id", "TEXT")?;
+    add_column_if_missing(conn, "sources", "avatar_url", "TEXT")?;
+    add_column_if_missing(conn, "sources", "banner_url", "TEXT")?;
+    add_column_if_missing(conn, "sources", "subscriber_count", "INTEGER")?;
+    add_column_if_missing(conn, "sources", "status_reason", "TEXT")?;
+
+    // Scheduled backups
+    add_column_if_missing(conn, "app_settings", "backup_interval_hours", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "app_settings", "backup_keep_count", "INTEGER NOT NULL DEFAULT 7")?;
+    add_column_if_missing(conn, "app_settings", "backup_include_thumbnails", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "app_settings", "backup_directory", "TEXT NOT NULL DEFAULT ''")?;
+
     Ok(())
 }
 
+/// Version 2: how long a post-processing hook may run before it is stopped
+fn hook_timeout(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute_batch("ALTER TABLE app_settings ADD COLUMN hook_timeout_minutes INTEGER NOT NULL DEFAULT 60;")
+}
+
+/// Schema version recorded in a database, 0 for databases from before versioning
+pub fn schema_version(conn: &Connection) -> Result<i64, rusqlite::Error> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Check whether a table already has a column, using PRAGMA table_info
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let column_iter = stmt.query_map([], |row| {
+        row.get::<_, String>(1) // column name is at index 1
+    })?;
+
+    for column_name in column_iter.flatten() {
+        if column_name == column {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Add a column to an existing table unless it is already there
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), rusqlite::Error> {
+    if !has_column(conn, table, column)? {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, definition))?;
+    }
+    Ok(())
+}
+
+/// Tables of the version 1 schema, with the columns `baseline` adds to older
+/// databases listed last in the same order, so fresh and migrated databases match
+/// column for column. Frozen: later changes go in new migrations.
 fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
     conn.execute_batch(
         "
@@ -50,7 +313,22 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             first_run_completed INTEGER NOT NULL DEFAULT 0,
             notifications_enabled INTEGER NOT NULL DEFAULT 1,
             bass_boost_preset TEXT NOT NULL DEFAULT 'Default',
-            bass_boost_custom_gain INTEGER NOT NULL DEFAULT 5
+            bass_boost_custom_gain INTEGER NOT NULL DEFAULT 5,
+            sponsorblock_enabled INTEGER NOT NULL DEFAULT 0,
+            sponsorblock_mark_categories TEXT NOT NULL DEFAULT 'sponsor,selfpromo,interaction',
+            sponsorblock_remove_categories TEXT NOT NULL DEFAULT '',
+            sponsorblock_api_url TEXT NOT NULL DEFAULT 'https://sponsor.ajay.app',
+            embed_metadata INTEGER NOT NULL DEFAULT 1,
+            write_nfo_files INTEGER NOT NULL DEFAULT 0,
+            write_info_json INTEGER NOT NULL DEFAULT 0,
+            partial_download_max_age_days INTEGER NOT NULL DEFAULT 7,
+            metadata_requests_per_minute TEXT NOT NULL DEFAULT 'youtube=120,patreon=60',
+            metadata_refresh_policy TEXT NOT NULL DEFAULT '7:1,30:7',
+            twitch_vod_retention_days INTEGER NOT NULL DEFAULT 14,
+            backup_interval_hours INTEGER NOT NULL DEFAULT 0,
+            backup_keep_count INTEGER NOT NULL DEFAULT 7,
+            backup_include_thumbnails INTEGER NOT NULL DEFAULT 0,
+            backup_directory TEXT NOT NULL DEFAULT ''
         );
 
         CREATE TABLE IF NOT EXISTS credentials (
@@ -81,6 +359,11 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             status TEXT NOT NULL DEFAULT 'pending',
             last_synced_at TEXT,
             created_at TEXT NOT NULL,
+            channel_id TEXT,
+            avatar_url TEXT,
+            banner_url TEXT,
+            subscriber_count INTEGER,
+            status_reason TEXT,
             FOREIGN KEY (creator_id) REFERENCES creators(id) ON DELETE CASCADE,
             FOREIGN KEY (credential_id) REFERENCES credentials(id) ON DELETE SET NULL
         );
@@ -97,6 +380,26 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             warehouse_item_id TEXT,
             metadata_complete INTEGER NOT NULL DEFAULT 0,
             created_at TEXT NOT NULL,
+            metadata_attempts INTEGER NOT NULL DEFAULT 0,
+            metadata_last_error TEXT,
+            metadata_next_attempt_at TEXT,
+            metadata_unavailable INTEGER NOT NULL DEFAULT 0,
+            description TEXT,
+            tags TEXT,
+            categories TEXT,
+            view_count INTEGER,
+            like_count INTEGER,
+            resolutions TEXT,
+            is_live INTEGER NOT NULL DEFAULT 0,
+            is_short INTEGER NOT NULL DEFAULT 0,
+            metadata_fetched_at TEXT,
+            webpage_url TEXT,
+            content_kind TEXT,
+            game TEXT,
+            expires_at TEXT,
+            media TEXT,
+            locked INTEGER NOT NULL DEFAULT 0,
+            required_tier TEXT,
             FOREIGN KEY (source_id) REFERENCES sources(id) ON DELETE CASCADE,
             FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE SET NULL,
             UNIQUE(source_id, external_id)
@@ -116,23 +419,100 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             file_size INTEGER NOT NULL,
             imported_at TEXT NOT NULL,
             is_manual_import INTEGER NOT NULL DEFAULT 0,
+            width INTEGER,
+            height INTEGER,
+            video_codec TEXT,
+            audio_codec TEXT,
+            bitrate INTEGER,
+            fps REAL,
+            verification_status TEXT NOT NULL DEFAULT 'unverified',
+            verification_error TEXT,
             FOREIGN KEY (creator_id) REFERENCES creators(id) ON DELETE CASCADE,
             FOREIGN KEY (feed_item_id) REFERENCES feed_items(id) ON DELETE SET NULL
         );
 
+        CREATE TABLE IF NOT EXISTS sponsor_segments (
+            id TEXT PRIMARY KEY,
+            warehouse_item_id TEXT NOT NULL,
+            category TEXT NOT NULL,
+            action_type TEXT NOT NULL,
+            start_time REAL NOT NULL,
+            end_time REAL NOT NULL,
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS warehouse_item_files (
+            id TEXT PRIMARY KEY,
+            warehouse_item_id TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            file_path TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            mime_type TEXT,
+            file_size INTEGER NOT NULL,
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS feed_item_chapters (
+            id TEXT PRIMARY KEY,
+            feed_item_id TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            start_time REAL NOT NULL,
+            end_time REAL NOT NULL,
+            FOREIGN KEY (feed_item_id) REFERENCES feed_items(id) ON DELETE CASCADE
+        );
+
+        -- Global record of downloaded videos, so the same video reachable from
+        -- several sources is only downloaded once
+        CREATE TABLE IF NOT EXISTS download_archive (
+            platform TEXT NOT NULL,
+            external_id TEXT NOT NULL,
+            warehouse_item_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (platform, external_id),
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS hooks (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            hook_type TEXT NOT NULL,
+            command TEXT NOT NULL,
+            run_on_download INTEGER NOT NULL DEFAULT 1,
+            run_on_import INTEGER NOT NULL DEFAULT 0,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS hook_runs (
+            id TEXT PRIMARY KEY,
+            hook_id TEXT NOT NULL,
+            hook_name TEXT NOT NULL,
+            warehouse_item_id TEXT NOT NULL,
+            trigger_event TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            exit_code INTEGER,
+            output TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT NOT NULL,
+            FOREIGN KEY (hook_id) REFERENCES hooks(id) ON DELETE CASCADE,
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE CASCADE
+        );
+
         CREATE INDEX IF NOT EXISTS idx_sources_creator ON sources(creator_id);
         CREATE INDEX IF NOT EXISTS idx_feed_items_source ON feed_items(source_id);
         CREATE INDEX IF NOT EXISTS idx_feed_items_download_status ON feed_items(download_status);
         CREATE INDEX IF NOT EXISTS idx_feed_items_metadata_complete ON feed_items(metadata_complete);
         CREATE INDEX IF NOT EXISTS idx_feed_items_source_metadata ON feed_items(source_id, metadata_complete);
         CREATE INDEX IF NOT EXISTS idx_warehouse_items_creator ON warehouse_items(creator_id);
+        CREATE INDEX IF NOT EXISTS idx_sponsor_segments_warehouse_item ON sponsor_segments(warehouse_item_id);
+        CREATE INDEX IF NOT EXISTS idx_warehouse_item_files_warehouse_item ON warehouse_item_files(warehouse_item_id);
+        CREATE INDEX IF NOT EXISTS idx_feed_item_chapters_feed_item ON feed_item_chapters(feed_item_id);
+        CREATE INDEX IF NOT EXISTS idx_download_archive_warehouse_item ON download_archive(warehouse_item_id);
+        CREATE INDEX IF NOT EXISTS idx_hook_runs_warehouse_item ON hook_runs(warehouse_item_id);
 
         -- FTS5 virtual tables for full-text search (standalone, not content-linked)
-        CREATE VIRTUAL TABLE IF NOT EXISTS feed_items_fts USING fts5(
-            id UNINDEXED,
-            title
-        );
-
         CREATE VIRTUAL TABLE IF NOT EXISTS warehouse_items_fts USING fts5(
             id UNINDEXED,
             title
@@ -143,20 +523,6 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             name
         );
 
-        -- Triggers to keep feed_items_fts in sync
-        CREATE TRIGGER IF NOT EXISTS feed_items_fts_ai AFTER INSERT ON feed_items BEGIN
-            INSERT INTO feed_items_fts(id, title) VALUES (NEW.id, NEW.title);
-        END;
-
-        CREATE TRIGGER IF NOT EXISTS feed_items_fts_ad AFTER DELETE ON feed_items BEGIN
-            DELETE FROM feed_items_fts WHERE id = OLD.id;
-        END;
-
-        CREATE TRIGGER IF NOT EXISTS feed_items_fts_au AFTER UPDATE ON feed_items BEGIN
-            DELETE FROM feed_items_fts WHERE id = OLD.id;
-            INSERT INTO feed_items_fts(id, title) VALUES (NEW.id, NEW.title);
-        END;
-
         -- Triggers to keep warehouse_items_fts in sync
         CREATE TRIGGER IF NOT EXISTS warehouse_items_fts_ai AFTER INSERT ON warehouse_items BEGIN
             INSERT INTO warehouse_items_fts(id, title) VALUES (NEW.id, NEW.title);
@@ -187,6 +553,8 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
         "
     )?;
 
+    conn.execute_batch(FEED_ITEMS_FTS_SCHEMA)?;
+
     Ok(())
 }
 
@@ -195,8 +563,8 @@ fn run_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
 fn rebuild_fts_indexes(conn: &Connection) -> Result<(), rusqlite::Error> {
     // Populate feed_items_fts with any missing entries
     conn.execute(
-        "INSERT INTO feed_items_fts(id, title)
-         SELECT id, title FROM feed_items
+        "INSERT INTO feed_items_fts(id, title, description)
+         SELECT id, title, description FROM feed_items
          WHERE id NOT IN (SELECT id FROM feed_items_fts)",
         [],
     )?;
@@ -219,3 +587,220 @@ fn rebuild_fts_indexes(conn: &Connection) -> Result<(), rusqlite::Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// `schema_history` holds the schema of a fresh database from every earlier
+    /// version of this file, oldest first. Add one whenever a migration lands; those
+    /// saved since the ledger was added include `schema_migrations`.
+    fn historical_schemas() -> Vec<PathBuf> {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/db/schema_history");
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .expect("schema_history is missing")
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn open(schema: &str) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(schema).unwrap();
+        conn
+    }
+
+    /// Tables with their columns in order, plus index and trigger names. Columns
+    /// added by `ALTER TABLE` come last, so `run_schema` must list them in the
+    /// order the migrations add them.
+    fn describe(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT type, name FROM sqlite_master WHERE name NOT LIKE 'sqlite_%' ORDER BY type, name")
+            .unwrap();
+        let objects: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut description = Vec::new();
+        for (kind, name) in objects {
+            if kind != "table" {
+                description.push(format!("{} {}", kind, name));
+                continue;
+            }
+            let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", name)).unwrap();
+            let columns: Vec<String> = stmt
+                .query_map([], |row| {
+                    Ok(format!(
+                        "{} {} notnull={} default={:?} pk={}",
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                        row.get::<_, i64>(5)?
+                    ))
+                })
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            description.push(format!("table {} ({})", name, columns.join(", ")));
+        }
+        description
+    }
+
+    fn fresh() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_all(&conn).unwrap();
+        conn
+    }
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn migration_versions_are_sequential() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1, "{}", migration.description);
+        }
+    }
+
+    #[test]
+    fn fresh_database_reaches_latest_version() {
+        let conn = fresh();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(
+            count(&conn, "SELECT COUNT(*) FROM schema_migrations WHERE applied_at IS NOT NULL"),
+            SCHEMA_VERSION
+        );
+    }
+
+    /// Indexes on added columns stay in `baseline`, since `run_schema` runs
+    /// before older tables have those columns
+    #[test]
+    fn baseline_columns_are_all_in_run_schema() {
+        let tables = |conn: &Connection| -> Vec<String> {
+            describe(conn).into_iter().filter(|d| d.starts_with("table ")).collect()
+        };
+        let conn = Connection::open_in_memory().unwrap();
+        run_schema(&conn).unwrap();
+        let schema = tables(&conn);
+
+        baseline(&conn).unwrap();
+
+        assert_eq!(tables(&conn), schema);
+    }
+
+    #[test]
+    fn historical_schemas_migrate_to_fresh_schema() {
+        let expected = describe(&fresh());
+        let paths = historical_schemas();
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let conn = open(&std::fs::read_to_string(&path).unwrap());
+
+            // Columns every version has, so data has to survive each path forward
+            conn.execute_batch(
+                "INSERT INTO creators (id, name, created_at, updated_at) VALUES ('c1', 'Creator', '2026-01-01', '2026-01-01');
+                 INSERT INTO sources (id, creator_id, platform, channel_url, created_at)
+                     VALUES ('s1', 'c1', 'youtube', 'https://www.youtube.com/@creator', '2026-01-01');
+                 INSERT INTO warehouse_items (id, creator_id, title, file_path, file_size, imported_at)
+                     VALUES ('w1', 'c1', 'Video', '/library/video.mp4', 1, '2026-01-02');
+                 INSERT INTO feed_items (id, source_id, external_id, title, download_status, warehouse_item_id, created_at)
+                     VALUES ('f1', 's1', 'abc', 'Video', 'downloaded', 'w1', '2026-01-01');",
+            )
+            .unwrap();
+            let version = schema_version(&conn).unwrap();
+
+            run_all(&conn).unwrap_or_else(|e| panic!("{}: {}", name, e));
+
+            assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION, "{}", name);
+            assert_eq!(describe(&conn), expected, "{}", name);
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM schema_migrations"), SCHEMA_VERSION, "{}", name);
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM feed_items WHERE id = 'f1'"), 1, "{}", name);
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM feed_items_fts WHERE id = 'f1'"), 1, "{}", name);
+            if version == 0 {
+                // Seeded from downloaded items by the baseline migration
+                assert_eq!(count(&conn, "SELECT COUNT(*) FROM download_archive WHERE external_id = 'abc'"), 1, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn rerunning_is_a_no_op() {
+        let conn = fresh();
+        let before = describe(&conn);
+        let applied: String = conn
+            .query_row("SELECT applied_at FROM schema_migrations WHERE version = 1", [], |row| row.get(0))
+            .unwrap();
+
+        run_all(&conn).unwrap();
+
+        assert_eq!(describe(&conn), before);
+        let reapplied: String = conn
+            .query_row("SELECT applied_at FROM schema_migrations WHERE version = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(reapplied, applied);
+    }
+
+    #[test]
+    fn versioned_database_without_ledger_is_backfilled() {
+        let schema = historical_schemas()
+            .into_iter()
+            .rev()
+            .map(|path| std::fs::read_to_string(path).unwrap())
+            .find(|schema| !schema.contains("schema_migrations"))
+            .unwrap();
+        let conn = open(&schema);
+        let version = schema_version(&conn).unwrap();
+        assert!(version > 0);
+
+        run_all(&conn).unwrap();
+
+        assert_eq!(
+            count(&conn, "SELECT COUNT(*) FROM schema_migrations WHERE applied_at IS NULL"),
+            version
+        );
+    }
+
+    #[test]
+    fn newer_database_is_refused_untouched() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+
+        let error = run_all(&conn).unwrap_err();
+
+        assert!(error.contains("update the app"), "{}", error);
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION + 1);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM sqlite_master"), 0);
+    }
+
+    #[test]
+    fn failed_migration_rolls_back() {
+        fn add_table(conn: &Connection) -> Result<(), rusqlite::Error> {
+            conn.execute_batch("CREATE TABLE extra (id TEXT PRIMARY KEY)")
+        }
+        fn fail_halfway(conn: &Connection) -> Result<(), rusqlite::Error> {
+            conn.execute_batch("CREATE TABLE partial (id TEXT PRIMARY KEY); SELECT * FROM missing_table;")
+        }
+        let migrations = [
+            Migration { version: 1, description: "Extra table", up: add_table },
+            Migration { version: 2, description: "Broken", up: fail_halfway },
+        ];
+        let conn = Connection::open_in_memory().unwrap();
+
+        let error = migrate(&conn, &migrations).unwrap_err();
+
+        assert!(error.starts_with("Migration 2 (Broken) failed"), "{}", error);
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM sqlite_master WHERE name = 'extra'"), 1);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM sqlite_master WHERE name = 'partial'"), 0);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM schema_migrations"), 1);
+    }
+}
@@ -2,18 +2,23 @@ pub mod migrations;
 
 use rusqlite::Connection;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 pub struct Database {
     pub conn: Mutex<Connection>,
 }
 
+/// Location of the library database in the app data directory
+pub fn database_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("n3ms_media_library.db")
+}
+
 impl Database {
     pub fn new(app_data_dir: PathBuf) -> Result<Self, rusqlite::Error> {
         fs::create_dir_all(&app_data_dir).expect("Failed to create app data directory");
 
-        let db_path = app_data_dir.join("n3ms_media_library.db");
+        let db_path = database_path(&app_data_dir);
         let conn = Connection::open(&db_path)?;
 
         // Enable WAL mode for better concurrency
@@ -24,7 +29,9 @@ impl Database {
         })
     }
 
-    pub fn run_migrations(&self) -> Result<(), rusqlite::Error> {
+    /// Bring the schema up to date. Fails without touching the database if it
+    /// was written by a newer version of the app.
+    pub fn run_migrations(&self) -> Result<(), String> {
         let conn = self.conn.lock().unwrap();
         migrations::run_all(&conn)
     }
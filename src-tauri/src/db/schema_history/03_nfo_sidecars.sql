@@ -0,0 +1,164 @@
+CREATE TABLE app_settings (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            library_path TEXT NOT NULL,
+            default_quality TEXT NOT NULL DEFAULT 'best',
+            sync_interval_seconds INTEGER NOT NULL DEFAULT 300,
+            theme TEXT NOT NULL DEFAULT 'dark',
+            first_run_completed INTEGER NOT NULL DEFAULT 0,
+            notifications_enabled INTEGER NOT NULL DEFAULT 1,
+            bass_boost_preset TEXT NOT NULL DEFAULT 'Default',
+            bass_boost_custom_gain INTEGER NOT NULL DEFAULT 5,
+            sponsorblock_enabled INTEGER NOT NULL DEFAULT 0,
+            sponsorblock_mark_categories TEXT NOT NULL DEFAULT 'sponsor,selfpromo,interaction',
+            sponsorblock_remove_categories TEXT NOT NULL DEFAULT '',
+            sponsorblock_api_url TEXT NOT NULL DEFAULT 'https://sponsor.ajay.app',
+            embed_metadata INTEGER NOT NULL DEFAULT 1,
+            write_nfo_files INTEGER NOT NULL DEFAULT 0,
+            write_info_json INTEGER NOT NULL DEFAULT 0
+        );
+
+CREATE TABLE credentials (
+            id TEXT PRIMARY KEY,
+            label TEXT NOT NULL,
+            platform TEXT NOT NULL,
+            cookie_path TEXT NOT NULL,
+            is_default INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+CREATE TABLE creators (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            photo_path TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+CREATE TABLE sources (
+            id TEXT PRIMARY KEY,
+            creator_id TEXT NOT NULL,
+            platform TEXT NOT NULL,
+            channel_url TEXT NOT NULL,
+            channel_name TEXT,
+            credential_id TEXT,
+            status TEXT NOT NULL DEFAULT 'pending',
+            last_synced_at TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (creator_id) REFERENCES creators(id) ON DELETE CASCADE,
+            FOREIGN KEY (credential_id) REFERENCES credentials(id) ON DELETE SET NULL
+        );
+
+CREATE TABLE feed_items (
+            id TEXT PRIMARY KEY,
+            source_id TEXT NOT NULL,
+            external_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            thumbnail_url TEXT,
+            published_at TEXT,
+            duration INTEGER,
+            download_status TEXT NOT NULL DEFAULT 'not_downloaded',
+            warehouse_item_id TEXT,
+            metadata_complete INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (source_id) REFERENCES sources(id) ON DELETE CASCADE,
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE SET NULL,
+            UNIQUE(source_id, external_id)
+        );
+
+CREATE TABLE warehouse_items (
+            id TEXT PRIMARY KEY,
+            creator_id TEXT NOT NULL,
+            feed_item_id TEXT,
+            title TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            thumbnail_path TEXT,
+            platform TEXT,
+            original_url TEXT,
+            published_at TEXT,
+            duration INTEGER,
+            file_size INTEGER NOT NULL,
+            imported_at TEXT NOT NULL,
+            is_manual_import INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (creator_id) REFERENCES creators(id) ON DELETE CASCADE,
+            FOREIGN KEY (feed_item_id) REFERENCES feed_items(id) ON DELETE SET NULL
+        );
+
+CREATE TABLE sponsor_segments (
+            id TEXT PRIMARY KEY,
+            warehouse_item_id TEXT NOT NULL,
+            category TEXT NOT NULL,
+            action_type TEXT NOT NULL,
+            start_time REAL NOT NULL,
+            end_time REAL NOT NULL,
+            FOREIGN KEY (warehouse_item_id) REFERENCES warehouse_items(id) ON DELETE CASCADE
+        );
+
+CREATE INDEX idx_sources_creator ON sources(creator_id);
+
+CREATE INDEX idx_feed_items_source ON feed_items(source_id);
+
+CREATE INDEX idx_feed_items_download_status ON feed_items(download_status);
+
+CREATE INDEX idx_feed_items_metadata_complete ON feed_items(metadata_complete);
+
+CREATE INDEX idx_feed_items_source_metadata ON feed_items(source_id, metadata_complete);
+
+CREATE INDEX idx_warehouse_items_creator ON warehouse_items(creator_id);
+
+CREATE INDEX idx_sponsor_segments_warehouse_item ON sponsor_segments(warehouse_item_id);
+
+CREATE VIRTUAL TABLE feed_items_fts USING fts5(
+            id UNINDEXED,
+            title
+        );
+
+CREATE VIRTUAL TABLE warehouse_items_fts USING fts5(
+            id UNINDEXED,
+            title
+        );
+
+CREATE VIRTUAL TABLE creators_fts USING fts5(
+            id UNINDEXED,
+            name
+        );
+
+CREATE TRIGGER feed_items_fts_ai AFTER INSERT ON feed_items BEGIN
+            INSERT INTO feed_items_fts(id, title) VALUES (NEW.id, NEW.title);
+        END;
+
+CREATE TRIGGER feed_items_fts_ad AFTER DELETE ON feed_items BEGIN
+            DELETE FROM feed_items_fts WHERE id = OLD.id;
+        END;
+
+CREATE TRIGGER feed_items_fts_au AFTER UPDATE ON feed_items BEGIN
+            DELETE FROM feed_items_fts WHERE id = OLD.id;
+            INSERT INTO feed_items_fts(id, title) VALUES (NEW.id, NEW.title);
+        END;
+
+CREATE TRIGGER warehouse_items_fts_ai AFTER INSERT ON warehouse_items BEGIN
+            INSERT INTO warehouse_items_fts(id, title) VALUES (NEW.id, NEW.title);
+        END;
+
+CREATE TRIGGER warehouse_items_fts_ad AFTER DELETE ON warehouse_items BEGIN
+            DELETE FROM warehouse_items_fts WHERE id = OLD.id;
+        END;
+
+CREATE TRIGGER warehouse_items_fts_au AFTER UPDATE ON warehouse_items BEGIN
+            DELETE FROM warehouse_items_fts WHERE id = OLD.id;
+            INSERT INTO warehouse_items_fts(id, title) VALUES (NEW.id, NEW.title);
+        END;
+
+CREATE TRIGGER creators_fts_ai AFTER INSERT ON creators BEGIN
+            INSERT INTO creators_fts(id, name) VALUES (NEW.id, NEW.name);
+        END;
+
+CREATE TRIGGER creators_fts_ad AFTER DELETE ON creators BEGIN
+            DELETE FROM creators_fts WHERE id = OLD.id;
+        END;
+
+CREATE TRIGGER creators_fts_au AFTER UPDATE ON creators BEGIN
+            DELETE FROM creators_fts WHERE id = OLD.id;
+            INSERT INTO creators_fts(id, name) VALUES (NEW.id, NEW.name);
+        END;
+
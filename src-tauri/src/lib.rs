@@ -6,7 +6,7 @@ mod workers;
 
 use db::Database;
 use tauri::Manager;
-use workers::{DownloadManager, MetadataWorker, SyncManager};
+use workers::{BackupScheduler, DownloadManager, MetadataWorker, SyncManager};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -52,6 +52,9 @@ pub fn run() {
             let metadata_worker = MetadataWorker::new(app.handle().clone());
             app.manage(metadata_worker);
 
+            // Start automatic backups
+            BackupScheduler::start(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -61,8 +64,14 @@ pub fn run() {
             commands::create_creator,
             commands::update_creator,
             commands::delete_creator,
+            commands::import_subscriptions,
+            commands::export_library,
+            commands::create_backup,
+            commands::inspect_backup,
+            commands::restore_backup,
             commands::get_sources_by_creator,
             commands::create_source,
+            commands::validate_source,
             commands::update_source,
             commands::delete_source,
             commands::get_credentials,
@@ -78,12 +87,22 @@ pub fn run() {
             commands::create_feed_items_batch,
             commands::update_feed_item,
             commands::get_feed_item_counts,
+            commands::get_feed_item_chapters,
             commands::sync_source,
             commands::sync_creator,
             commands::sync_all,
             commands::get_warehouse_items_by_creator,
             commands::create_warehouse_item,
             commands::delete_warehouse_item,
+            commands::get_sponsor_segments,
+            commands::get_warehouse_item_files,
+            commands::backfill_sidecar_files,
+            commands::verify_warehouse_item,
+            commands::get_hooks,
+            commands::create_hook,
+            commands::update_hook,
+            commands::delete_hook,
+            commands::get_hook_runs,
             commands::get_app_settings,
             commands::update_app_settings,
             commands::download_items,
@@ -99,6 +118,8 @@ pub fn run() {
             commands::request_notification_permission,
             commands::send_test_notification,
             commands::fetch_feed_items_metadata,
+            commands::retry_feed_items_metadata,
+            commands::get_metadata_queue_status,
             commands::get_incomplete_metadata_items,
             commands::pause_metadata_worker,
             commands::resume_metadata_worker,
@@ -10,6 +10,36 @@ pub struct AppSettings {
     pub notifications_enabled: bool,
     pub bass_boost_preset: String,
     pub bass_boost_custom_gain: i64,
+    pub sponsorblock_enabled: bool,
+    /// Comma-separated SponsorBlock categories to mark as chapters
+    pub sponsorblock_mark_categories: String,
+    /// Comma-separated SponsorBlock categories to cut out of the file
+    pub sponsorblock_remove_categories: String,
+    pub sponsorblock_api_url: String,
+    /// Embed title, creator, date, description, chapters and cover art into downloads
+    pub embed_metadata: bool,
+    /// Write .nfo sidecar files for Jellyfin/Kodi/Plex next to library items
+    pub write_nfo_files: bool,
+    /// Write .info.json sidecar files next to library items
+    pub write_info_json: bool,
+    /// Days to keep fragments of interrupted downloads so they can resume; 0 discards them
+    pub partial_download_max_age_days: i64,
+    /// Metadata requests per minute for each platform, e.g. "youtube=120,patreon=60"
+    pub metadata_requests_per_minute: String,
+    /// Metadata refresh rules as "max_age_days:interval_days", e.g. "7:1,30:7"; empty disables refreshing
+    pub metadata_refresh_policy: String,
+    /// Days Twitch keeps past broadcasts before they expire; highlights and clips don't expire
+    pub twitch_vod_retention_days: i64,
+    /// Hours between automatic backups; 0 turns them off
+    pub backup_interval_hours: i64,
+    /// Automatic backups to keep; older ones are deleted
+    pub backup_keep_count: i64,
+    /// Put warehouse thumbnails into automatic backups
+    pub backup_include_thumbnails: bool,
+    /// Folder for automatic backups; empty uses the app data folder
+    pub backup_directory: String,
+    /// Minutes a hook may run before it is stopped; 0 means no limit
+    pub hook_timeout_minutes: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,4 +52,20 @@ pub struct UpdateAppSettingsRequest {
     pub notifications_enabled: Option<bool>,
     pub bass_boost_preset: Option<String>,
     pub bass_boost_custom_gain: Option<i64>,
+    pub sponsorblock_enabled: Option<bool>,
+    pub sponsorblock_mark_categories: Option<String>,
+    pub sponsorblock_remove_categories: Option<String>,
+    pub sponsorblock_api_url: Option<String>,
+    pub embed_metadata: Option<bool>,
+    pub write_nfo_files: Option<bool>,
+    pub write_info_json: Option<bool>,
+    pub partial_download_max_age_days: Option<i64>,
+    pub metadata_requests_per_minute: Option<String>,
+    pub metadata_refresh_policy: Option<String>,
+    pub twitch_vod_retention_days: Option<i64>,
+    pub backup_interval_hours: Option<i64>,
+    pub backup_keep_count: Option<i64>,
+    pub backup_include_thumbnails: Option<bool>,
+    pub backup_directory: Option<String>,
+    pub hook_timeout_minutes: Option<i64>,
 }
@@ -13,6 +13,61 @@ pub struct FeedItem {
     pub warehouse_item_id: Option<String>,
     pub metadata_complete: bool,
     pub created_at: String,
+    /// Failed metadata fetches since the last success
+    pub metadata_attempts: i64,
+    pub metadata_last_error: Option<String>,
+    /// The background worker won't retry before this time
+    pub metadata_next_attempt_at: Option<String>,
+    /// Set once retries are exhausted or the item is gone for good
+    pub metadata_unavailable: bool,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+    pub view_count: Option<i64>,
+    pub like_count: Option<i64>,
+    /// Available video heights, best first (e.g. "1080p")
+    pub resolutions: Vec<String>,
+    /// Livestream or livestream recording
+    pub is_live: bool,
+    pub is_short: bool,
+    /// Last successful metadata fetch, used by the refresh policy
+    pub metadata_fetched_at: Option<String>,
+    /// Item URL recorded at sync time, for platforms that can't build one from the external ID
+    pub webpage_url: Option<String>,
+    /// Platform-specific kind of item: "video", "short", "stream" or "podcast" on YouTube,
+    /// "vod", "highlight" or "clip" on Twitch, the post type on Patreon
+    pub content_kind: Option<String>,
+    /// Game or category the item was streamed under
+    pub game: Option<String>,
+    /// When the platform takes the item down (Twitch VODs), so it can be downloaded first
+    pub expires_at: Option<String>,
+    /// Files attached to a post (Patreon audio, images and attachments), downloaded
+    /// next to the video if there is one
+    pub media: Vec<PostMedia>,
+    /// The source's credential doesn't unlock the post; it can't be downloaded
+    pub locked: bool,
+    /// Lowest tier (or pledge) that unlocks a restricted post
+    pub required_tier: Option<String>,
+}
+
+/// A file attached to a post
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostMedia {
+    /// "audio", "image" or "file"
+    pub kind: String,
+    pub url: String,
+    pub file_name: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedItemChapter {
+    pub id: String,
+    pub feed_item_id: String,
+    pub position: i64,
+    pub title: String,
+    pub start_time: f64,
+    pub end_time: f64,
 }
 
 #[derive(Debug, Deserialize)]
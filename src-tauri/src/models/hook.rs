@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// A user-defined post-processing step that runs after a download or import
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Hook {
+    pub id: String,
+    pub name: String,
+    /// "command" runs a shell command, "ffmpeg" runs ffmpeg with an argument preset
+    pub hook_type: String,
+    /// Shell command line, or the ffmpeg arguments placed between input and output
+    pub command: String,
+    pub run_on_download: bool,
+    pub run_on_import: bool,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateHookRequest {
+    pub name: String,
+    pub hook_type: String,
+    pub command: String,
+    pub run_on_download: Option<bool>,
+    pub run_on_import: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateHookRequest {
+    pub name: Option<String>,
+    pub hook_type: Option<String>,
+    pub command: Option<String>,
+    pub run_on_download: Option<bool>,
+    pub run_on_import: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+/// The recorded result of running a hook for a warehouse item
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HookRun {
+    pub id: String,
+    pub hook_id: String,
+    pub hook_name: String,
+    pub warehouse_item_id: String,
+    /// "download" or "import"
+    pub trigger: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr, truncated to the last few KB
+    pub output: String,
+    pub started_at: String,
+    pub finished_at: String,
+}
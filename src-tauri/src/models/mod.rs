@@ -2,12 +2,15 @@ pub mod app_settings;
 pub mod creator;
 pub mod credential;
 pub mod feed_item;
+pub mod hook;
 pub mod source;
+pub mod sponsor_segment;
 pub mod warehouse_item;
 
 pub use app_settings::{AppSettings, UpdateAppSettingsRequest};
 pub use creator::Creator;
 pub use credential::Credential;
-pub use feed_item::FeedItem;
+pub use feed_item::{FeedItem, PostMedia};
 pub use source::Source;
+pub use sponsor_segment::SponsorSegment;
 pub use warehouse_item::WarehouseItem;
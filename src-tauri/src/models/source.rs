@@ -7,8 +7,15 @@ pub struct Source {
     pub platform: String,
     pub channel_url: String,
     pub channel_name: Option<String>,
+    /// Platform ID of the channel, set by validation
+    pub channel_id: Option<String>,
+    pub avatar_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub subscriber_count: Option<i64>,
     pub credential_id: Option<String>,
     pub status: String,
+    /// Why validation or the last sync failed, when `status` is "error"
+    pub status_reason: Option<String>,
     pub last_synced_at: Option<String>,
     pub created_at: String,
 }
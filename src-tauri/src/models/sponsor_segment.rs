@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SponsorSegment {
+    pub id: String,
+    pub warehouse_item_id: String,
+    pub category: String,
+    pub action_type: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
@@ -15,6 +15,29 @@ pub struct WarehouseItem {
     pub file_size: i64,
     pub imported_at: String,
     pub is_manual_import: bool,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub bitrate: Option<i64>,
+    pub fps: Option<f64>,
+    /// "verified", "corrupt", or "unverified" if ffprobe was not available
+    pub verification_status: String,
+    pub verification_error: Option<String>,
+}
+
+/// One file of a warehouse item downloaded from a post with several files.
+/// The item's own `file_path` is the main file, which is listed here too.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WarehouseItemFile {
+    pub id: String,
+    pub warehouse_item_id: String,
+    pub position: i64,
+    pub file_path: String,
+    /// "video", "audio", "image" or "file"
+    pub kind: String,
+    pub mime_type: Option<String>,
+    pub file_size: i64,
 }
 
 #[derive(Debug, Deserialize)]
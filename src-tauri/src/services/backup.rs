@@ -0,0 +1,568 @@
+use crate::commands::settings::load_settings;
+use crate::db::migrations::{self, SCHEMA_VERSION};
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Layout version of backup archives; bumped when the archive layout changes
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DATABASE_NAME: &str = "library.db";
+const SETTINGS_NAME: &str = "settings.json";
+
+/// manifest.json of a backup archive
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// `PRAGMA user_version` of the database snapshot
+    pub schema_version: i64,
+    pub created_at: String,
+    pub database: String,
+    /// App settings as JSON, for reading without opening the database; restoring
+    /// uses the copy in the database
+    pub settings: String,
+    pub cookie_files: Vec<BackupFile>,
+    pub thumbnails: Vec<BackupFile>,
+}
+
+/// A file stored in a backup besides the database
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Credential or warehouse item the file belongs to
+    pub owner_id: String,
+    /// Where the file was when the backup was made; restoring goes by the database
+    pub original_path: String,
+    pub archive_path: String,
+}
+
+/// Write a backup archive of the database at `db_path`, its settings and
+/// credential cookie files, and optionally warehouse thumbnails.
+///
+/// The database is copied with SQLite's online backup API from a separate
+/// read-only connection in a single step, so the snapshot is consistent while
+/// the app keeps writing through WAL.
+pub fn create_backup(db_path: &Path, output_path: &Path, include_thumbnails: bool) -> Result<BackupManifest, String> {
+    let snapshot_path = with_suffix(output_path, ".db.part");
+    let archive_part = with_suffix(output_path, ".part");
+    let result = write_archive(db_path, &snapshot_path, &archive_part, include_thumbnails);
+    let _ = std::fs::remove_file(&snapshot_path);
+
+    match result {
+        Ok(manifest) => {
+            std::fs::rename(&archive_part, output_path)
+                .map_err(|e| format!("Failed to move backup into place: {}", e))?;
+            Ok(manifest)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&archive_part);
+            Err(e)
+        }
+    }
+}
+
+fn write_archive(
+    db_path: &Path,
+    snapshot_path: &Path,
+    archive_path: &Path,
+    include_thumbnails: bool,
+) -> Result<BackupManifest, String> {
+    let _ = std::fs::remove_file(snapshot_path);
+    let source = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut snapshot = Connection::open(snapshot_path).map_err(|e| e.to_string())?;
+    copy_database(&source, &mut snapshot)?;
+
+    // Everything else comes from the snapshot, so it matches the database
+    let schema_version = migrations::schema_version(&snapshot).map_err(|e| e.to_string())?;
+    let settings = load_settings(&snapshot).map_err(|e| e.to_string())?;
+    let settings = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    let cookie_files = existing_files(
+        &snapshot,
+        "SELECT id, cookie_path FROM credentials ORDER BY id",
+        "cookies",
+    )?;
+    let thumbnails = if include_thumbnails {
+        existing_files(
+            &snapshot,
+            "SELECT id, thumbnail_path FROM warehouse_items WHERE thumbnail_path IS NOT NULL ORDER BY id",
+            "thumbnails",
+        )?
+    } else {
+        Vec::new()
+    };
+    drop(snapshot);
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        database: DATABASE_NAME.to_string(),
+        settings: SETTINGS_NAME.to_string(),
+        cookie_files,
+        thumbnails,
+    };
+
+    let file = File::create(archive_path).map_err(|e| format!("Failed to create backup: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    add_file(&mut zip, options, DATABASE_NAME, snapshot_path)?;
+    zip.start_file(SETTINGS_NAME, options).map_err(|e| e.to_string())?;
+    zip.write_all(settings.as_bytes()).map_err(|e| e.to_string())?;
+    for file in manifest.cookie_files.iter().chain(&manifest.thumbnails) {
+        add_file(&mut zip, options, &file.archive_path, Path::new(&file.original_path))?;
+    }
+    // Written last: an archive with a manifest was written completely
+    zip.start_file(MANIFEST_NAME, options).map_err(|e| e.to_string())?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    Ok(manifest)
+}
+
+/// Read and check the manifest of a backup archive
+pub fn read_manifest(archive_path: &Path) -> Result<BackupManifest, String> {
+    let mut archive = open_archive(archive_path)?;
+    manifest_from(&mut archive)
+}
+
+/// Replace the live database with the one in a backup archive.
+///
+/// The manifest and the database snapshot are checked before anything is touched:
+/// the archive must come from a build with the same or an older schema, and the
+/// snapshot must pass an integrity check and match the manifest's schema version.
+/// Cookie files are restored to `{app data}/cookies` and their credentials pointed
+/// there. Thumbnails go back to the paths the restored database has for them, if
+/// those are in the app data or library folder and no file is already there.
+/// Manifest entries for credentials or items the database doesn't have are
+/// rejected, so a damaged or crafted archive can't write anywhere else.
+/// Older snapshots are migrated before they replace the live database. Files are
+/// extracted to a staging folder and only moved into place once the database has
+/// been replaced, so a failed restore leaves the live cookies and thumbnails alone.
+pub fn restore_backup(live: &mut Connection, archive_path: &Path, app_data_dir: &Path) -> Result<BackupManifest, String> {
+    let mut archive = open_archive(archive_path)?;
+    let manifest = manifest_from(&mut archive)?;
+
+    let staging_path = app_data_dir.join("restore.db.part");
+    let staging_dir = app_data_dir.join("restore.part");
+    let _ = std::fs::remove_file(&staging_path);
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    let result = (|| {
+        extract(&mut archive, &manifest.database, &staging_path)?;
+        let staging = Connection::open(&staging_path).map_err(|e| e.to_string())?;
+
+        let integrity: String = staging
+            .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+            .map_err(|e| format!("Backup database is unreadable: {}", e))?;
+        if integrity != "ok" {
+            return Err(format!("Backup database is damaged: {}", integrity));
+        }
+        let schema_version = migrations::schema_version(&staging).map_err(|e| e.to_string())?;
+        if schema_version != manifest.schema_version {
+            return Err(format!(
+                "Backup database is at schema version {}, but its manifest says {}",
+                schema_version, manifest.schema_version
+            ));
+        }
+
+        migrations::run_all(&staging).map_err(|e| format!("Failed to migrate backup database: {}", e))?;
+
+        // (staged file, final path) for every file to move into place
+        let mut restored_files = Vec::new();
+        std::fs::create_dir_all(&staging_dir).map_err(|e| e.to_string())?;
+
+        let cookies_dir = app_data_dir.join("cookies");
+        for cookie_file in &manifest.cookie_files {
+            let credential_id = Uuid::parse_str(&cookie_file.owner_id)
+                .ok()
+                .filter(|_| has_row(&staging, "credentials", &cookie_file.owner_id))
+                .ok_or_else(|| format!("Backup has a cookie file for an unknown credential: {}", cookie_file.owner_id))?;
+            let file_name = format!("{}.txt", credential_id.hyphenated());
+            let staged = staging_dir.join(format!("cookie-{}", file_name));
+            extract(&mut archive, &cookie_file.archive_path, &staged)?;
+            let path = cookies_dir.join(file_name);
+            staging
+                .execute(
+                    "UPDATE credentials SET cookie_path = ? WHERE id = ?",
+                    (path.to_string_lossy(), &cookie_file.owner_id),
+                )
+                .map_err(|e| e.to_string())?;
+            restored_files.push((staged, path));
+        }
+
+        // Only the live library folder counts; the backup's own settings could name any folder
+        let mut allowed_dirs = vec![app_data_dir.to_path_buf()];
+        allowed_dirs.extend(
+            load_settings(live)
+                .ok()
+                .map(|settings| PathBuf::from(settings.library_path))
+                .filter(|path| path.is_absolute()),
+        );
+        for (index, thumbnail) in manifest.thumbnails.iter().enumerate() {
+            let thumbnail_path: Option<Option<String>> = staging
+                .query_row(
+                    "SELECT thumbnail_path FROM warehouse_items WHERE id = ?",
+                    [&thumbnail.owner_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            let Some(thumbnail_path) = thumbnail_path else {
+                return Err(format!("Backup has a thumbnail for an unknown item: {}", thumbnail.owner_id));
+            };
+
+            // Thumbnails from a library that was elsewhere are left out
+            let Some(path) = thumbnail_path.map(PathBuf::from).filter(|path| is_inside(path, &allowed_dirs)) else {
+                continue;
+            };
+            if path.exists() {
+                continue;
+            }
+            let staged = staging_dir.join(format!("thumbnail-{}", index));
+            extract(&mut archive, &thumbnail.archive_path, &staged)?;
+            restored_files.push((staged, path));
+        }
+
+        copy_database(&staging, live)?;
+        drop(staging);
+
+        // The database is restored; a file that can't be moved is reported but
+        // doesn't undo it
+        let mut failed = Vec::new();
+        for (staged, path) in restored_files {
+            if let Err(e) = move_file(&staged, &path) {
+                failed.push(format!("{}: {}", path.display(), e));
+            }
+        }
+        if !failed.is_empty() {
+            return Err(format!(
+                "The database was restored, but some files could not be: {}",
+                failed.join("; ")
+            ));
+        }
+        Ok(())
+    })();
+    let _ = std::fs::remove_file(&staging_path);
+    let _ = std::fs::remove_dir_all(&staging_dir);
+
+    result.map(|_| manifest)
+}
+
+fn has_row(conn: &Connection, table: &str, id: &str) -> bool {
+    conn.query_row(&format!("SELECT 1 FROM {} WHERE id = ?", table), [id], |_| Ok(()))
+        .is_ok()
+}
+
+/// Whether `path` is an absolute path inside one of `dirs`, without `..` steps
+fn is_inside(path: &Path, dirs: &[PathBuf]) -> bool {
+    path.is_absolute()
+        && !path.components().any(|c| matches!(c, Component::ParentDir | Component::CurDir))
+        && dirs.iter().any(|dir| path.starts_with(dir))
+}
+
+/// Move a file, creating the destination folder and falling back to a copy
+/// across file systems
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Copy a whole database in one backup step, which holds a single read
+/// transaction on the source for a consistent copy
+fn copy_database(source: &Connection, destination: &mut Connection) -> Result<(), String> {
+    let backup = Backup::new(source, destination).map_err(|e| format!("Failed to start backup: {}", e))?;
+    match backup.step(-1).map_err(|e| format!("Backup failed: {}", e))? {
+        StepResult::Done => Ok(()),
+        _ => Err("Database is busy, try again".to_string()),
+    }
+}
+
+fn open_archive(archive_path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))
+}
+
+fn manifest_from(archive: &mut ZipArchive<File>) -> Result<BackupManifest, String> {
+    let mut json = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| "Backup has no manifest; it may be incomplete".to_string())?
+        .read_to_string(&mut json)
+        .map_err(|e| e.to_string())?;
+    let manifest: BackupManifest = serde_json::from_str(&json).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err("This backup was made by a newer version of the app. Please update to restore it.".to_string());
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(format!(
+            "This backup has database schema version {}, but this version of the app only knows up to {}. Please update to restore it.",
+            manifest.schema_version, SCHEMA_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Files named by an `id, path` query that still exist, with their place in the archive
+fn existing_files(conn: &Connection, sql: &str, folder: &str) -> Result<Vec<BackupFile>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|(_, path)| Path::new(path).is_file())
+        .map(|(owner_id, original_path)| {
+            let extension = Path::new(&original_path)
+                .extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .unwrap_or_default();
+            BackupFile {
+                archive_path: format!("{}/{}{}", folder, owner_id, extension),
+                owner_id,
+                original_path,
+            }
+        })
+        .collect())
+}
+
+fn add_file(zip: &mut ZipWriter<File>, options: SimpleFileOptions, name: &str, path: &Path) -> Result<(), String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    std::io::copy(&mut file, zip).map_err(|e| format!("Failed to write {} to backup: {}", name, e))?;
+    Ok(())
+}
+
+fn extract(archive: &mut ZipArchive<File>, name: &str, path: &Path) -> Result<(), String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| format!("Backup is missing {}", name))?;
+    let mut file = File::create(path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    std::io::copy(&mut entry, &mut file).map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+    Ok(())
+}
+
+/// `backup.zip` -> `backup.zip{suffix}`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database_path;
+
+    const CREDENTIAL_ID: &str = "2f0c3a53-5b0e-4c1f-9a53-0c6b3c2d8e11";
+    const ITEM_ID: &str = "item-1";
+
+    /// A library with one credential and one thumbnailed item, in a folder of its own
+    struct Fixture {
+        root: PathBuf,
+        app_data: PathBuf,
+        library: PathBuf,
+        live: Connection,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("backup-test-{}", Uuid::new_v4()));
+            let app_data = root.join("app");
+            let library = root.join("library");
+            std::fs::create_dir_all(&app_data).unwrap();
+            std::fs::create_dir_all(&library).unwrap();
+
+            let cookie_path = root.join("exported-cookies.txt");
+            let thumbnail_path = library.join("thumb.jpg");
+            std::fs::write(&cookie_path, "cookies").unwrap();
+            std::fs::write(&thumbnail_path, "thumbnail").unwrap();
+
+            let live = Connection::open(database_path(&app_data)).unwrap();
+            migrations::run_all(&live).unwrap();
+            live.execute("INSERT INTO app_settings (id, library_path) VALUES (1, ?)", [library.to_string_lossy()])
+                .unwrap();
+            live.execute(
+                "INSERT INTO credentials (id, label, platform, cookie_path, created_at, updated_at)
+                 VALUES (?, 'Main', 'patreon', ?, '', '')",
+                (CREDENTIAL_ID, cookie_path.to_string_lossy()),
+            )
+            .unwrap();
+            live.execute(
+                "INSERT INTO creators (id, name, created_at, updated_at) VALUES ('creator-1', 'Creator', '', '')",
+                [],
+            )
+            .unwrap();
+            live.execute(
+                "INSERT INTO warehouse_items (id, creator_id, title, file_path, thumbnail_path, file_size, imported_at)
+                 VALUES (?, 'creator-1', 'Video', ?, ?, 0, '')",
+                (ITEM_ID, library.join("video.mp4").to_string_lossy(), thumbnail_path.to_string_lossy()),
+            )
+            .unwrap();
+
+            Self { root, app_data, library, live }
+        }
+
+        fn backup(&self) -> PathBuf {
+            let path = self.root.join("backup.zip");
+            create_backup(&database_path(&self.app_data), &path, true).unwrap();
+            path
+        }
+
+        fn restore(&mut self, archive: &Path) -> Result<BackupManifest, String> {
+            restore_backup(&mut self.live, archive, &self.app_data)
+        }
+
+        fn credential_count(&self) -> i64 {
+            self.live.query_row("SELECT COUNT(*) FROM credentials", [], |row| row.get(0)).unwrap()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// Copy of a backup archive with its manifest changed by `edit`
+    fn with_manifest(archive_path: &Path, edit: impl Fn(&mut serde_json::Value)) -> PathBuf {
+        let mut archive = open_archive(archive_path).unwrap();
+        let output = with_suffix(archive_path, ".edited.zip");
+        let mut zip = ZipWriter::new(File::create(&output).unwrap());
+        let options = SimpleFileOptions::default();
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).unwrap();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            if entry.name() == MANIFEST_NAME {
+                let mut manifest: serde_json::Value = serde_json::from_slice(&content).unwrap();
+                edit(&mut manifest);
+                content = serde_json::to_vec(&manifest).unwrap();
+            }
+            zip.start_file(entry.name(), options).unwrap();
+            zip.write_all(&content).unwrap();
+        }
+        zip.finish().unwrap();
+        output
+    }
+
+    #[test]
+    fn restores_database_cookies_and_thumbnails() {
+        let mut fixture = Fixture::new();
+        let archive = fixture.backup();
+
+        fixture.live.execute("DELETE FROM credentials", []).unwrap();
+        std::fs::remove_file(fixture.library.join("thumb.jpg")).unwrap();
+
+        let manifest = fixture.restore(&archive).unwrap();
+        assert_eq!(manifest.schema_version, SCHEMA_VERSION);
+        assert_eq!(fixture.credential_count(), 1);
+
+        let cookie_path: String = fixture
+            .live
+            .query_row("SELECT cookie_path FROM credentials", [], |row| row.get(0))
+            .unwrap();
+        let expected = fixture.app_data.join("cookies").join(format!("{}.txt", CREDENTIAL_ID));
+        assert_eq!(PathBuf::from(cookie_path), expected);
+        assert_eq!(std::fs::read_to_string(expected).unwrap(), "cookies");
+        assert_eq!(std::fs::read_to_string(fixture.library.join("thumb.jpg")).unwrap(), "thumbnail");
+        assert!(!fixture.app_data.join("restore.part").exists());
+    }
+
+    #[test]
+    fn rejects_newer_format_version() {
+        let mut fixture = Fixture::new();
+        let archive = with_manifest(&fixture.backup(), |m| m["format_version"] = (BACKUP_FORMAT_VERSION + 1).into());
+        assert!(fixture.restore(&archive).unwrap_err().contains("newer version"));
+    }
+
+    #[test]
+    fn rejects_newer_schema_version() {
+        let mut fixture = Fixture::new();
+        let archive = with_manifest(&fixture.backup(), |m| m["schema_version"] = (SCHEMA_VERSION + 1).into());
+        assert!(fixture.restore(&archive).unwrap_err().contains("only knows up to"));
+    }
+
+    #[test]
+    fn rejects_manifest_that_disagrees_with_database() {
+        let mut fixture = Fixture::new();
+        let archive = with_manifest(&fixture.backup(), |m| m["schema_version"] = (SCHEMA_VERSION - 1).into());
+        fixture.live.execute("DELETE FROM credentials", []).unwrap();
+
+        assert!(fixture.restore(&archive).unwrap_err().contains("but its manifest says"));
+        assert_eq!(fixture.credential_count(), 0);
+    }
+
+    #[test]
+    fn rejects_cookie_files_outside_the_cookie_folder() {
+        let mut fixture = Fixture::new();
+        let archive = with_manifest(&fixture.backup(), |m| m["cookie_files"][0]["owner_id"] = "../../escaped".into());
+        fixture.live.execute("DELETE FROM credentials", []).unwrap();
+
+        assert!(fixture.restore(&archive).unwrap_err().contains("unknown credential"));
+        assert!(!fixture.root.join("escaped.txt").exists());
+        assert_eq!(fixture.credential_count(), 0);
+    }
+
+    #[test]
+    fn thumbnails_go_where_the_database_says() {
+        let mut fixture = Fixture::new();
+        let outside = fixture.root.join("outside.jpg");
+        let archive = with_manifest(&fixture.backup(), |m| {
+            m["thumbnails"][0]["original_path"] = outside.to_string_lossy().into()
+        });
+        std::fs::remove_file(fixture.library.join("thumb.jpg")).unwrap();
+
+        fixture.restore(&archive).unwrap();
+        assert!(!outside.exists());
+        assert!(fixture.library.join("thumb.jpg").exists());
+    }
+
+    #[test]
+    fn skips_thumbnails_outside_the_library() {
+        let mut fixture = Fixture::new();
+        let outside = fixture.root.join("outside.jpg");
+        fixture
+            .live
+            .execute("UPDATE warehouse_items SET thumbnail_path = ?", [outside.to_string_lossy()])
+            .unwrap();
+        std::fs::rename(fixture.library.join("thumb.jpg"), &outside).unwrap();
+        let archive = fixture.backup();
+        std::fs::remove_file(&outside).unwrap();
+
+        fixture.restore(&archive).unwrap();
+        assert!(!outside.exists());
+    }
+
+    #[test]
+    fn only_absolute_paths_inside_allowed_folders_count() {
+        let library = std::env::temp_dir().join("library");
+        let dirs = [library.clone()];
+        assert!(is_inside(&library.join("a").join("thumb.jpg"), &dirs));
+        assert!(!is_inside(&library.join("..").join("thumb.jpg"), &dirs));
+        assert!(!is_inside(Path::new("library/thumb.jpg"), &dirs));
+        assert!(!is_inside(&std::env::temp_dir().join("elsewhere.jpg"), &dirs));
+    }
+}
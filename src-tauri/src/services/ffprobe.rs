@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Stream and container details of a media file as reported by ffprobe
+#[derive(Debug, Clone, Default)]
+pub struct MediaProbe {
+    pub duration: Option<i64>,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub bitrate: Option<i64>,
+    pub fps: Option<f64>,
+}
+
+/// Why a file couldn't be verified
+enum ProbeFailure {
+    /// ffprobe couldn't be run or its output read; says nothing about the file
+    Tool,
+    /// ffprobe read the file and found it damaged
+    Corrupt(String),
+}
+
+/// Probe a media file and check that it actually decodes.
+///
+/// Reading the container header is not enough to catch truncated downloads,
+/// so one frame is decoded at the start and one near the end of the file.
+/// Returns an error describing why the file is considered corrupt, or None when
+/// ffprobe itself failed and the file can't be judged.
+pub fn verify_media(ffprobe_path: &Path, file_path: &str) -> Option<Result<MediaProbe, String>> {
+    match check_media(ffprobe_path, file_path) {
+        Ok(probe) => Some(Ok(probe)),
+        Err(ProbeFailure::Corrupt(reason)) => Some(Err(reason)),
+        Err(ProbeFailure::Tool) => None,
+    }
+}
+
+fn check_media(ffprobe_path: &Path, file_path: &str) -> Result<MediaProbe, ProbeFailure> {
+    let probe = probe_streams(ffprobe_path, file_path)?;
+
+    let stream = if probe.video_codec.is_some() { "v:0" } else { "a:0" };
+    let mut intervals = vec!["%+#1".to_string()];
+    if let Some(duration) = probe.duration.filter(|d| *d > 10) {
+        intervals.push(format!("{}%+#1", duration - 5));
+    }
+
+    let output = run_ffprobe(
+        ffprobe_path,
+        &[
+            "-v", "error",
+            "-select_streams", stream,
+            "-read_intervals", &intervals.join(","),
+            "-show_entries", "frame=pts_time",
+            "-of", "csv=p=0",
+            file_path,
+        ],
+    )?;
+
+    let decoded_frames = output.lines().filter(|l| !l.trim().is_empty()).count();
+    if decoded_frames < intervals.len() {
+        return Err(ProbeFailure::Corrupt("File does not decode completely (truncated or damaged)".to_string()));
+    }
+
+    Ok(probe)
+}
+
+fn probe_streams(ffprobe_path: &Path, file_path: &str) -> Result<MediaProbe, ProbeFailure> {
+    let output = run_ffprobe(
+        ffprobe_path,
+        &["-v", "error", "-print_format", "json", "-show_format", "-show_streams", file_path],
+    )?;
+
+    let json: serde_json::Value = serde_json::from_str(&output).map_err(|_| ProbeFailure::Tool)?;
+
+    let streams = json["streams"].as_array().cloned().unwrap_or_default();
+    let video = streams
+        .iter()
+        .find(|s| s["codec_type"] == "video" && s["disposition"]["attached_pic"].as_i64() != Some(1));
+    let audio = streams.iter().find(|s| s["codec_type"] == "audio");
+
+    if video.is_none() && audio.is_none() {
+        return Err(ProbeFailure::Corrupt("File has no audio or video streams".to_string()));
+    }
+
+    // ffprobe reports most numbers as strings
+    let number = |v: &serde_json::Value| v.as_str().and_then(|s| s.parse::<f64>().ok()).or(v.as_f64());
+
+    Ok(MediaProbe {
+        duration: number(&json["format"]["duration"]).map(|d| d.round() as i64),
+        width: video.and_then(|v| v["width"].as_i64()),
+        height: video.and_then(|v| v["height"].as_i64()),
+        video_codec: video.and_then(|v| v["codec_name"].as_str()).map(|s| s.to_string()),
+        audio_codec: audio.and_then(|a| a["codec_name"].as_str()).map(|s| s.to_string()),
+        bitrate: number(&json["format"]["bit_rate"]).map(|b| b as i64),
+        fps: video.and_then(|v| v["avg_frame_rate"].as_str()).and_then(parse_frame_rate),
+    })
+}
+
+/// Parse an ffprobe frame rate like "30000/1001"
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/')?;
+    let num: f64 = num.parse().ok()?;
+    let den: f64 = den.parse().ok()?;
+    if den == 0.0 || num == 0.0 {
+        return None;
+    }
+    Some((num / den * 100.0).round() / 100.0)
+}
+
+fn run_ffprobe(ffprobe_path: &Path, args: &[&str]) -> Result<String, ProbeFailure> {
+    let mut cmd = Command::new(ffprobe_path);
+    cmd.args(args);
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().map_err(|_| ProbeFailure::Tool)?;
+
+    // ffprobe exits with an error for files it can't demux
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("unknown error").trim().to_string();
+        return Err(ProbeFailure::Corrupt(format!("ffprobe could not read the file: {}", reason)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
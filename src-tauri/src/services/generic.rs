@@ -0,0 +1,125 @@
+use crate::services::platform::{published_at_from_json, AuthRequirement, ItemMetadata, ListedItem, PlatformFetcher};
+use crate::services::video_details::VideoDetails;
+use crate::services::ytdlp::{dump_json_batch, flat_playlist, BatchEntry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Any playlist or channel URL yt-dlp can extract (Vimeo, Odysee, PeerTube, Bandcamp, ...).
+/// Items keep the URL yt-dlp reported for them, since it can't be rebuilt from the ID.
+pub struct GenericFetcher;
+
+impl GenericFetcher {
+    /// External ID of an entry: the extractor's `id`, prefixed with the extractor
+    /// key so IDs from different sites can't collide in the download archive
+    fn external_id(v: &Value) -> Option<String> {
+        let id = v["id"].as_str().filter(|id| !id.is_empty())?;
+        let extractor = v["ie_key"]
+            .as_str()
+            .or_else(|| v["extractor_key"].as_str())
+            .map(|key| key.to_lowercase());
+
+        Some(match extractor {
+            Some(extractor) => format!("{}:{}", extractor, id),
+            None => id.to_string(),
+        })
+    }
+
+    fn thumbnail(v: &Value) -> Option<String> {
+        v["thumbnail"].as_str().map(|s| s.to_string()).or_else(|| {
+            v["thumbnails"]
+                .as_array()
+                .and_then(|t| t.last())
+                .and_then(|t| t["url"].as_str())
+                .map(|s| s.to_string())
+        })
+    }
+}
+
+impl PlatformFetcher for GenericFetcher {
+    fn platform(&self) -> &'static str {
+        "generic"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "yt-dlp"
+    }
+
+    fn auth(&self) -> AuthRequirement {
+        AuthRequirement::Optional
+    }
+
+    fn list(&self, channel_url: &str, cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<Vec<ListedItem>, String> {
+        let extra_args: Vec<&str> = match cookie_path {
+            Some(cookies) => vec!["--cookies", cookies],
+            None => Vec::new(),
+        };
+
+        // Flat entries carry `url`; a single-video URL yields a full entry with `webpage_url`
+        let items = flat_playlist(ytdlp_path, channel_url, &extra_args)?
+            .into_iter()
+            .filter_map(|v| {
+                let external_id = Self::external_id(&v)?;
+                let url = v["webpage_url"]
+                    .as_str()
+                    .or_else(|| v["url"].as_str())
+                    .filter(|url| url.starts_with("http"))?
+                    .to_string();
+
+                Some(ListedItem {
+                    title: v["title"]
+                        .as_str()
+                        .filter(|t| !t.is_empty())
+                        .unwrap_or(&url)
+                        .to_string(),
+                    thumbnail_url: Self::thumbnail(&v),
+                    published_at: published_at_from_json(&v),
+                    duration: v["duration"].as_f64().map(|d| d as i64),
+                    external_id,
+                    url: Some(url),
+                    description: None,
+                    content_kind: None,
+                    game: None,
+                    media: Vec::new(),
+                    access: None,
+                    metadata_complete: false,
+                })
+            })
+            .collect();
+
+        Ok(items)
+    }
+
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        cookie_path: Option<&str>,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String> {
+        let extra_args: Vec<&str> = match cookie_path {
+            Some(cookies) => vec!["--cookies", cookies],
+            None => Vec::new(),
+        };
+        let results = dump_json_batch(ytdlp_path, items, &extra_args)?;
+
+        Ok(results
+            .into_iter()
+            .map(|(id, result)| {
+                let metadata = result.map(|v| ItemMetadata {
+                    published_at: published_at_from_json(&v),
+                    duration: v["duration"].as_f64().map(|d| d as i64),
+                    thumbnail: Self::thumbnail(&v),
+                    title: v["title"].as_str().filter(|t| !t.is_empty()).map(|t| t.to_string()),
+                    game: None,
+                    details: Some(VideoDetails::from_json(&v)),
+                    access: None,
+                });
+                (id, metadata)
+            })
+            .collect())
+    }
+
+    fn canonical_url(&self, _external_id: &str) -> Option<String> {
+        None
+    }
+}
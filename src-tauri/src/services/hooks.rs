@@ -0,0 +1,341 @@
+use crate::models::hook::Hook;
+use crate::models::WarehouseItem;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt as _;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Hook output kept in the run record (tail of stdout + stderr)
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+/// How often a running hook is checked against its timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Outcome of running a single hook
+pub struct HookOutcome {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    /// Set when an ffmpeg hook replaced the media file
+    pub new_file_size: Option<i64>,
+}
+
+/// Run a hook for a warehouse item.
+///
+/// Command hooks run through the system shell with the item's fields in
+/// `N3M_*` environment variables and the item as JSON on stdin. ffmpeg hooks
+/// re-encode the file with the hook's arguments and replace it on success.
+/// A hook still running after `timeout` is killed, with everything it started,
+/// and counts as failed.
+pub fn run_hook(
+    hook: &Hook,
+    item: &WarehouseItem,
+    trigger: &str,
+    ffmpeg_path: Option<&Path>,
+    timeout: Option<Duration>,
+) -> HookOutcome {
+    let result = match hook.hook_type.as_str() {
+        "command" => run_command_hook(hook, item, trigger, timeout),
+        "ffmpeg" => match ffmpeg_path {
+            Some(ffmpeg) => run_ffmpeg_hook(hook, item, ffmpeg, timeout),
+            None => Err("ffmpeg not found".to_string()),
+        },
+        other => Err(format!("Unknown hook type: {}", other)),
+    };
+
+    result.unwrap_or_else(|e| HookOutcome {
+        success: false,
+        exit_code: None,
+        output: e,
+        new_file_size: None,
+    })
+}
+
+fn run_command_hook(
+    hook: &Hook,
+    item: &WarehouseItem,
+    trigger: &str,
+    timeout: Option<Duration>,
+) -> Result<HookOutcome, String> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", &hook.command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", &hook.command]);
+        cmd
+    };
+
+    if let Some(dir) = Path::new(&item.file_path).parent() {
+        cmd.current_dir(dir);
+    }
+
+    cmd.envs(item_env_vars(item, trigger))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    // Its own process group, so a timeout also stops what the shell started
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start hook: {}", e))?;
+
+    let mut payload = serde_json::to_value(item).map_err(|e| e.to_string())?;
+    payload["trigger"] = serde_json::Value::from(trigger);
+
+    // Written on a thread so a hook that never reads stdin can't block us past
+    // the timeout. Hooks that don't read it close it early; that's not an error.
+    if let Some(mut stdin) = child.stdin.take() {
+        let payload = payload.to_string();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(payload.as_bytes());
+        });
+    }
+
+    wait_for_hook(child, timeout)
+}
+
+fn run_ffmpeg_hook(
+    hook: &Hook,
+    item: &WarehouseItem,
+    ffmpeg_path: &Path,
+    timeout: Option<Duration>,
+) -> Result<HookOutcome, String> {
+    let input = Path::new(&item.file_path);
+    if !input.exists() {
+        return Err(format!("Media file does not exist: {}", item.file_path));
+    }
+
+    // Write next to the original and swap it in only if ffmpeg succeeds
+    let extension = input.extension().and_then(|e| e.to_str()).unwrap_or("mp4");
+    let temp_output = input.with_extension(format!("hook.{}", extension));
+
+    let mut cmd = Command::new(ffmpeg_path);
+    cmd.args(["-hide_banner", "-nostdin", "-y", "-i"])
+        .arg(input)
+        .args(split_args(&hook.command))
+        .arg(&temp_output)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    let mut outcome = wait_for_hook(child, timeout)?;
+
+    if !outcome.success {
+        let _ = std::fs::remove_file(&temp_output);
+        return Ok(outcome);
+    }
+
+    if let Err(e) = std::fs::rename(&temp_output, input) {
+        let _ = std::fs::remove_file(&temp_output);
+        outcome.success = false;
+        outcome.output.push_str(&format!("\nFailed to replace original file: {}", e));
+        return Ok(outcome);
+    }
+
+    outcome.new_file_size = std::fs::metadata(input).map(|m| m.len() as i64).ok();
+    Ok(outcome)
+}
+
+/// Wait for a hook process and collect its output, killing it once `timeout` has
+/// passed. Output is read on separate threads so a full pipe can't stall the hook.
+fn wait_for_hook(mut child: Child, timeout: Option<Duration>) -> Result<HookOutcome, String> {
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let status = loop {
+        match child.try_wait().map_err(|e| format!("Failed to wait for hook: {}", e))? {
+            Some(status) => break Some(status),
+            None if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                kill_process_tree(&mut child);
+                break None;
+            }
+            None => std::thread::sleep(POLL_INTERVAL),
+        }
+    };
+
+    let joined = |handle: Option<JoinHandle<Vec<u8>>>| handle.and_then(|h| h.join().ok()).unwrap_or_default();
+    let mut output = combine_output(&joined(stdout), &joined(stderr));
+
+    if status.is_none() {
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        let minutes = timeout.unwrap_or_default().as_secs() / 60;
+        output.push_str(&format!("Stopped after running for {} minutes", minutes));
+    }
+
+    Ok(HookOutcome {
+        success: status.is_some_and(|s| s.success()),
+        exit_code: status.and_then(|s| s.code()),
+        output,
+        new_file_size: None,
+    })
+}
+
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+/// Kill a hook and the processes it started. Children of a shell hook would
+/// otherwise keep running and hold its output pipes open.
+fn kill_process_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // The hook leads its own process group; a negative pid signals the group.
+        // `--` keeps kill from reading it as an option.
+        let _ = Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", child.id())])
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("taskkill");
+        cmd.args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW);
+        let _ = cmd.status();
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn item_env_vars(item: &WarehouseItem, trigger: &str) -> Vec<(&'static str, String)> {
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+    vec![
+        ("N3M_TRIGGER", trigger.to_string()),
+        ("N3M_ITEM_ID", item.id.clone()),
+        ("N3M_CREATOR_ID", item.creator_id.clone()),
+        ("N3M_FEED_ITEM_ID", optional(&item.feed_item_id)),
+        ("N3M_TITLE", item.title.clone()),
+        ("N3M_FILE_PATH", item.file_path.clone()),
+        ("N3M_PLATFORM", optional(&item.platform)),
+        ("N3M_ORIGINAL_URL", optional(&item.original_url)),
+        ("N3M_PUBLISHED_AT", optional(&item.published_at)),
+        ("N3M_DURATION", item.duration.map(|d| d.to_string()).unwrap_or_default()),
+        ("N3M_FILE_SIZE", item.file_size.to_string()),
+        ("N3M_IS_MANUAL_IMPORT", item.is_manual_import.to_string()),
+    ]
+}
+
+/// Split an argument preset on whitespace, keeping double-quoted parts together
+fn split_args(preset: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+
+    for c in preset.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+fn combine_output(stdout: &[u8], stderr: &[u8]) -> String {
+    let mut output = String::from_utf8_lossy(stdout).to_string();
+    let stderr = String::from_utf8_lossy(stderr);
+    if !stderr.trim().is_empty() {
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&stderr);
+    }
+
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return output;
+    }
+
+    // Keep the tail, where errors usually are, on a char boundary
+    let mut start = output.len() - MAX_OUTPUT_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    output[start..].to_string()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn spawn_shell(script: &str) -> Child {
+        Command::new("sh")
+            .args(["-c", script])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn collects_output_of_finished_hook() {
+        let outcome = wait_for_hook(spawn_shell("echo done; exit 3"), Some(Duration::from_secs(10))).unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.exit_code, Some(3));
+        assert!(outcome.output.contains("done"));
+    }
+
+    #[test]
+    fn kills_hook_and_its_children_after_timeout() {
+        // The background sleep keeps the pipes open unless the whole group is killed
+        let started = Instant::now();
+        let outcome = wait_for_hook(spawn_shell("sleep 30 & sleep 30"), Some(Duration::from_secs(1))).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!outcome.success);
+        assert_eq!(outcome.exit_code, None);
+        assert!(outcome.output.contains("Stopped after running"));
+    }
+}
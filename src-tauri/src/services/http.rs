@@ -0,0 +1,161 @@
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!("n3ms-media-library/", env!("CARGO_PKG_VERSION"));
+
+/// Feeds and pages larger than this are rejected
+const MAX_TEXT_BYTES: u64 = 10 * 1024 * 1024;
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(15))
+        .timeout_read(Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+fn describe_error(url: &str, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(code, _) => format!("HTTP Error {} for {}", code, url),
+        ureq::Error::Transport(e) => format!("Failed to fetch {}: {}", url, e),
+    }
+}
+
+/// Host of an http(s) URL, without port
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// Cookie header for a URL from a Netscape-format cookie file (the format yt-dlp reads)
+fn cookie_header(cookie_path: &str, url: &str) -> Result<Option<String>, String> {
+    let content = std::fs::read_to_string(cookie_path)
+        .map_err(|e| format!("Failed to read cookie file {}: {}", cookie_path, e))?;
+    let host = url_host(url).unwrap_or_default().to_lowercase();
+    let now = chrono::Utc::now().timestamp();
+
+    let cookies: Vec<String> = content
+        .lines()
+        .map(|line| line.strip_prefix("#HttpOnly_").unwrap_or(line))
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [domain, _, _, _, expires, name, value] = fields[..] else {
+                return None;
+            };
+            let domain = domain.trim_start_matches('.').to_lowercase();
+            let matches_host = host == domain || host.ends_with(&format!(".{}", domain));
+            // 0 marks a session cookie
+            let expired = expires.parse::<i64>().is_ok_and(|at| at != 0 && at < now);
+            (matches_host && !expired).then(|| format!("{}={}", name, value))
+        })
+        .collect();
+
+    Ok((!cookies.is_empty()).then(|| cookies.join("; ")))
+}
+
+/// Start a GET request, with cookies from a cookie file when given
+fn get(url: &str, cookie_path: Option<&str>) -> Result<ureq::Request, String> {
+    let mut request = agent().get(url);
+    if let Some(cookies) = cookie_path.map(|path| cookie_header(path, url)).transpose()?.flatten() {
+        request = request.set("Cookie", &cookies);
+    }
+    Ok(request)
+}
+
+/// Fetch a URL as text, e.g. a feed, a web page or an API response
+pub fn get_text(url: &str, cookie_path: Option<&str>) -> Result<String, String> {
+    let response = get(url, cookie_path)?.call().map_err(|e| describe_error(url, e))?;
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .take(MAX_TEXT_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    Ok(body)
+}
+
+/// Download a URL to a file, resuming from `{output_path}.part` when the server allows it.
+/// A part file that already holds the whole file is moved into place.
+///
+/// `on_progress` gets the bytes written so far and the total size when known;
+/// returning false stops the download and keeps the part file.
+pub fn download_file(
+    url: &str,
+    cookie_path: Option<&str>,
+    output_path: &str,
+    mut on_progress: impl FnMut(u64, Option<u64>) -> bool,
+) -> Result<(), String> {
+    let part_path = format!("{}.part", output_path);
+    let existing = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = get(url, cookie_path)?;
+    if existing > 0 {
+        request = request.set("Range", &format!("bytes={}-", existing));
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // Nothing past the end of the part file: it's complete if the server says the
+        // file is exactly that long. Otherwise the part file can't be trusted.
+        Err(ureq::Error::Status(416, response)) if existing > 0 => {
+            let file_size = response
+                .header("Content-Range")
+                .and_then(|range| range.strip_prefix("bytes */"))
+                .and_then(|len| len.parse::<u64>().ok());
+            if file_size == Some(existing) {
+                on_progress(existing, Some(existing));
+                return std::fs::rename(&part_path, output_path)
+                    .map_err(|e| format!("Failed to move download into place: {}", e));
+            }
+            std::fs::remove_file(&part_path).map_err(|e| format!("Failed to remove {}: {}", part_path, e))?;
+            return download_file(url, cookie_path, output_path, on_progress);
+        }
+        Err(e) => return Err(describe_error(url, e)),
+    };
+
+    // 206 continues the part file; a full response starts over
+    let resumed = existing > 0 && response.status() == 206;
+    let mut written = if resumed { existing } else { 0 };
+    let total = response
+        .header("Content-Length")
+        .and_then(|len| len.parse::<u64>().ok())
+        .map(|len| len + written);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part_path)
+        .map_err(|e| format!("Failed to open {}: {}", part_path, e))?;
+
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Download interrupted: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {}: {}", part_path, e))?;
+        written += read as u64;
+
+        if !on_progress(written, total) {
+            return Err("Download cancelled".to_string());
+        }
+    }
+
+    if let Some(total) = total.filter(|total| written < *total) {
+        return Err(format!("Download ended early ({} of {} bytes)", written, total));
+    }
+
+    drop(file);
+    std::fs::rename(&part_path, output_path)
+        .map_err(|e| format!("Failed to move download into place: {}", e))
+}
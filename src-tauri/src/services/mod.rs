@@ -1,7 +1,26 @@
+pub mod backup;
+pub mod ffprobe;
+pub mod generic;
+pub mod hooks;
+pub mod http;
+pub mod nfo;
 pub mod patreon;
+pub mod platform;
+pub mod rate_limit;
+pub mod refresh_policy;
+pub mod rss;
 pub mod sidecar;
+pub mod sponsorblock;
+pub mod subscriptions;
+pub mod twitch;
+pub mod video_details;
 pub mod youtube;
+pub mod ytdlp;
 
+pub use generic::GenericFetcher;
 pub use patreon::PatreonFetcher;
-pub use sidecar::{get_ffmpeg_path, get_ytdlp_path, is_ffmpeg_available};
+pub use platform::{get_fetcher, PlatformFetcher};
+pub use rss::RssFetcher;
+pub use twitch::TwitchFetcher;
+pub use sidecar::{get_ffmpeg_path, get_ffprobe_path, get_ytdlp_path, is_ffmpeg_available};
 pub use youtube::YouTubeFetcher;
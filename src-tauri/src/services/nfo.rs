@@ -0,0 +1,234 @@
+use crate::models::{Creator, WarehouseItem};
+use std::path::{Path, PathBuf};
+
+/// Write media server sidecar files (Jellyfin/Kodi/Plex) for a warehouse item.
+///
+/// The library layout is `{library}/{creator}/{platform}/{file}`, which media
+/// servers read as show / season / episode; post downloads sit one folder deeper,
+/// so callers pass the creator folder. With `write_nfo` this writes `{file}.nfo`
+/// next to the media file and `tvshow.nfo` plus poster art in `creator_dir` if
+/// they don't exist yet. With `write_info_json` it writes `{file}.info.json`.
+/// `external_id` is the platform's ID of a downloaded item.
+pub fn write_sidecars(
+    item: &WarehouseItem,
+    creator: &Creator,
+    external_id: Option<&str>,
+    creator_dir: Option<&Path>,
+    write_nfo: bool,
+    write_info_json: bool,
+) -> Result<(), String> {
+    let media_path = Path::new(&item.file_path);
+    if !media_path.exists() {
+        return Err(format!("Media file does not exist: {}", item.file_path));
+    }
+
+    let info_json_path = sidecar_path(media_path, "info.json");
+
+    // yt-dlp writes its own info.json for downloads; only fill in the gaps
+    if write_info_json && !info_json_path.exists() {
+        write_item_info_json(&info_json_path, item, creator, external_id)?;
+    }
+
+    if write_nfo {
+        let description = read_description(&info_json_path);
+        let nfo = item_nfo(item, creator, description.as_deref());
+        std::fs::write(sidecar_path(media_path, "nfo"), nfo).map_err(|e| format!("Failed to write NFO file: {}", e))?;
+
+        if let Some(creator_dir) = creator_dir {
+            write_creator_nfo(creator_dir, creator)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `/dir/name.mp4` -> `/dir/name.{extension}`
+fn sidecar_path(media_path: &Path, extension: &str) -> PathBuf {
+    media_path.with_extension(extension)
+}
+
+fn item_nfo(item: &WarehouseItem, creator: &Creator, description: Option<&str>) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<episodedetails>\n");
+
+    push_tag(&mut xml, "title", &item.title);
+    push_tag(&mut xml, "showtitle", &creator.name);
+    if let Some(description) = description {
+        push_tag(&mut xml, "plot", description);
+    }
+    if let Some(date) = item.published_at.as_deref().and_then(|d| d.get(0..10)) {
+        push_tag(&mut xml, "aired", date);
+        push_tag(&mut xml, "premiered", date);
+        push_tag(&mut xml, "year", &date[0..4]);
+    }
+    if let Some(duration) = item.duration {
+        // NFO runtime is in minutes
+        push_tag(&mut xml, "runtime", &((duration + 59) / 60).to_string());
+    }
+    if let Some(platform) = &item.platform {
+        push_tag(&mut xml, "studio", platform);
+    }
+    xml.push_str(&format!(
+        "  <uniqueid type=\"n3ms\" default=\"true\">{}</uniqueid>\n",
+        escape_xml(&item.id)
+    ));
+    // Not <trailer>: media servers would offer the source page as a playable trailer
+    if let Some(url) = &item.original_url {
+        xml.push_str(&format!("  <uniqueid type=\"url\">{}</uniqueid>\n", escape_xml(url)));
+    }
+    push_tag(&mut xml, "dateadded", &item.imported_at);
+
+    xml.push_str("</episodedetails>\n");
+    xml
+}
+
+fn write_item_info_json(
+    info_json_path: &Path,
+    item: &WarehouseItem,
+    creator: &Creator,
+    external_id: Option<&str>,
+) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(&item_info(item, creator, external_id)).map_err(|e| e.to_string())?;
+    std::fs::write(info_json_path, content).map_err(|e| format!("Failed to write info.json: {}", e))
+}
+
+/// The fields of a yt-dlp info.json that media server plugins read. `id` is the
+/// platform's video ID, as in yt-dlp's own files.
+fn item_info(item: &WarehouseItem, creator: &Creator, external_id: Option<&str>) -> serde_json::Value {
+    let upload_date = item
+        .published_at
+        .as_deref()
+        .and_then(|d| d.get(0..10))
+        .map(|d| d.replace('-', ""));
+
+    serde_json::json!({
+        "id": external_id,
+        "title": item.title,
+        "uploader": creator.name,
+        "channel": creator.name,
+        "upload_date": upload_date,
+        "duration": item.duration,
+        "webpage_url": item.original_url,
+        "extractor": item.platform,
+        "filesize": item.file_size,
+    })
+}
+
+/// Write `tvshow.nfo` and copy the creator photo as `poster.*`, unless already present
+fn write_creator_nfo(creator_dir: &Path, creator: &Creator) -> Result<(), String> {
+    let tvshow_path = creator_dir.join("tvshow.nfo");
+    if !tvshow_path.exists() {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<tvshow>\n");
+        push_tag(&mut xml, "title", &creator.name);
+        push_tag(&mut xml, "dateadded", &creator.created_at);
+        xml.push_str("</tvshow>\n");
+
+        std::fs::write(&tvshow_path, xml).map_err(|e| format!("Failed to write tvshow.nfo: {}", e))?;
+    }
+
+    if let Some(photo_path) = creator.photo_path.as_deref().map(Path::new) {
+        let extension = photo_path.extension().and_then(|e| e.to_str()).unwrap_or("jpg");
+        let poster_path = creator_dir.join(format!("poster.{}", extension));
+        if photo_path.exists() && !poster_path.exists() {
+            std::fs::copy(photo_path, &poster_path).map_err(|e| format!("Failed to copy poster: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the description from an info.json written by yt-dlp, if any
+fn read_description(info_json_path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(info_json_path).ok()?;
+    let value = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    value["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+        .map(|d| d.to_string())
+}
+
+fn push_tag(xml: &mut String, tag: &str, value: &str) {
+    xml.push_str(&format!("  <{}>{}</{}>\n", tag, escape_xml(value), tag));
+}
+
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (WarehouseItem, Creator) {
+        let item = WarehouseItem {
+            id: "5f0c6c1e-0000-4000-8000-000000000001".to_string(),
+            creator_id: "5f0c6c1e-0000-4000-8000-000000000002".to_string(),
+            feed_item_id: Some("5f0c6c1e-0000-4000-8000-000000000003".to_string()),
+            title: "Q&A <live> \"special\"".to_string(),
+            file_path: "/library/Tom & Jerry/youtube/qa.mp4".to_string(),
+            thumbnail_path: None,
+            platform: Some("youtube".to_string()),
+            original_url: Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42".to_string()),
+            published_at: Some("2024-03-05T12:00:00Z".to_string()),
+            duration: Some(61),
+            file_size: 1024,
+            imported_at: "2024-03-06T08:00:00Z".to_string(),
+            is_manual_import: false,
+            width: None,
+            height: None,
+            video_codec: None,
+            audio_codec: None,
+            bitrate: None,
+            fps: None,
+            verification_status: "verified".to_string(),
+            verification_error: None,
+        };
+        let creator = Creator {
+            id: item.creator_id.clone(),
+            name: "Tom & Jerry's".to_string(),
+            photo_path: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        (item, creator)
+    }
+
+    #[test]
+    fn escapes_item_nfo() {
+        let (item, creator) = sample();
+
+        assert_eq!(
+            item_nfo(&item, &creator, Some("Fish > chips")),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <episodedetails>\n\
+             \x20 <title>Q&amp;A &lt;live&gt; &quot;special&quot;</title>\n\
+             \x20 <showtitle>Tom &amp; Jerry&apos;s</showtitle>\n\
+             \x20 <plot>Fish &gt; chips</plot>\n\
+             \x20 <aired>2024-03-05</aired>\n\
+             \x20 <premiered>2024-03-05</premiered>\n\
+             \x20 <year>2024</year>\n\
+             \x20 <runtime>2</runtime>\n\
+             \x20 <studio>youtube</studio>\n\
+             \x20 <uniqueid type=\"n3ms\" default=\"true\">5f0c6c1e-0000-4000-8000-000000000001</uniqueid>\n\
+             \x20 <uniqueid type=\"url\">https://www.youtube.com/watch?v=dQw4w9WgXcQ&amp;t=42</uniqueid>\n\
+             \x20 <dateadded>2024-03-06T08:00:00Z</dateadded>\n\
+             </episodedetails>\n"
+        );
+    }
+
+    #[test]
+    fn info_json_uses_the_external_id() {
+        let (item, creator) = sample();
+
+        let info = item_info(&item, &creator, Some("dQw4w9WgXcQ"));
+        assert_eq!(info["id"], "dQw4w9WgXcQ");
+        assert_eq!(info["upload_date"], "20240305");
+        assert_eq!(info["uploader"], "Tom & Jerry's");
+
+        assert!(item_info(&item, &creator, None)["id"].is_null());
+    }
+}
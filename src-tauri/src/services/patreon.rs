@@ -1,4 +1,11 @@
+use crate::models::PostMedia;
+use crate::services::http;
+use crate::services::platform::{parse_upload_date, AuthRequirement, ItemMetadata, ListedItem, PlatformFetcher, PostAccess};
+use crate::services::video_details::VideoDetails;
+use crate::services::ytdlp::{dump_json_batch, BatchEntry};
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
@@ -8,6 +15,33 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// A single post from Patreon's JSON:API, with the same fields as `POSTS_API`
+const POST_API: &str = "https://www.patreon.com/api/posts/{post_id}\
+    ?include=audio,images,attachments,attachments_media,access_rules.tier.null\
+    &fields[post]=title,post_type,published_at,teaser_text,image,current_user_can_view,min_cents_pledged_to_view\
+    &fields[media]=file_name,mimetype,download_url,image_urls\
+    &fields[attachment]=name,url\
+    &fields[access-rule]=access_rule_type\
+    &fields[reward]=title,amount_cents\
+    &json-api-use-default-includes=false\
+    &json-api-version=1.0";
+
+/// First page of a campaign's posts from Patreon's JSON:API, newest first.
+/// Later pages are linked from `links.next`.
+const POSTS_API: &str = "https://www.patreon.com/api/posts\
+    ?filter[campaign_id]={campaign_id}\
+    &filter[contains_exclusive_posts]=true\
+    &filter[is_draft]=false\
+    &sort=-published_at\
+    &include=audio,images,attachments,attachments_media,access_rules.tier.null\
+    &fields[post]=title,post_type,published_at,teaser_text,image,current_user_can_view,min_cents_pledged_to_view\
+    &fields[media]=file_name,mimetype,download_url,image_urls\
+    &fields[attachment]=name,url\
+    &fields[access-rule]=access_rule_type\
+    &fields[reward]=title,amount_cents\
+    &json-api-use-default-includes=false\
+    &json-api-version=1.0";
+
 #[derive(Debug, Deserialize)]
 pub struct PatreonPost {
     pub id: String,
@@ -18,6 +52,9 @@ pub struct PatreonPost {
     /// True if title was extracted from URL slug (not the real title)
     #[serde(default)]
     pub title_is_fallback: bool,
+    /// Only filled by the full metadata fetch
+    #[serde(skip)]
+    pub details: Option<VideoDetails>,
 }
 
 pub struct PatreonFetcher;
@@ -148,6 +185,7 @@ impl PatreonFetcher {
                                 }),
                             duration: v["duration"].as_f64(),
                             upload_date: v["upload_date"].as_str().map(|s| s.to_string()),
+                            details: None,
                         })
                     })
             })
@@ -156,112 +194,544 @@ impl PatreonFetcher {
         Ok(posts)
     }
 
-    /// Convert upload_date (YYYYMMDD) to ISO 8601 format
-    pub fn parse_upload_date(upload_date: &str) -> Option<String> {
-        if upload_date.len() != 8 {
-            return None;
-        }
+    /// Campaign ID of a creator, from its page
+    fn campaign_id(creator_url: &str, cookie_path: &str) -> Result<String, String> {
+        let page_url = creator_url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(creator_url)
+            .trim_end_matches('/')
+            .trim_end_matches("/posts");
+        let html = http::get_text(page_url, Some(cookie_path))?;
 
-        let year = &upload_date[0..4];
-        let month = &upload_date[4..6];
-        let day = &upload_date[6..8];
+        ["patreon.com/api/campaigns/", "\"campaign\":{\"data\":{\"id\":\"", "\"campaign_id\":"]
+            .iter()
+            .find_map(|marker| {
+                let start = html.find(marker)? + marker.len();
+                let id: String = html[start..]
+                    .trim_start_matches('"')
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                (!id.is_empty()).then_some(id)
+            })
+            .ok_or_else(|| format!("Could not find the Patreon campaign of {}", creator_url))
+    }
+
+    /// List all posts of a creator through Patreon's API. Unlike yt-dlp, this also
+    /// sees audio, image and attachment posts.
+    pub fn fetch_campaign_posts(creator_url: &str, cookie_path: &str) -> Result<Vec<ListedItem>, String> {
+        let campaign_id = Self::campaign_id(creator_url, cookie_path)?;
+        let mut next = Some(POSTS_API.replace("{campaign_id}", &campaign_id));
+        let mut items = Vec::new();
+
+        while let Some(url) = next {
+            let body = http::get_text(&url, Some(cookie_path))?;
+            let page: Value =
+                serde_json::from_str(&body).map_err(|e| format!("Invalid Patreon API response: {}", e))?;
+
+            items.extend(Self::posts_from_page(&page));
+            next = page["links"]["next"].as_str().map(|s| s.to_string());
+        }
 
-        Some(format!("{}-{}-{}T00:00:00Z", year, month, day))
+        Ok(items)
     }
 
-    /// Fetch full metadata for a single Patreon post by its ID
-    /// Returns metadata including accurate published_at date
-    pub fn fetch_post_metadata(post_id: &str, cookie_path: &str, ytdlp_path: &Path) -> Result<PatreonPost, String> {
-        let url = format!("https://www.patreon.com/posts/{}", post_id);
+    /// Posts of an API response (a page of posts or a single one) that have something to
+    /// download, or that might once they're unlocked
+    fn posts_from_page(page: &Value) -> Vec<ListedItem> {
+        let included: HashMap<(&str, &str), &Value> = page["included"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| Some(((v["type"].as_str()?, v["id"].as_str()?), v)))
+            .collect();
 
-        let mut cmd = Command::new(ytdlp_path);
-        cmd.args([
-            "--dump-json",
-            "--no-download",
-            "--no-warnings",
-            "--cookies",
-            cookie_path,
-            &url,
-        ]);
+        let posts = match &page["data"] {
+            Value::Array(posts) => posts.iter().collect(),
+            post => vec![post],
+        };
 
-        #[cfg(target_os = "windows")]
-        cmd.creation_flags(CREATE_NO_WINDOW);
+        posts
+            .into_iter()
+            .filter_map(|post| {
+                let id = post["id"].as_str()?.to_string();
+                let attributes = &post["attributes"];
+                let content_kind = Self::post_kind(attributes["post_type"].as_str().unwrap_or_default());
+                let media = Self::attached_media(post, &included);
+                let access = Self::access(post, &included);
 
-        let output = cmd.output()
-            .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+                // Text, link and poll posts without files have nothing to download.
+                // Locked posts don't reveal their files.
+                if content_kind != "video" && media.is_empty() && !access.locked {
+                    return None;
+                }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // Check for common auth errors
-            if stderr.contains("Unable to download") || stderr.contains("HTTP Error 401") {
-                return Err(
-                    "Authentication failed. Please check your cookie file is valid and not expired."
+                let image = &attributes["image"];
+                Some(ListedItem {
+                    title: attributes["title"]
+                        .as_str()
+                        .map(|t| t.trim())
+                        .filter(|t| !t.is_empty())
+                        .unwrap_or("Untitled Post")
                         .to_string(),
-                );
+                    thumbnail_url: image["large_url"]
+                        .as_str()
+                        .or_else(|| image["url"].as_str())
+                        .or_else(|| image["thumb_url"].as_str())
+                        .map(|s| s.to_string()),
+                    published_at: attributes["published_at"]
+                        .as_str()
+                        .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                        .and_then(|d| parse_upload_date(&d.format("%Y%m%d").to_string())),
+                    duration: None,
+                    description: attributes["teaser_text"]
+                        .as_str()
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.to_string()),
+                    // Videos still need yt-dlp for their duration and details, unless
+                    // they're locked and yt-dlp can't see them either
+                    metadata_complete: content_kind != "video" || access.locked,
+                    content_kind: Some(content_kind.to_string()),
+                    game: None,
+                    media,
+                    access: Some(access),
+                    external_id: id,
+                    url: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the cookie's account can view a post, and the lowest tier that unlocks it
+    fn access(post: &Value, included: &HashMap<(&str, &str), &Value>) -> PostAccess {
+        let attributes = &post["attributes"];
+
+        // Posts can be restricted to several tiers; the cheapest one is the one to name
+        let tier = post["relationships"]["access_rules"]["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| included.get(&(r["type"].as_str()?, r["id"].as_str()?)))
+            .filter_map(|rule| {
+                let tier = &rule["relationships"]["tier"]["data"];
+                included.get(&(tier["type"].as_str()?, tier["id"].as_str()?))
+            })
+            .filter_map(|tier| {
+                let title = tier["attributes"]["title"].as_str().filter(|t| !t.is_empty())?;
+                Some((tier["attributes"]["amount_cents"].as_i64().unwrap_or(0), title))
+            })
+            .min_by_key(|(amount_cents, _)| *amount_cents)
+            .map(|(_, title)| title.to_string());
+
+        let min_cents = attributes["min_cents_pledged_to_view"].as_i64().filter(|cents| *cents > 0);
+        let required_tier = tier.or_else(|| {
+            min_cents.map(|cents| format!("${}.{:02} or more", cents / 100, cents % 100))
+        });
+
+        PostAccess {
+            // Posts without the flag are ones the API would only return if viewable
+            locked: attributes["current_user_can_view"].as_bool() == Some(false),
+            required_tier,
+        }
+    }
+
+    /// Fetch a single post through the API, as it would be listed
+    fn fetch_post(post_id: &str, cookie_path: Option<&str>) -> Option<ListedItem> {
+        let url = POST_API.replace("{post_id}", post_id);
+        let body = http::get_text(&url, cookie_path).ok()?;
+        let page: Value = serde_json::from_str(&body).ok()?;
+        Self::posts_from_page(&page).pop()
+    }
+
+    /// Content kind of a Patreon post type
+    fn post_kind(post_type: &str) -> &str {
+        match post_type {
+            "video_external_file" | "video_embed" | "livestream_youtube" | "livestream_crowdcast" => "video",
+            "audio_file" | "audio_embed" => "audio",
+            "image_file" => "image",
+            "text_only" => "text",
+            other => other,
+        }
+    }
+
+    /// Audio, images and attachments of a post, resolved from the page's `included` resources
+    fn attached_media(post: &Value, included: &HashMap<(&str, &str), &Value>) -> Vec<PostMedia> {
+        let relationships = &post["relationships"];
+        let mut media: Vec<PostMedia> = Vec::new();
+
+        for (relationship, default_kind) in [
+            ("audio", "audio"),
+            ("images", "image"),
+            ("attachments_media", "file"),
+            ("attachments", "file"),
+        ] {
+            // To-one relationships (audio) hold an object, to-many ones an array
+            let data = &relationships[relationship]["data"];
+            let refs = data.as_array().cloned().unwrap_or_else(|| vec![data.clone()]);
+
+            for resource in refs.iter().filter_map(|r| included.get(&(r["type"].as_str()?, r["id"].as_str()?))) {
+                let attributes = &resource["attributes"];
+                let url = attributes["download_url"]
+                    .as_str()
+                    .or_else(|| attributes["image_urls"]["original"].as_str())
+                    .or_else(|| attributes["image_urls"]["default"].as_str())
+                    .or_else(|| attributes["url"].as_str())
+                    .filter(|url| url.starts_with("http"));
+                let Some(url) = url else { continue };
+                if media.iter().any(|m| m.url == url) {
+                    continue;
+                }
+
+                let mime_type = attributes["mimetype"].as_str().map(|t| t.to_string());
+                let kind = match mime_type.as_deref() {
+                    Some(t) if t.starts_with("audio/") => "audio",
+                    Some(t) if t.starts_with("image/") => "image",
+                    Some(_) => "file",
+                    None => default_kind,
+                };
+
+                media.push(PostMedia {
+                    kind: kind.to_string(),
+                    url: url.to_string(),
+                    file_name: attributes["file_name"]
+                        .as_str()
+                        .or_else(|| attributes["name"].as_str())
+                        .map(|n| n.to_string()),
+                    mime_type,
+                });
             }
-            return Err(format!("yt-dlp failed: {}", stderr));
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        media
+    }
 
-        serde_json::from_str::<serde_json::Value>(&stdout)
-            .map_err(|e| format!("Failed to parse JSON: {}", e))
-            .and_then(|v| {
-                let id = v["id"].as_str().unwrap_or(post_id).to_string();
+    /// Fetch full metadata for many posts with a single yt-dlp run.
+    /// Returns a result per post ID; the outer error means yt-dlp couldn't run.
+    pub fn fetch_posts_metadata(
+        entries: &[BatchEntry],
+        cookie_path: &str,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<PatreonPost, String>>, String> {
+        let results = dump_json_batch(ytdlp_path, entries, &["--cookies", cookie_path])?;
+
+        Ok(results
+            .into_iter()
+            .map(|(id, result)| {
+                let post = result
+                    .map_err(|e| {
+                        if e.contains("Unable to download") || e.contains("HTTP Error 401") {
+                            "Authentication failed. Please check your cookie file is valid and not expired."
+                                .to_string()
+                        } else {
+                            e
+                        }
+                    })
+                    .and_then(|v| Self::post_from_json(&v, &id));
+                (id, post)
+            })
+            .collect())
+    }
 
-                // Try to get title from yt-dlp response (should work for individual posts)
-                let real_title = v["title"].as_str().map(|s| s.to_string());
+    fn post_from_json(v: &serde_json::Value, post_id: &str) -> Result<PatreonPost, String> {
+        let id = v["id"].as_str().unwrap_or(post_id).to_string();
 
-                let (title, title_is_fallback) = if let Some(t) = real_title {
-                    (t, false)
+        // Try to get title from yt-dlp response (should work for individual posts)
+        let real_title = v["title"].as_str().map(|s| s.to_string());
+
+        let (title, title_is_fallback) = if let Some(t) = real_title {
+            (t, false)
+        } else {
+            // Fall back to webpage_url_basename
+            let fallback = v["webpage_url_basename"].as_str().map(|basename| {
+                let parts: Vec<&str> = basename.rsplitn(2, '-').collect();
+                let title_slug = if parts.len() == 2 && parts[0].parse::<u64>().is_ok() {
+                    parts[1]
                 } else {
-                    // Fall back to webpage_url_basename
-                    let fallback = v["webpage_url_basename"].as_str().map(|basename| {
-                        let parts: Vec<&str> = basename.rsplitn(2, '-').collect();
-                        let title_slug = if parts.len() == 2 && parts[0].parse::<u64>().is_ok() {
-                            parts[1]
-                        } else {
-                            basename
-                        };
-                        title_slug
-                            .replace('-', " ")
-                            .split_whitespace()
-                            .map(|word| {
-                                let mut chars = word.chars();
-                                match chars.next() {
-                                    None => String::new(),
-                                    Some(first) => {
-                                        first.to_uppercase().chain(chars).collect()
-                                    }
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                            .join(" ")
-                    });
-                    (fallback.unwrap_or_default(), true)
+                    basename
                 };
+                title_slug
+                    .replace('-', " ")
+                    .split_whitespace()
+                    .map(|word| {
+                        let mut chars = word.chars();
+                        match chars.next() {
+                            None => String::new(),
+                            Some(first) => {
+                                first.to_uppercase().chain(chars).collect()
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+            (fallback.unwrap_or_default(), true)
+        };
 
-                if title.is_empty() {
-                    return Err("Could not extract title from response".to_string());
-                }
+        if title.is_empty() {
+            return Err("Could not extract title from response".to_string());
+        }
 
-                Ok(PatreonPost {
-                    id,
-                    title,
-                    title_is_fallback,
-                    thumbnail: v["thumbnail"]
-                        .as_str()
+        Ok(PatreonPost {
+            id,
+            title,
+            title_is_fallback,
+            thumbnail: v["thumbnail"]
+                .as_str()
+                .map(|s| s.to_string())
+                .or_else(|| {
+                    v["thumbnails"]
+                        .as_array()
+                        .and_then(|t| t.first())
+                        .and_then(|t| t["url"].as_str())
                         .map(|s| s.to_string())
-                        .or_else(|| {
-                            v["thumbnails"]
-                                .as_array()
-                                .and_then(|t| t.first())
-                                .and_then(|t| t["url"].as_str())
-                                .map(|s| s.to_string())
+                }),
+            duration: v["duration"].as_f64(),
+            upload_date: v["upload_date"].as_str().map(|s| s.to_string()),
+            details: Some(VideoDetails::from_json(v)),
+        })
+    }
+}
+
+impl PlatformFetcher for PatreonFetcher {
+    fn platform(&self) -> &'static str {
+        "patreon"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Patreon"
+    }
+
+    fn auth(&self) -> AuthRequirement {
+        AuthRequirement::Required
+    }
+
+    fn list(&self, channel_url: &str, cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<Vec<ListedItem>, String> {
+        let cookie_path = cookie_path.ok_or("Patreon requires a cookie file")?;
+
+        // yt-dlp only sees video posts, so it's the fallback when the API can't be read
+        if let Ok(posts) = Self::fetch_campaign_posts(channel_url, cookie_path) {
+            return Ok(posts);
+        }
+        let posts = Self::fetch_creator(channel_url, cookie_path, ytdlp_path)?;
+
+        Ok(posts
+            .into_iter()
+            .map(|post| ListedItem {
+                published_at: post.upload_date.as_deref().and_then(parse_upload_date),
+                duration: post.duration.map(|d| d as i64),
+                description: None,
+                content_kind: Some("video".to_string()),
+                game: None,
+                media: Vec::new(),
+                access: None,
+                // If title is from URL slug fallback, mark as incomplete so metadata worker fetches real title
+                metadata_complete: !post.title_is_fallback,
+                external_id: post.id,
+                url: None,
+                title: post.title,
+                thumbnail_url: post.thumbnail,
+            })
+            .collect())
+    }
+
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        cookie_path: Option<&str>,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String> {
+        let cookie_path = cookie_path.ok_or("Patreon requires a cookie file")?;
+        let posts = Self::fetch_posts_metadata(items, cookie_path, ytdlp_path)?;
+
+        Ok(posts
+            .into_iter()
+            .map(|(id, post)| {
+                let metadata = match post {
+                    Ok(post) => Ok(ItemMetadata {
+                        published_at: post.upload_date.as_deref().and_then(parse_upload_date),
+                        duration: post.duration.map(|d| d as i64),
+                        thumbnail: post.thumbnail,
+                        // Include title if it's a real title (not fallback)
+                        title: if post.title_is_fallback { None } else { Some(post.title) },
+                        game: None,
+                        details: post.details,
+                        // yt-dlp only gets the post when it's viewable
+                        access: Some(PostAccess {
+                            locked: false,
+                            required_tier: None,
                         }),
-                    duration: v["duration"].as_f64(),
-                    upload_date: v["upload_date"].as_str().map(|s| s.to_string()),
-                })
+                    }),
+                    // yt-dlp can't see locked posts nor posts without a video; the API tells which
+                    Err(e) => Self::fetch_post(&id, Some(cookie_path))
+                        .filter(|listed| {
+                            let locked = listed.access.as_ref().is_some_and(|access| access.locked);
+                            locked || listed.content_kind.as_deref() != Some("video")
+                        })
+                        .map(|listed| ItemMetadata {
+                            published_at: listed.published_at,
+                            duration: None,
+                            thumbnail: listed.thumbnail_url,
+                            title: Some(listed.title),
+                            game: None,
+                            details: None,
+                            access: listed.access,
+                        })
+                        .ok_or(e),
+                };
+                (id, metadata)
             })
+            .collect())
+    }
+
+    fn canonical_url(&self, external_id: &str) -> Option<String> {
+        Some(format!("https://www.patreon.com/posts/{}", external_id))
+    }
+
+    /// Download URLs of post files are signed and expire, so they're fetched again
+    fn post_media(&self, external_id: &str, cookie_path: Option<&str>) -> Option<Vec<PostMedia>> {
+        Self::fetch_post(external_id, cookie_path).map(|post| post.media)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Vec<ListedItem> {
+        PatreonFetcher::posts_from_page(&serde_json::from_str(json).unwrap())
+    }
+
+    /// (kind, url, file name, mime type) of each file of a post
+    fn files(item: &ListedItem) -> Vec<(&str, &str, Option<&str>, Option<&str>)> {
+        item.media
+            .iter()
+            .map(|m| (m.kind.as_str(), m.url.as_str(), m.file_name.as_deref(), m.mime_type.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn lists_posts_with_files_or_video() {
+        let items = parse(include_str!("fixtures/patreon/posts.json"));
+
+        let ids: Vec<&str> = items.iter().map(|item| item.external_id.as_str()).collect();
+        // 1004 is a text post without files
+        assert_eq!(ids, ["1001", "1002", "1003", "1005"]);
+
+        let kinds: Vec<Option<&str>> = items.iter().map(|item| item.content_kind.as_deref()).collect();
+        assert_eq!(kinds, [Some("audio"), Some("image"), Some("text"), Some("video")]);
+
+        // Only videos wait for yt-dlp's details
+        let complete: Vec<bool> = items.iter().map(|item| item.metadata_complete).collect();
+        assert_eq!(complete, [true, true, true, false]);
+    }
+
+    #[test]
+    fn reads_post_attributes() {
+        let items = parse(include_str!("fixtures/patreon/posts.json"));
+
+        let audio = &items[0];
+        assert_eq!(audio.title, "Episode 12 (audio)");
+        assert_eq!(audio.published_at.as_deref(), Some("2026-03-02T00:00:00Z"));
+        assert_eq!(audio.description.as_deref(), Some("The full episode"));
+        assert_eq!(audio.thumbnail_url.as_deref(), Some("https://c10.patreonusercontent.com/large/1001.jpg"));
+
+        let images = &items[1];
+        assert_eq!(images.title, "Sketches");
+        assert_eq!(images.description, None);
+        assert_eq!(images.thumbnail_url.as_deref(), Some("https://c10.patreonusercontent.com/url/1002.jpg"));
+
+        assert_eq!(items[2].title, "Untitled Post");
+    }
+
+    #[test]
+    fn resolves_audio_image_and_attachment_files() {
+        let items = parse(include_str!("fixtures/patreon/posts.json"));
+
+        // To-one relationship
+        assert_eq!(
+            files(&items[0]),
+            [(
+                "audio",
+                "https://c10.patreonusercontent.com/audio/episode-12.mp3?token=a",
+                Some("episode-12.mp3"),
+                Some("audio/mpeg"),
+            )]
+        );
+
+        // To-many relationship; kinds come from the relationship without a mime type
+        assert_eq!(
+            files(&items[1]),
+            [
+                ("image", "https://c10.patreonusercontent.com/original/sketch-1.png", Some("sketch-1.png"), None),
+                (
+                    "image",
+                    "https://c10.patreonusercontent.com/default/sketch-2.png",
+                    Some("sketch-2.png"),
+                    Some("image/png"),
+                ),
+            ]
+        );
+
+        assert_eq!(
+            files(&items[2]),
+            [
+                ("file", "https://www.patreon.com/file?h=1003&m=1", Some("notes.pdf"), Some("application/pdf")),
+                ("file", "https://www.patreon.com/file?h=1003&i=2", Some("project.zip"), None),
+            ]
+        );
+
+        assert!(items[3].media.is_empty());
+    }
+
+    /// (locked, required tier) of each post
+    fn access(items: &[ListedItem]) -> Vec<(bool, Option<&str>)> {
+        items
+            .iter()
+            .map(|item| {
+                let access = item.access.as_ref().unwrap();
+                (access.locked, access.required_tier.as_deref())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_locks_and_the_cheapest_tier() {
+        let items = parse(include_str!("fixtures/patreon/access.json"));
+
+        assert_eq!(
+            access(&items),
+            [
+                // Gold and Silver unlock it; the rule without a tier is ignored
+                (true, Some("Silver")),
+                // No tier rules, so the pledge amount is named
+                (true, Some("$3.50 or more")),
+                (false, Some("Silver")),
+                // Posts without the flag are only returned when viewable
+                (false, None),
+            ]
+        );
+
+        // Locked posts are listed without files, and their details can't be fetched
+        assert!(items[1].media.is_empty());
+        assert!(items[0].metadata_complete);
+        assert!(!items[2].metadata_complete);
+    }
+
+    #[test]
+    fn reads_a_single_post() {
+        let items = parse(include_str!("fixtures/patreon/post.json"));
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].external_id, "1001");
+        assert_eq!(
+            files(&items[0]),
+            [(
+                "audio",
+                "https://c10.patreonusercontent.com/audio/episode-12.mp3?token=b",
+                Some("episode-12.mp3"),
+                Some("audio/mpeg"),
+            )]
+        );
     }
 }
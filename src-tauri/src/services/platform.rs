@@ -0,0 +1,246 @@
+use crate::models::{AppSettings, PostMedia};
+use crate::services::video_details::VideoDetails;
+use crate::services::ytdlp::{self, BatchEntry};
+use crate::services::{GenericFetcher, PatreonFetcher, RssFetcher, TwitchFetcher, YouTubeFetcher};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Whether a platform needs a cookie file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthRequirement {
+    None,
+    /// Cookies are passed along when configured, but not needed
+    Optional,
+    Required,
+}
+
+/// An item found when listing a source
+pub struct ListedItem {
+    pub external_id: String,
+    /// Item URL, for platforms where it can't be built from the external ID
+    pub url: Option<String>,
+    pub title: String,
+    pub thumbnail_url: Option<String>,
+    /// ISO 8601
+    pub published_at: Option<String>,
+    pub duration: Option<i64>,
+    /// Only set by listings that carry it, such as feeds
+    pub description: Option<String>,
+    /// Platform-specific kind of item, e.g. "short" on YouTube or "clip" on Twitch
+    pub content_kind: Option<String>,
+    /// Game or category the item was streamed under
+    pub game: Option<String>,
+    /// Files attached to a post, besides its video
+    pub media: Vec<PostMedia>,
+    /// Whether the account can see the item, where the platform tells
+    pub access: Option<PostAccess>,
+    /// False when the listing lacks details the metadata worker should fill in
+    pub metadata_complete: bool,
+}
+
+/// Access to a paywalled item for the account behind the source's cookies
+#[derive(Debug, Clone)]
+pub struct PostAccess {
+    /// The account's membership doesn't unlock the item
+    pub locked: bool,
+    /// Lowest tier (or pledge) that unlocks it, if it's restricted at all
+    pub required_tier: Option<String>,
+}
+
+/// Metadata fetched for a single feed item
+pub struct ItemMetadata {
+    /// ISO 8601
+    pub published_at: Option<String>,
+    pub duration: Option<i64>,
+    pub thumbnail: Option<String>,
+    /// Only set when it improves on the title stored at sync time
+    pub title: Option<String>,
+    pub game: Option<String>,
+    pub details: Option<VideoDetails>,
+    pub access: Option<PostAccess>,
+}
+
+/// Details of a source's channel, resolved when the source is validated
+pub struct ChannelInfo {
+    /// The platform's own ID for the channel, which outlives renames and handle changes
+    pub channel_id: Option<String>,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub subscriber_count: Option<i64>,
+}
+
+impl ChannelInfo {
+    /// Read channel details from yt-dlp's JSON for a channel or playlist
+    pub fn from_json(v: &serde_json::Value) -> Self {
+        let text = |key: &str| v[key].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+        let thumbnails = v["thumbnails"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let thumbnail = |kind: &str| {
+            // YouTube names the full-size images "avatar_uncropped" and "banner_uncropped"
+            let uncropped = format!("{}_uncropped", kind);
+            thumbnails
+                .iter()
+                .find(|t| t["id"].as_str() == Some(uncropped.as_str()))
+                .or_else(|| thumbnails.iter().find(|t| t["id"].as_str().is_some_and(|id| id.contains(kind))))
+                .and_then(|t| t["url"].as_str())
+                .map(|url| url.to_string())
+        };
+
+        Self {
+            channel_id: text("channel_id").or_else(|| text("uploader_id")).or_else(|| text("id")),
+            name: text("channel").or_else(|| text("uploader")).or_else(|| text("title")),
+            avatar_url: thumbnail("avatar"),
+            banner_url: thumbnail("banner"),
+            subscriber_count: v["channel_follower_count"].as_i64(),
+        }
+    }
+}
+
+/// Everything platform-specific about listing, fetching and downloading.
+/// Fetchers are looked up by `Source.platform` through `get_fetcher`.
+pub trait PlatformFetcher: Send + Sync {
+    /// The `Source.platform` value this fetcher handles
+    fn platform(&self) -> &'static str;
+
+    /// Human-readable platform name for messages
+    fn display_name(&self) -> &'static str;
+
+    fn auth(&self) -> AuthRequirement;
+
+    /// List all items of a source
+    fn list(&self, channel_url: &str, cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<Vec<ListedItem>, String>;
+
+    /// Newest items of a source from a cheap listing (e.g. a feed of the latest uploads),
+    /// used to skip the full listing when nothing changed. None when there's no such listing.
+    fn list_recent(&self, _channel_url: &str) -> Option<Vec<ListedItem>> {
+        None
+    }
+
+    /// Resolve the channel behind a source URL. Fails when the URL can't be listed.
+    fn channel_info(&self, channel_url: &str, cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<ChannelInfo, String> {
+        let mut args = vec![];
+        if let Some(cookies) = cookie_path {
+            args.extend(["--cookies", cookies]);
+        }
+        Ok(ChannelInfo::from_json(&ytdlp::playlist_info(ytdlp_path, channel_url, &args)?))
+    }
+
+    /// Fetch full metadata for items, keyed by external ID with the URL from `item_url`.
+    /// Returns a result per ID; the outer error means nothing could be fetched.
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        cookie_path: Option<&str>,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String>;
+
+    /// URL of a single item built from its external ID, if the platform allows it
+    fn canonical_url(&self, external_id: &str) -> Option<String>;
+
+    /// Extra yt-dlp arguments for downloading an item
+    fn download_args(&self, cookie_path: Option<&str>) -> Vec<String> {
+        match cookie_path {
+            Some(cookies) => vec!["--cookies".to_string(), cookies.to_string()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether items are plain media files fetched over HTTP instead of through yt-dlp
+    fn downloads_directly(&self) -> bool {
+        false
+    }
+
+    /// Current files of a post, for platforms whose file URLs expire after a while.
+    /// None keeps the files recorded at sync time.
+    fn post_media(&self, _external_id: &str, _cookie_path: Option<&str>) -> Option<Vec<PostMedia>> {
+        None
+    }
+
+    /// Whether SponsorBlock has segments for this platform's videos
+    fn supports_sponsorblock(&self) -> bool {
+        false
+    }
+
+    /// Days after publishing that an item of this kind is taken down, if ever
+    fn expires_after_days(&self, _content_kind: Option<&str>, _settings: &AppSettings) -> Option<i64> {
+        None
+    }
+}
+
+static FETCHERS: &[&dyn PlatformFetcher] = &[&YouTubeFetcher, &PatreonFetcher, &TwitchFetcher, &RssFetcher, &GenericFetcher];
+
+/// Look up the fetcher for a `Source.platform` value
+pub fn get_fetcher(platform: &str) -> Result<&'static dyn PlatformFetcher, String> {
+    FETCHERS
+        .iter()
+        .copied()
+        .find(|f| f.platform() == platform)
+        .ok_or_else(|| format!("Unknown platform: {}", platform))
+}
+
+/// URL of a feed item, as passed to yt-dlp and stored as original_url.
+/// The URL recorded at sync time wins over one built from the external ID.
+pub fn item_url(
+    fetcher: &dyn PlatformFetcher,
+    external_id: &str,
+    stored_url: Option<&str>,
+) -> Result<String, String> {
+    stored_url
+        .map(|url| url.to_string())
+        .or_else(|| fetcher.canonical_url(external_id))
+        .ok_or_else(|| format!("No URL recorded for {} item {}", fetcher.display_name(), external_id))
+}
+
+/// When an item expires, from its publish date and the platform's retention
+pub fn expires_at(
+    fetcher: &dyn PlatformFetcher,
+    content_kind: Option<&str>,
+    published_at: Option<&str>,
+    settings: &AppSettings,
+) -> Option<String> {
+    let days = fetcher.expires_after_days(content_kind, settings)?;
+    let published = chrono::DateTime::parse_from_rfc3339(published_at?).ok()?;
+    Some((published + chrono::Duration::days(days)).with_timezone(&chrono::Utc).to_rfc3339())
+}
+
+/// Check a resolved cookie file against the platform's auth requirement
+pub fn require_cookies(
+    fetcher: &dyn PlatformFetcher,
+    cookie_path: Option<String>,
+) -> Result<Option<String>, String> {
+    match (fetcher.auth(), cookie_path) {
+        (AuthRequirement::None, _) => Ok(None),
+        (AuthRequirement::Required, None) => Err(format!(
+            "No credential configured for {}. Please add a cookie file in Settings.",
+            fetcher.display_name()
+        )),
+        (_, cookie_path) => Ok(cookie_path),
+    }
+}
+
+/// Convert a yt-dlp upload_date (YYYYMMDD) to ISO 8601
+pub fn parse_upload_date(upload_date: &str) -> Option<String> {
+    if upload_date.len() != 8 {
+        return None;
+    }
+
+    let year = &upload_date[0..4];
+    let month = &upload_date[4..6];
+    let day = &upload_date[6..8];
+
+    Some(format!("{}-{}-{}T00:00:00Z", year, month, day))
+}
+
+/// Publish date of a yt-dlp entry from upload_date or a Unix timestamp, as ISO 8601
+pub fn published_at_from_json(v: &serde_json::Value) -> Option<String> {
+    v["upload_date"]
+        .as_str()
+        .and_then(parse_upload_date)
+        .or_else(|| {
+            v["timestamp"]
+                .as_i64()
+                .or_else(|| v["release_timestamp"].as_i64())
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .and_then(|dt| parse_upload_date(&dt.format("%Y%m%d").to_string()))
+        })
+}
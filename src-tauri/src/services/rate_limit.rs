@@ -0,0 +1,65 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Requests per minute for platforms missing from the setting
+pub const DEFAULT_REQUESTS_PER_MINUTE: usize = 30;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Parse a "platform=count" list such as "youtube=120,patreon=60"
+pub fn parse_rate_limits(limits: &str) -> Result<HashMap<String, usize>, String> {
+    let mut parsed = HashMap::new();
+
+    for part in limits.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (platform, count) = part
+            .split_once('=')
+            .ok_or_else(|| format!("Expected platform=count, got: {}", part))?;
+        let count: usize = count
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid request count for {}: {}", platform.trim(), count.trim()))?;
+        if count == 0 {
+            return Err(format!("Request count for {} must be at least 1", platform.trim()));
+        }
+        parsed.insert(platform.trim().to_lowercase(), count);
+    }
+
+    Ok(parsed)
+}
+
+/// Validate a rate limit list and return it in canonical form
+pub fn normalize_rate_limits(limits: &str) -> Result<String, String> {
+    let mut parsed: Vec<(String, usize)> = parse_rate_limits(limits)?.into_iter().collect();
+    parsed.sort();
+
+    Ok(parsed
+        .iter()
+        .map(|(platform, count)| format!("{}={}", platform, count))
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Sliding one-minute window of requests made per platform
+#[derive(Default)]
+pub struct RateBudget {
+    requests: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RateBudget {
+    /// How many requests the platform may make right now
+    pub fn available(&mut self, platform: &str, per_minute: usize) -> usize {
+        let window = self.requests.entry(platform.to_string()).or_default();
+        while window.front().is_some_and(|t| t.elapsed() >= WINDOW) {
+            window.pop_front();
+        }
+        per_minute.saturating_sub(window.len())
+    }
+
+    pub fn record(&mut self, platform: &str, count: usize) {
+        let window = self.requests.entry(platform.to_string()).or_default();
+        let now = Instant::now();
+        for _ in 0..count {
+            window.push_back(now);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// Items published less than `max_age_days` ago are refreshed every `interval_days`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RefreshRule {
+    pub max_age_days: i64,
+    pub interval_days: i64,
+}
+
+/// Parse a "max_age_days:interval_days" list such as "7:1,30:7", youngest rule first
+pub fn parse_refresh_policy(policy: &str) -> Result<Vec<RefreshRule>, String> {
+    let mut rules = Vec::new();
+
+    for part in policy.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (max_age, interval) = part
+            .split_once(':')
+            .ok_or_else(|| format!("Expected max_age_days:interval_days, got: {}", part))?;
+        let parse_days = |value: &str| {
+            value
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|days| *days > 0)
+                .ok_or_else(|| format!("Invalid number of days in refresh rule: {}", part))
+        };
+
+        rules.push(RefreshRule {
+            max_age_days: parse_days(max_age)?,
+            interval_days: parse_days(interval)?,
+        });
+    }
+
+    rules.sort();
+    rules.dedup_by_key(|rule| rule.max_age_days);
+    Ok(rules)
+}
+
+/// Validate a refresh policy and return it in canonical form
+pub fn normalize_refresh_policy(policy: &str) -> Result<String, String> {
+    Ok(parse_refresh_policy(policy)?
+        .iter()
+        .map(|rule| format!("{}:{}", rule.max_age_days, rule.interval_days))
+        .collect::<Vec<_>>()
+        .join(","))
+}
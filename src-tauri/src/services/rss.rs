@@ -0,0 +1,466 @@
+use crate::services::http;
+use crate::services::platform::{AuthRequirement, ChannelInfo, ItemMetadata, ListedItem, PlatformFetcher};
+use crate::services::ytdlp::BatchEntry;
+use roxmltree::Node;
+use std::collections::HashMap;
+use std::path::Path;
+
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+const MEDIA_NS: &str = "http://search.yahoo.com/mrss/";
+const ITUNES_NS: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+const CONTENT_NS: &str = "http://purl.org/rss/1.0/modules/content/";
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const YT_NS: &str = "http://www.youtube.com/xml/schemas/2015";
+
+/// A media file attached to a feed entry
+#[derive(Debug, Clone)]
+pub struct Enclosure {
+    pub url: String,
+    pub mime_type: Option<String>,
+}
+
+/// An entry of an RSS or Atom feed
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    /// guid (RSS) or id (Atom), falling back to the link
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    /// ISO 8601
+    pub published_at: Option<String>,
+    pub description: Option<String>,
+    pub thumbnail: Option<String>,
+    pub duration: Option<i64>,
+    pub enclosure: Option<Enclosure>,
+    /// yt:videoId in YouTube channel and playlist feeds
+    pub youtube_video_id: Option<String>,
+}
+
+/// Title and artwork of a feed itself
+pub struct FeedChannel {
+    pub title: Option<String>,
+    pub image: Option<String>,
+}
+
+/// Parse an RSS 2.0, RSS 1.0 or Atom document into its entries
+pub fn parse_feed(xml: &str) -> Result<Vec<FeedEntry>, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| format!("Invalid feed XML: {}", e))?;
+    let root = doc.root_element();
+
+    let entries = match root.tag_name().name() {
+        "rss" => root
+            .children()
+            .filter(|n| n.has_tag_name("channel"))
+            .flat_map(|channel| channel.children().filter(|n| n.has_tag_name("item")))
+            .filter_map(rss_entry)
+            .collect(),
+        // RSS 1.0 puts items next to the channel
+        "RDF" => root
+            .children()
+            .filter(|n| n.tag_name().name() == "item")
+            .filter_map(rss_entry)
+            .collect(),
+        "feed" => root
+            .children()
+            .filter(|n| n.has_tag_name((ATOM_NS, "entry")))
+            .filter_map(atom_entry)
+            .collect(),
+        other => return Err(format!("Not an RSS or Atom feed (root element <{}>)", other)),
+    };
+
+    Ok(entries)
+}
+
+/// Read the title and artwork of an RSS 2.0, RSS 1.0 or Atom document
+pub fn parse_feed_channel(xml: &str) -> Result<FeedChannel, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| format!("Invalid feed XML: {}", e))?;
+    let root = doc.root_element();
+
+    match root.tag_name().name() {
+        "rss" | "RDF" => {
+            let channel = root
+                .children()
+                .find(|n| n.is_element() && n.tag_name().name() == "channel")
+                .ok_or("Feed has no <channel> element")?;
+            let ns = channel.tag_name().namespace();
+            Ok(FeedChannel {
+                title: child_text(channel, ns, "title"),
+                image: thumbnail(channel)
+                    .or_else(|| child(channel, ns, "image").and_then(|i| child_text(i, ns, "url"))),
+            })
+        }
+        "feed" => Ok(FeedChannel {
+            title: child_text(root, Some(ATOM_NS), "title"),
+            image: child_text(root, Some(ATOM_NS), "logo").or_else(|| child_text(root, Some(ATOM_NS), "icon")),
+        }),
+        other => Err(format!("Not an RSS or Atom feed (root element <{}>)", other)),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, ns: Option<&str>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name && n.tag_name().namespace() == ns)
+}
+
+fn child_text(node: Node, ns: Option<&str>, name: &str) -> Option<String> {
+    child(node, ns, name)
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+fn rss_entry(item: Node) -> Option<FeedEntry> {
+    // RSS 2.0 elements have no namespace, RSS 1.0 elements share the item's
+    let ns = item.tag_name().namespace();
+
+    let link = child_text(item, ns, "link");
+    let enclosure = child(item, ns, "enclosure")
+        .and_then(|e| {
+            Some(Enclosure {
+                url: e.attribute("url")?.to_string(),
+                mime_type: e.attribute("type").map(|t| t.to_string()),
+            })
+        })
+        .or_else(|| media_content(item));
+
+    let id = child_text(item, ns, "guid")
+        .or_else(|| item.attribute((RDF_NS, "about")).map(|a| a.to_string()))
+        .or_else(|| link.clone())
+        .or_else(|| enclosure.as_ref().map(|e| e.url.clone()))?;
+
+    let published_at = child_text(item, ns, "pubDate")
+        .and_then(|d| chrono::DateTime::parse_from_rfc2822(&d).ok())
+        .map(|d| iso8601(&d))
+        .or_else(|| child_text(item, Some(DC_NS), "date").and_then(|d| parse_rfc3339(&d)));
+
+    Some(FeedEntry {
+        title: child_text(item, ns, "title").unwrap_or_else(|| id.clone()),
+        id,
+        link,
+        published_at,
+        description: child_text(item, Some(CONTENT_NS), "encoded")
+            .or_else(|| child_text(item, ns, "description"))
+            .or_else(|| child_text(item, Some(ITUNES_NS), "summary")),
+        thumbnail: thumbnail(item),
+        duration: child_text(item, Some(ITUNES_NS), "duration").and_then(|d| parse_duration(&d)),
+        enclosure,
+        youtube_video_id: None,
+    })
+}
+
+fn atom_entry(entry: Node) -> Option<FeedEntry> {
+    let ns = Some(ATOM_NS);
+    let links: Vec<Node> = entry.children().filter(|n| n.has_tag_name((ATOM_NS, "link"))).collect();
+
+    let link = links
+        .iter()
+        .find(|l| matches!(l.attribute("rel"), None | Some("alternate")))
+        .and_then(|l| l.attribute("href"))
+        .map(|href| href.to_string());
+    let enclosure = links
+        .iter()
+        .find(|l| l.attribute("rel") == Some("enclosure"))
+        .and_then(|l| {
+            Some(Enclosure {
+                url: l.attribute("href")?.to_string(),
+                mime_type: l.attribute("type").map(|t| t.to_string()),
+            })
+        })
+        .or_else(|| media_content(entry));
+
+    let id = child_text(entry, ns, "id").or_else(|| link.clone())?;
+    let group = child(entry, Some(MEDIA_NS), "group");
+
+    Some(FeedEntry {
+        title: child_text(entry, ns, "title").unwrap_or_else(|| id.clone()),
+        id,
+        link,
+        published_at: child_text(entry, ns, "published")
+            .or_else(|| child_text(entry, ns, "updated"))
+            .and_then(|d| parse_rfc3339(&d)),
+        description: child_text(entry, ns, "summary")
+            .or_else(|| child_text(entry, ns, "content"))
+            .or_else(|| group.and_then(|g| child_text(g, Some(MEDIA_NS), "description"))),
+        thumbnail: thumbnail(entry).or_else(|| group.and_then(thumbnail)),
+        duration: None,
+        enclosure,
+        youtube_video_id: child_text(entry, Some(YT_NS), "videoId"),
+    })
+}
+
+/// media:content of an audio or video type, for feeds without a regular enclosure
+fn media_content(node: Node) -> Option<Enclosure> {
+    let contents = node
+        .children()
+        .chain(child(node, Some(MEDIA_NS), "group").into_iter().flat_map(|g| g.children()))
+        .filter(|n| n.has_tag_name((MEDIA_NS, "content")));
+
+    for content in contents {
+        let mime_type = content.attribute("type");
+        let medium = content.attribute("medium");
+        let is_media = matches!(medium, Some("audio") | Some("video"))
+            || mime_type.is_some_and(|t| t.starts_with("audio/") || t.starts_with("video/"));
+        if let (true, Some(url)) = (is_media, content.attribute("url")) {
+            return Some(Enclosure {
+                url: url.to_string(),
+                mime_type: mime_type.map(|t| t.to_string()),
+            });
+        }
+    }
+    None
+}
+
+fn thumbnail(node: Node) -> Option<String> {
+    child(node, Some(MEDIA_NS), "thumbnail")
+        .and_then(|t| t.attribute("url"))
+        .or_else(|| child(node, Some(ITUNES_NS), "image").and_then(|i| i.attribute("href")))
+        .map(|url| url.to_string())
+}
+
+fn iso8601<Tz: chrono::TimeZone>(date: &chrono::DateTime<Tz>) -> String {
+    date.with_timezone(&chrono::Utc)
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn parse_rfc3339(date: &str) -> Option<String> {
+    chrono::DateTime::parse_from_rfc3339(date).ok().map(|d| iso8601(&d))
+}
+
+/// itunes:duration is seconds, MM:SS or HH:MM:SS
+fn parse_duration(duration: &str) -> Option<i64> {
+    duration
+        .split(':')
+        .try_fold(0i64, |total, part| part.trim().parse::<f64>().ok().map(|v| total * 60 + v as i64))
+}
+
+/// Kind of media an enclosure holds, from its MIME type or file extension
+fn enclosure_kind(enclosure: &Enclosure) -> &'static str {
+    let mime_type = enclosure.mime_type.as_deref().unwrap_or_default();
+    let path = enclosure.url.split('?').next().unwrap_or_default().to_lowercase();
+    let audio_extension = [".mp3", ".m4a", ".aac", ".ogg", ".opus", ".flac", ".wav"]
+        .iter()
+        .any(|ext| path.ends_with(ext));
+
+    if mime_type.starts_with("audio/") || (mime_type.is_empty() && audio_extension) {
+        "audio"
+    } else {
+        "video"
+    }
+}
+
+/// Podcasts and other RSS/Atom feeds with media enclosures.
+/// Enclosures are plain files, so they download over HTTP instead of through yt-dlp.
+pub struct RssFetcher;
+
+impl RssFetcher {
+    /// Fetch and parse a feed
+    pub fn fetch_feed(feed_url: &str) -> Result<Vec<FeedEntry>, String> {
+        parse_feed(&http::get_text(feed_url, None)?)
+    }
+}
+
+impl PlatformFetcher for RssFetcher {
+    fn platform(&self) -> &'static str {
+        "rss"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RSS"
+    }
+
+    fn auth(&self) -> AuthRequirement {
+        AuthRequirement::None
+    }
+
+    fn list(&self, channel_url: &str, _cookie_path: Option<&str>, _ytdlp_path: &Path) -> Result<Vec<ListedItem>, String> {
+        // Entries without an enclosure (e.g. text-only blog posts) have nothing to download
+        Ok(Self::fetch_feed(channel_url)?
+            .into_iter()
+            .filter_map(|entry| {
+                let enclosure = entry.enclosure?;
+                Some(ListedItem {
+                    content_kind: Some(enclosure_kind(&enclosure).to_string()),
+                    external_id: entry.id,
+                    url: Some(enclosure.url),
+                    title: entry.title,
+                    thumbnail_url: entry.thumbnail,
+                    published_at: entry.published_at,
+                    duration: entry.duration,
+                    description: entry.description,
+                    game: None,
+                    media: Vec::new(),
+                    access: None,
+                    // The feed is all there is to know about an entry
+                    metadata_complete: true,
+                })
+            })
+            .collect())
+    }
+
+    /// The feed's own title and artwork; feeds have no ID or subscriber count
+    fn channel_info(&self, channel_url: &str, _cookie_path: Option<&str>, _ytdlp_path: &Path) -> Result<ChannelInfo, String> {
+        let channel = parse_feed_channel(&http::get_text(channel_url, None)?)?;
+        Ok(ChannelInfo {
+            channel_id: None,
+            name: channel.title,
+            avatar_url: channel.image,
+            banner_url: None,
+            subscriber_count: None,
+        })
+    }
+
+    /// Entries carry their metadata in the feed, which the next sync reads again
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        _cookie_path: Option<&str>,
+        _ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String> {
+        Ok(items
+            .iter()
+            .map(|item| {
+                let metadata = ItemMetadata {
+                    published_at: None,
+                    duration: None,
+                    thumbnail: None,
+                    title: None,
+                    game: None,
+                    details: None,
+                    access: None,
+                };
+                (item.id.clone(), Ok(metadata))
+            })
+            .collect())
+    }
+
+    fn canonical_url(&self, _external_id: &str) -> Option<String> {
+        None
+    }
+
+    fn downloads_directly(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rss2() {
+        let xml = include_str!("fixtures/feeds/rss2.xml");
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        let first = &entries[0];
+        assert_eq!(first.id, "episode-2");
+        assert_eq!(first.title, "Episode 2");
+        assert_eq!(first.link.as_deref(), Some("https://example.com/episodes/2"));
+        assert_eq!(first.published_at.as_deref(), Some("2026-02-03T09:30:00Z"));
+        assert_eq!(first.description.as_deref(), Some("<p>Full show notes</p>"));
+        let enclosure = first.enclosure.as_ref().unwrap();
+        assert_eq!(enclosure.url, "https://example.com/media/2.mp4");
+        assert_eq!(enclosure_kind(enclosure), "video");
+
+        // Without a guid the link identifies the entry
+        assert_eq!(entries[1].id, "https://example.com/episodes/1");
+        assert_eq!(entries[1].description.as_deref(), Some("First episode"));
+        assert!(entries[1].enclosure.is_none());
+
+        let channel = parse_feed_channel(xml).unwrap();
+        assert_eq!(channel.title.as_deref(), Some("Example Show"));
+        assert_eq!(channel.image.as_deref(), Some("https://example.com/logo.png"));
+    }
+
+    #[test]
+    fn parses_rdf() {
+        let xml = include_str!("fixtures/feeds/rdf.xml");
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "https://example.org/posts/1");
+        assert_eq!(entries[0].title, "First post");
+        assert_eq!(entries[0].published_at.as_deref(), Some("2026-01-15T10:00:00Z"));
+        assert_eq!(entries[0].description.as_deref(), Some("Hello"));
+
+        let channel = parse_feed_channel(xml).unwrap();
+        assert_eq!(channel.title.as_deref(), Some("Example Journal"));
+    }
+
+    #[test]
+    fn parses_atom() {
+        let xml = include_str!("fixtures/feeds/atom.xml");
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        let video = &entries[0];
+        assert_eq!(video.id, "yt:video:abc123DEF45");
+        assert_eq!(video.youtube_video_id.as_deref(), Some("abc123DEF45"));
+        assert_eq!(video.link.as_deref(), Some("https://www.youtube.com/watch?v=abc123DEF45"));
+        // published wins over updated
+        assert_eq!(video.published_at.as_deref(), Some("2026-02-01T18:00:00Z"));
+        assert_eq!(video.description.as_deref(), Some("Video description"));
+        assert_eq!(
+            video.thumbnail.as_deref(),
+            Some("https://i.ytimg.com/vi/abc123DEF45/hqdefault.jpg")
+        );
+
+        assert_eq!(entries[1].published_at.as_deref(), Some("2026-02-03T09:00:00Z"));
+        assert!(entries[1].link.as_deref().unwrap().contains("/shorts/"));
+
+        let channel = parse_feed_channel(xml).unwrap();
+        assert_eq!(channel.title.as_deref(), Some("Example Channel"));
+        assert_eq!(channel.image.as_deref(), Some("https://example.com/channel.png"));
+    }
+
+    #[test]
+    fn parses_media_content() {
+        let xml = include_str!("fixtures/feeds/media_content.xml");
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        // Image content is skipped in favour of the video
+        let clip = entries[0].enclosure.as_ref().unwrap();
+        assert_eq!(clip.url, "https://example.net/clip-1.webm");
+        assert_eq!(enclosure_kind(clip), "video");
+        assert_eq!(entries[0].thumbnail.as_deref(), Some("https://example.net/clip-1-thumb.jpg"));
+
+        // Without guid or link the media URL identifies the entry
+        let sound = &entries[1];
+        assert_eq!(sound.id, "https://example.net/sound.ogg?dl=1");
+        assert_eq!(enclosure_kind(sound.enclosure.as_ref().unwrap()), "audio");
+
+        let channel = parse_feed_channel(xml).unwrap();
+        assert_eq!(channel.image.as_deref(), Some("https://example.net/channel.jpg"));
+    }
+
+    #[test]
+    fn parses_itunes() {
+        let xml = include_str!("fixtures/feeds/itunes.xml");
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].duration, Some(3723));
+        assert_eq!(entries[0].description.as_deref(), Some("Episode summary"));
+        assert_eq!(entries[0].thumbnail.as_deref(), Some("https://example.fm/ep-10.jpg"));
+        assert_eq!(enclosure_kind(entries[0].enclosure.as_ref().unwrap()), "audio");
+
+        assert_eq!(entries[1].duration, Some(330));
+        // No MIME type: the extension decides
+        assert_eq!(enclosure_kind(entries[1].enclosure.as_ref().unwrap()), "audio");
+        assert_eq!(entries[2].duration, Some(95));
+        assert_eq!(enclosure_kind(entries[2].enclosure.as_ref().unwrap()), "video");
+
+        let channel = parse_feed_channel(xml).unwrap();
+        assert_eq!(channel.title.as_deref(), Some("Example Podcast"));
+        assert_eq!(channel.image.as_deref(), Some("https://example.fm/cover.jpg"));
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        let error = parse_feed("<html><body/></html>").unwrap_err();
+        assert!(error.contains("<html>"), "{}", error);
+        assert!(parse_feed("not xml").is_err());
+    }
+}
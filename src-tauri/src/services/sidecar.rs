@@ -88,6 +88,13 @@ pub fn get_ffmpeg_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
     get_sidecar_path(app_handle, "ffmpeg")
 }
 
+/// Get the path to the ffprobe sidecar binary.
+/// In development, falls back to system ffprobe if sidecar not found.
+/// In production, uses the bundled sidecar.
+pub fn get_ffprobe_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    get_sidecar_path(app_handle, "ffprobe")
+}
+
 /// Check if ffmpeg is available (bundled or system)
 pub fn is_ffmpeg_available(app_handle: &AppHandle) -> bool {
     get_ffmpeg_path(app_handle).is_ok()
@@ -0,0 +1,302 @@
+use crate::models::AppSettings;
+use std::path::Path;
+
+/// Segment categories understood by the SponsorBlock API
+pub const CATEGORIES: &[&str] = &[
+    "sponsor",
+    "intro",
+    "outro",
+    "selfpromo",
+    "preview",
+    "filler",
+    "interaction",
+    "music_offtopic",
+    "poi_highlight",
+    "chapter",
+];
+
+/// Categories yt-dlp can cut from a file. Highlights are a single point and
+/// chapters have no segment to cut, so `--sponsorblock-remove` rejects them.
+pub const REMOVABLE_CATEGORIES: &[&str] = &[
+    "sponsor",
+    "intro",
+    "outro",
+    "selfpromo",
+    "preview",
+    "filler",
+    "interaction",
+    "music_offtopic",
+];
+
+/// A segment reported by SponsorBlock for a downloaded video.
+/// Times are in seconds on the timeline of the file as stored on disk.
+#[derive(Debug, Clone)]
+pub struct SponsorBlockSegment {
+    pub category: String,
+    pub action_type: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// SponsorBlock configuration for a single download
+pub struct SponsorBlockOptions {
+    pub mark_categories: Vec<String>,
+    pub remove_categories: Vec<String>,
+    pub api_url: String,
+}
+
+impl SponsorBlockOptions {
+    /// Build options from app settings.
+    /// Returns None if SponsorBlock is disabled or no categories are selected.
+    pub fn from_settings(settings: &AppSettings) -> Option<Self> {
+        if !settings.sponsorblock_enabled {
+            return None;
+        }
+
+        let mark_categories = split_categories(&settings.sponsorblock_mark_categories);
+        // Settings saved before the remove list was validated may hold categories
+        // that would make yt-dlp refuse every download
+        let remove_categories: Vec<String> = split_categories(&settings.sponsorblock_remove_categories)
+            .into_iter()
+            .filter(|c| REMOVABLE_CATEGORIES.contains(&c.as_str()))
+            .collect();
+        if mark_categories.is_empty() && remove_categories.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            mark_categories,
+            remove_categories,
+            api_url: settings.sponsorblock_api_url.clone(),
+        })
+    }
+
+    /// yt-dlp arguments that fetch segments, apply them with ffmpeg and write
+    /// the raw segment list to `segments_file` once the file is in place
+    pub fn ytdlp_args(&self, segments_file: &str) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if !self.mark_categories.is_empty() {
+            args.push("--sponsorblock-mark".to_string());
+            args.push(self.mark_categories.join(","));
+        }
+        if !self.remove_categories.is_empty() {
+            args.push("--sponsorblock-remove".to_string());
+            args.push(self.remove_categories.join(","));
+        }
+        if !self.api_url.is_empty() {
+            args.push("--sponsorblock-api".to_string());
+            args.push(self.api_url.clone());
+        }
+
+        // --print-to-file doesn't imply --quiet/--simulate like --print does,
+        // so progress output keeps working
+        args.push("--print-to-file".to_string());
+        args.push("after_move:%(sponsorblock_chapters)j".to_string());
+        args.push(segments_file.to_string());
+
+        args
+    }
+
+    /// Read the segment list written by yt-dlp and return the segments that are
+    /// still present in the file, shifted to account for any removed segments
+    pub fn read_kept_segments(&self, segments_file: &Path) -> Vec<SponsorBlockSegment> {
+        let content = match std::fs::read_to_string(segments_file) {
+            Ok(content) => content,
+            Err(_) => return Vec::new(),
+        };
+
+        // yt-dlp prints "NA" when the video has no segments
+        let value = match serde_json::from_str::<serde_json::Value>(content.trim()) {
+            Ok(value) => value,
+            Err(_) => return Vec::new(),
+        };
+
+        let segments: Vec<SponsorBlockSegment> = value
+            .as_array()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|v| {
+                        Some(SponsorBlockSegment {
+                            category: v["category"].as_str()?.to_string(),
+                            action_type: v["type"].as_str().unwrap_or("skip").to_string(),
+                            start_time: v["start_time"].as_f64()?,
+                            end_time: v["end_time"].as_f64()?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (removed, kept): (Vec<_>, Vec<_>) = segments
+            .into_iter()
+            .partition(|s| self.remove_categories.contains(&s.category));
+
+        // Merge overlapping removed ranges so shared time is only subtracted once
+        let mut removed: Vec<(f64, f64)> = removed.iter().map(|r| (r.start_time, r.end_time)).collect();
+        removed.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let removed = removed.into_iter().fold(Vec::<(f64, f64)>::new(), |mut merged, (start, end)| {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+            merged
+        });
+
+        // A time in the original video maps to itself minus the removed time before it,
+        // which also clips a kept segment that overlaps a removed range
+        let map_time = |time: f64| -> f64 {
+            let removed_before: f64 = removed
+                .iter()
+                .map(|(start, end)| (end.min(time) - start).max(0.0))
+                .sum();
+            time - removed_before
+        };
+
+        kept.into_iter()
+            .filter_map(|mut s| {
+                s.start_time = map_time(s.start_time);
+                s.end_time = map_time(s.end_time);
+                // Nothing is left of segments inside a removed range
+                (s.end_time > s.start_time).then_some(s)
+            })
+            .collect()
+    }
+}
+
+fn split_categories(categories: &str) -> Vec<String> {
+    categories
+        .split(',')
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Validate a comma-separated category list against `allowed` and return it in
+/// canonical form
+pub fn normalize_categories(categories: &str, allowed: &[&str]) -> Result<String, String> {
+    let categories = split_categories(categories);
+
+    if let Some(invalid) = categories.iter().find(|c| !allowed.contains(&c.as_str())) {
+        return Err(if CATEGORIES.contains(&invalid.as_str()) {
+            format!("SponsorBlock category {} can't be removed, only marked", invalid)
+        } else {
+            format!("Unknown SponsorBlock category: {}", invalid)
+        });
+    }
+
+    Ok(categories.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(remove_categories: &[&str]) -> SponsorBlockOptions {
+        SponsorBlockOptions {
+            mark_categories: vec!["selfpromo".to_string()],
+            remove_categories: remove_categories.iter().map(|c| c.to_string()).collect(),
+            api_url: String::new(),
+        }
+    }
+
+    /// Stand-in for the `sponsorblock_chapters` list yt-dlp writes after the move
+    fn read_segments(options: &SponsorBlockOptions, name: &str, json: &str) -> Vec<(String, f64, f64)> {
+        let path = std::env::temp_dir().join(format!("sponsorblock-{}-{}.json", std::process::id(), name));
+        std::fs::write(&path, json).unwrap();
+        let segments = options.read_kept_segments(&path);
+        let _ = std::fs::remove_file(&path);
+        segments.into_iter().map(|s| (s.category, s.start_time, s.end_time)).collect()
+    }
+
+    #[test]
+    fn normalizes_categories() {
+        assert_eq!(
+            normalize_categories(" Sponsor, selfpromo ,,intro", CATEGORIES).unwrap(),
+            "sponsor,selfpromo,intro"
+        );
+        assert_eq!(normalize_categories("", CATEGORIES).unwrap(), "");
+        assert!(normalize_categories("sponsor,ads", CATEGORIES).unwrap_err().contains("ads"));
+    }
+
+    #[test]
+    fn only_cuttable_categories_can_be_removed() {
+        assert_eq!(normalize_categories("poi_highlight,chapter", CATEGORIES).unwrap(), "poi_highlight,chapter");
+        for category in ["poi_highlight", "chapter"] {
+            let error = normalize_categories(&format!("sponsor,{}", category), REMOVABLE_CATEGORIES).unwrap_err();
+            assert!(error.contains("can't be removed"), "{}", error);
+        }
+        assert!(REMOVABLE_CATEGORIES.iter().all(|c| CATEGORIES.contains(c)));
+    }
+
+    #[test]
+    fn shifts_kept_segments_past_removed_ones() {
+        let json = r#"[
+            {"category": "sponsor", "type": "skip", "start_time": 10.0, "end_time": 20.0},
+            {"category": "selfpromo", "type": "skip", "start_time": 30.0, "end_time": 40.0},
+            {"category": "intro", "type": "skip", "start_time": 50.0, "end_time": 55.0},
+            {"category": "sponsor", "type": "skip", "start_time": 52.0, "end_time": 60.0},
+            {"category": "selfpromo", "type": "skip", "start_time": 70.0, "end_time": 80.0}
+        ]"#;
+
+        let segments = read_segments(&options(&["sponsor", "intro"]), "shift", json);
+
+        // 10s removed before 30s; 10s + the merged 50-60 range before 70s
+        assert_eq!(
+            segments,
+            vec![
+                ("selfpromo".to_string(), 20.0, 30.0),
+                ("selfpromo".to_string(), 50.0, 60.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_kept_segments_inside_removed_ones() {
+        let json = r#"[
+            {"category": "sponsor", "start_time": 10.0, "end_time": 40.0},
+            {"category": "selfpromo", "start_time": 15.0, "end_time": 20.0}
+        ]"#;
+
+        assert!(read_segments(&options(&["sponsor"]), "inside", json).is_empty());
+        assert_eq!(
+            read_segments(&options(&[]), "none_removed", json),
+            vec![
+                ("sponsor".to_string(), 10.0, 40.0),
+                ("selfpromo".to_string(), 15.0, 20.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn clips_kept_segments_that_overlap_removed_ones() {
+        let json = r#"[
+            {"category": "selfpromo", "start_time": 10.0, "end_time": 30.0},
+            {"category": "sponsor", "start_time": 20.0, "end_time": 25.0}
+        ]"#;
+        // The removed middle shortens the kept segment
+        assert_eq!(
+            read_segments(&options(&["sponsor"]), "overlap_end", json),
+            vec![("selfpromo".to_string(), 10.0, 25.0)]
+        );
+
+        let json = r#"[
+            {"category": "selfpromo", "start_time": 10.0, "end_time": 30.0},
+            {"category": "sponsor", "start_time": 5.0, "end_time": 15.0}
+        ]"#;
+        // The removed start moves the kept segment to where the cut ends
+        assert_eq!(
+            read_segments(&options(&["sponsor"]), "overlap_start", json),
+            vec![("selfpromo".to_string(), 5.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn reads_no_segments_from_na_or_missing_file() {
+        assert!(read_segments(&options(&[]), "na", "NA\n").is_empty());
+        assert!(options(&[])
+            .read_kept_segments(Path::new("/nonexistent/sponsorblock.json"))
+            .is_empty());
+    }
+}
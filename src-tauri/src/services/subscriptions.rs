@@ -0,0 +1,359 @@
+/// `Source.platform` values an OPML `platform` attribute may name
+const PLATFORMS: &[&str] = &["youtube", "patreon", "twitch", "rss", "generic"];
+
+/// A channel found in an imported subscription list
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    /// Channel or feed title, when the list has one
+    pub name: Option<String>,
+    /// `Source.platform` value for the URL
+    pub platform: &'static str,
+    pub channel_url: String,
+}
+
+/// Kinds of subscription list `import_subscriptions` reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionFormat {
+    Opml,
+    /// `subscriptions.csv` from a Google Takeout of YouTube
+    TakeoutCsv,
+    /// One URL per line
+    UrlList,
+}
+
+impl SubscriptionFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Opml => "opml",
+            Self::TakeoutCsv => "takeout_csv",
+            Self::UrlList => "url_list",
+        }
+    }
+
+    /// Tell the format from the file's contents, falling back to its extension
+    pub fn detect(file_name: &str, content: &str) -> Self {
+        let start = content.trim_start_matches('\u{feff}').trim_start();
+        let first_line = start.lines().next().unwrap_or_default().to_lowercase();
+
+        if start.starts_with('<') {
+            Self::Opml
+        } else if first_line.starts_with("channel id,") {
+            Self::TakeoutCsv
+        } else if file_name.to_lowercase().ends_with(".opml") {
+            Self::Opml
+        } else {
+            Self::UrlList
+        }
+    }
+}
+
+/// Read the channels of a subscription list
+pub fn parse_subscriptions(content: &str, format: SubscriptionFormat) -> Result<Vec<Subscription>, String> {
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        SubscriptionFormat::Opml => parse_opml(content),
+        SubscriptionFormat::TakeoutCsv => parse_takeout_csv(content),
+        SubscriptionFormat::UrlList => Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|url| subscription_for_url(url, None))
+            .collect()),
+    }
+}
+
+/// Feeds in an OPML outline, at any depth. YouTube channel and playlist feeds
+/// become YouTube sources; everything else is imported as an RSS feed.
+fn parse_opml(content: &str) -> Result<Vec<Subscription>, String> {
+    let doc = roxmltree::Document::parse(content).map_err(|e| format!("Invalid OPML: {}", e))?;
+    if !doc.root_element().has_tag_name("opml") {
+        return Err("Not an OPML file".to_string());
+    }
+
+    Ok(doc
+        .descendants()
+        .filter(|n| n.has_tag_name("outline"))
+        .filter_map(|outline| {
+            let name = outline
+                .attribute("title")
+                .or_else(|| outline.attribute("text"))
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty());
+
+            let feed_url = outline.attribute("xmlUrl").map(str::trim).filter(|u| !u.is_empty());
+            let page_url = outline.attribute("htmlUrl").map(str::trim).filter(|u| !u.is_empty());
+
+            let mut subscription = match (feed_url, page_url) {
+                // Our own exports link the source URL, which may be a channel tab or playlist
+                (Some(feed_url), Some(page_url))
+                    if youtube_feed_channel(feed_url).is_some() && platform_for_url(page_url) == "youtube" =>
+                {
+                    subscription_for_url(page_url, name)
+                }
+                (Some(feed_url), _) => match youtube_feed_channel(feed_url) {
+                    Some(channel_url) => Subscription { name, platform: "youtube", channel_url },
+                    None => Subscription { name, platform: "rss", channel_url: feed_url.to_string() },
+                },
+                // Outlines with only a web page link, e.g. lists of channel URLs
+                (None, Some(page_url)) => subscription_for_url(page_url, name),
+                (None, None) => return None,
+            };
+
+            // Exports name the platform, which beats guessing from the URL
+            if let Some(platform) = outline
+                .attribute("platform")
+                .and_then(|p| PLATFORMS.iter().find(|known| **known == p))
+            {
+                subscription.platform = platform;
+            }
+            Some(subscription)
+        })
+        .collect())
+}
+
+/// A subscription to a URL of unknown kind
+fn subscription_for_url(url: &str, name: Option<String>) -> Subscription {
+    match youtube_feed_channel(url) {
+        Some(channel_url) => Subscription { name, platform: "youtube", channel_url },
+        None => Subscription { name, platform: platform_for_url(url), channel_url: url.to_string() },
+    }
+}
+
+/// Channel or playlist URL behind a YouTube feed URL
+fn youtube_feed_channel(feed_url: &str) -> Option<String> {
+    let query = feed_url.split_once("youtube.com/feeds/videos.xml?")?.1;
+    query.split('&').find_map(|pair| match pair.split_once('=')? {
+        ("channel_id", id) if !id.is_empty() => Some(format!("https://www.youtube.com/channel/{}", id)),
+        ("playlist_id", id) if !id.is_empty() => Some(format!("https://www.youtube.com/playlist?list={}", id)),
+        _ => None,
+    })
+}
+
+/// Takeout's subscriptions.csv: Channel Id, Channel Url, Channel Title
+fn parse_takeout_csv(content: &str) -> Result<Vec<Subscription>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut subscriptions = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid subscriptions.csv: {}", e))?;
+        let channel_id = record.get(0).map(str::trim).unwrap_or_default();
+        let channel_url = record
+            .get(1)
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| url.replacen("http://", "https://", 1))
+            .or_else(|| (!channel_id.is_empty()).then(|| format!("https://www.youtube.com/channel/{}", channel_id)));
+
+        if let Some(channel_url) = channel_url {
+            subscriptions.push(Subscription {
+                name: record.get(2).map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+                platform: "youtube",
+                channel_url,
+            });
+        }
+    }
+    Ok(subscriptions)
+}
+
+/// Guess the platform of a channel URL from its host
+pub fn platform_for_url(url: &str) -> &'static str {
+    let host = url_key(url).split(['/', '?', '#']).next().unwrap_or_default().to_string();
+    let path = url.split(['?', '#']).next().unwrap_or_default().to_lowercase();
+
+    if host == "youtube.com" || host.ends_with(".youtube.com") || host == "youtu.be" {
+        "youtube"
+    } else if host == "patreon.com" {
+        "patreon"
+    } else if host == "twitch.tv" {
+        "twitch"
+    } else if [".xml", ".rss", ".atom", "/feed", "/rss"].iter().any(|suffix| path.trim_end_matches('/').ends_with(suffix)) {
+        "rss"
+    } else {
+        "generic"
+    }
+}
+
+/// Why a URL can't be added as a source, if it can't
+pub fn check_url(subscription: &Subscription) -> Option<String> {
+    let url = &subscription.channel_url;
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Some("Not a web URL".to_string());
+    }
+    None
+}
+
+/// A channel URL reduced for duplicate checks: no scheme, "www." or "m.",
+/// trailing slash or host case
+pub fn url_key(url: &str) -> String {
+    let rest = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_start_matches("m.")
+        .trim_end_matches('/');
+    match rest.split_once('/') {
+        Some((host, path)) => format!("{}/{}", host.to_lowercase(), path),
+        None => rest.to_lowercase(),
+    }
+}
+
+/// YouTube channel ID in a /channel/ URL, to match sources validated from a handle URL
+pub fn youtube_channel_id(url: &str) -> Option<&str> {
+    url.split_once("/channel/")?
+        .1
+        .split(['/', '?', '#'])
+        .next()
+        .filter(|id| id.starts_with("UC"))
+}
+
+/// A creator name for a subscription without a title: the handle, login or last
+/// path segment of its URL
+pub fn name_from_url(url: &str) -> String {
+    let key = url_key(url);
+    let path = key.split(['?', '#']).next().unwrap_or_default();
+    path.split('/')
+        .skip(1)
+        .filter(|segment| !segment.is_empty())
+        .find(|segment| !matches!(*segment, "c" | "user" | "channel" | "cw"))
+        .map(|segment| segment.trim_start_matches('@').to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(file_name: &str, content: &str) -> (SubscriptionFormat, Vec<Subscription>) {
+        let format = SubscriptionFormat::detect(file_name, content);
+        (format, parse_subscriptions(content, format).unwrap())
+    }
+
+    fn summary(subscriptions: &[Subscription]) -> Vec<(Option<&str>, &str, &str)> {
+        subscriptions
+            .iter()
+            .map(|s| (s.name.as_deref(), s.platform, s.channel_url.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_nested_opml() {
+        let opml = r#"<?xml version="1.0"?>
+<opml version="1.1">
+  <body>
+    <outline text="YouTube Subscriptions">
+      <outline text="Some Channel" title="Some Channel" type="rss"
+               xmlUrl="https://www.youtube.com/feeds/videos.xml?channel_id=UCabc123" />
+      <outline title="A Playlist" xmlUrl="https://www.youtube.com/feeds/videos.xml?playlist_id=PLxyz" />
+    </outline>
+    <outline text="Blog" xmlUrl="https://example.com/feed.xml" htmlUrl="https://example.com/" />
+    <outline text="Exported" platform="patreon" htmlUrl="https://www.patreon.com/someone" />
+    <outline text="Tab" xmlUrl="https://www.youtube.com/feeds/videos.xml?channel_id=UCdef456"
+             htmlUrl="https://www.youtube.com/@handle/videos" />
+    <outline text="Empty folder" />
+  </body>
+</opml>"#;
+
+        let (format, subscriptions) = parse("subs.xml", opml);
+        assert_eq!(format, SubscriptionFormat::Opml);
+        assert_eq!(
+            summary(&subscriptions),
+            vec![
+                (Some("Some Channel"), "youtube", "https://www.youtube.com/channel/UCabc123"),
+                (Some("A Playlist"), "youtube", "https://www.youtube.com/playlist?list=PLxyz"),
+                (Some("Blog"), "rss", "https://example.com/feed.xml"),
+                (Some("Exported"), "patreon", "https://www.patreon.com/someone"),
+                (Some("Tab"), "youtube", "https://www.youtube.com/@handle/videos"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_xml() {
+        assert!(parse_subscriptions("<rss version=\"2.0\"></rss>", SubscriptionFormat::Opml).is_err());
+    }
+
+    #[test]
+    fn parses_takeout_csv() {
+        let csv = "\u{feff}Channel Id,Channel Url,Channel Title\n\
+                   UCabc123,http://www.youtube.com/channel/UCabc123,Some Channel\n\
+                   UCdef456,,\n\
+                   ,,\n";
+
+        let (format, subscriptions) = parse("subscriptions.csv", csv);
+        assert_eq!(format, SubscriptionFormat::TakeoutCsv);
+        assert_eq!(
+            summary(&subscriptions),
+            vec![
+                (Some("Some Channel"), "youtube", "https://www.youtube.com/channel/UCabc123"),
+                (None, "youtube", "https://www.youtube.com/channel/UCdef456"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_url_list() {
+        let list = "# my channels\n\
+                    https://www.youtube.com/@someone\n\
+                    \n\
+                    https://www.twitch.tv/streamer\n\
+                    https://example.com/blog/rss\n\
+                    https://example.com/videos\n\
+                    https://www.youtube.com/feeds/videos.xml?channel_id=UCabc123\n";
+
+        let (format, subscriptions) = parse("channels.txt", list);
+        assert_eq!(format, SubscriptionFormat::UrlList);
+        assert_eq!(
+            summary(&subscriptions),
+            vec![
+                (None, "youtube", "https://www.youtube.com/@someone"),
+                (None, "twitch", "https://www.twitch.tv/streamer"),
+                (None, "rss", "https://example.com/blog/rss"),
+                (None, "generic", "https://example.com/videos"),
+                (None, "youtube", "https://www.youtube.com/channel/UCabc123"),
+            ]
+        );
+    }
+
+    #[test]
+    fn opml_extension_is_a_fallback() {
+        assert_eq!(SubscriptionFormat::detect("subs.opml", ""), SubscriptionFormat::Opml);
+        assert_eq!(SubscriptionFormat::detect("subs.txt", "  <opml/>"), SubscriptionFormat::Opml);
+        assert_eq!(SubscriptionFormat::detect("subs.opml", "https://a.com\n"), SubscriptionFormat::Opml);
+    }
+
+    #[test]
+    fn url_key_ignores_scheme_www_and_trailing_slash() {
+        let key = url_key("https://www.youtube.com/@Someone");
+        assert_eq!(key, "youtube.com/@Someone");
+        assert_eq!(url_key("http://YouTube.com/@Someone/"), key);
+        assert_eq!(url_key("  https://m.youtube.com/@Someone "), key);
+        // Paths stay case sensitive
+        assert_ne!(url_key("https://www.youtube.com/@someone"), key);
+    }
+
+    #[test]
+    fn channel_ids_match_across_url_forms() {
+        assert_eq!(youtube_channel_id("https://www.youtube.com/channel/UCabc123/videos"), Some("UCabc123"));
+        assert_eq!(youtube_channel_id("https://www.youtube.com/channel/UCabc123?view=0"), Some("UCabc123"));
+        assert_eq!(youtube_channel_id("https://www.youtube.com/@someone"), None);
+    }
+
+    #[test]
+    fn names_come_from_the_url_path() {
+        assert_eq!(name_from_url("https://www.youtube.com/@someone"), "someone");
+        assert_eq!(name_from_url("https://www.youtube.com/c/Someone/videos"), "Someone");
+        assert_eq!(name_from_url("https://www.twitch.tv/streamer"), "streamer");
+        assert_eq!(name_from_url("https://example.com/"), "example.com");
+    }
+
+    #[test]
+    fn check_url_needs_a_web_url() {
+        let feed = |url: &str| Subscription { name: None, platform: "rss", channel_url: url.to_string() };
+        assert!(check_url(&feed("ftp://example.com/feed")).is_some());
+        assert!(check_url(&feed("https://example.com/feed")).is_none());
+    }
+}
@@ -0,0 +1,190 @@
+use crate::models::AppSettings;
+use crate::services::platform::{published_at_from_json, AuthRequirement, ItemMetadata, ListedItem, PlatformFetcher};
+use crate::services::video_details::VideoDetails;
+use crate::services::ytdlp::{dump_json_batch, flat_playlist, BatchEntry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Channel listings synced for a Twitch source, with the content kind they produce
+const LISTINGS: &[(&str, &str)] = &[
+    ("vod", "videos?filter=archives&sort=time"),
+    ("highlight", "videos?filter=highlights&sort=time"),
+    ("clip", "clips?filter=clips&range=all"),
+];
+
+/// Twitch past broadcasts (VODs), highlights and clips of a channel.
+/// VOD IDs keep yt-dlp's "v" prefix, which tells them apart from clip slugs.
+pub struct TwitchFetcher;
+
+impl TwitchFetcher {
+    /// Channel login from a URL like https://www.twitch.tv/name/videos
+    fn channel_login(channel_url: &str) -> Result<String, String> {
+        let trimmed = channel_url
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_start_matches("m.");
+
+        trimmed
+            .strip_prefix("twitch.tv/")
+            .and_then(|path| path.split(['/', '?', '#']).next())
+            .filter(|login| !login.is_empty())
+            .map(|login| login.to_lowercase())
+            .ok_or_else(|| format!("Not a Twitch channel URL: {}", channel_url))
+    }
+
+    fn external_id(v: &Value, url: &str, content_kind: &str) -> Option<String> {
+        let from_url = url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|segment| !segment.is_empty());
+
+        if content_kind == "clip" {
+            return from_url.or_else(|| v["id"].as_str()).map(|id| id.to_string());
+        }
+
+        v["id"]
+            .as_str()
+            .map(|id| id.to_string())
+            .or_else(|| from_url.map(|id| format!("v{}", id)))
+    }
+
+    /// Game of a VOD or clip; VOD chapters are the games played during the stream
+    fn game(v: &Value) -> Option<String> {
+        v["game"]
+            .as_str()
+            .or_else(|| v["categories"].as_array().and_then(|c| c.first()).and_then(|c| c.as_str()))
+            .or_else(|| v["chapters"].as_array().and_then(|c| c.first()).and_then(|c| c["title"].as_str()))
+            .filter(|game| !game.is_empty())
+            .map(|game| game.to_string())
+    }
+
+    fn thumbnail(v: &Value) -> Option<String> {
+        v["thumbnail"].as_str().map(|s| s.to_string()).or_else(|| {
+            v["thumbnails"]
+                .as_array()
+                .and_then(|t| t.last())
+                .and_then(|t| t["url"].as_str())
+                .map(|s| s.to_string())
+        })
+    }
+}
+
+impl PlatformFetcher for TwitchFetcher {
+    fn platform(&self) -> &'static str {
+        "twitch"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Twitch"
+    }
+
+    /// Subscriber-only VODs need cookies; everything else is public
+    fn auth(&self) -> AuthRequirement {
+        AuthRequirement::Optional
+    }
+
+    fn list(&self, channel_url: &str, cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<Vec<ListedItem>, String> {
+        let login = Self::channel_login(channel_url)?;
+        let extra_args: Vec<&str> = match cookie_path {
+            Some(cookies) => vec!["--cookies", cookies],
+            None => Vec::new(),
+        };
+
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+
+        // A channel without highlights or clips shouldn't fail the whole sync
+        for (content_kind, path) in LISTINGS {
+            let url = format!("https://www.twitch.tv/{}/{}", login, path);
+            let entries = match flat_playlist(ytdlp_path, &url, &extra_args) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+
+            items.extend(entries.into_iter().filter_map(|v| {
+                let url = v["url"]
+                    .as_str()
+                    .or_else(|| v["webpage_url"].as_str())
+                    .filter(|url| url.starts_with("http"))?
+                    .to_string();
+                let external_id = Self::external_id(&v, &url, content_kind)?;
+
+                Some(ListedItem {
+                    title: v["title"]
+                        .as_str()
+                        .filter(|t| !t.is_empty())
+                        .unwrap_or("Untitled Broadcast")
+                        .to_string(),
+                    thumbnail_url: Self::thumbnail(&v),
+                    published_at: published_at_from_json(&v),
+                    duration: v["duration"].as_f64().map(|d| d as i64),
+                    description: None,
+                    content_kind: Some(content_kind.to_string()),
+                    game: Self::game(&v),
+                    media: Vec::new(),
+                    access: None,
+                    external_id,
+                    url: Some(url),
+                    metadata_complete: false,
+                })
+            }));
+        }
+
+        if errors.len() == LISTINGS.len() {
+            return Err(errors.remove(0));
+        }
+
+        Ok(items)
+    }
+
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        cookie_path: Option<&str>,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String> {
+        let extra_args: Vec<&str> = match cookie_path {
+            Some(cookies) => vec!["--cookies", cookies],
+            None => Vec::new(),
+        };
+        let results = dump_json_batch(ytdlp_path, items, &extra_args)?;
+
+        Ok(results
+            .into_iter()
+            .map(|(id, result)| {
+                let metadata = result.map(|v| ItemMetadata {
+                    published_at: published_at_from_json(&v),
+                    duration: v["duration"].as_f64().map(|d| d as i64),
+                    thumbnail: Self::thumbnail(&v),
+                    title: v["title"].as_str().filter(|t| !t.is_empty()).map(|t| t.to_string()),
+                    game: Self::game(&v),
+                    details: Some(VideoDetails::from_json(&v)),
+                    access: None,
+                });
+                (id, metadata)
+            })
+            .collect())
+    }
+
+    fn canonical_url(&self, external_id: &str) -> Option<String> {
+        match external_id.strip_prefix('v') {
+            Some(video_id) if !video_id.is_empty() && video_id.chars().all(|c| c.is_ascii_digit()) => {
+                Some(format!("https://www.twitch.tv/videos/{}", video_id))
+            }
+            _ => Some(format!("https://clips.twitch.tv/{}", external_id)),
+        }
+    }
+
+    /// Past broadcasts are deleted after the channel's retention period;
+    /// highlights and clips are kept
+    fn expires_after_days(&self, content_kind: Option<&str>, settings: &AppSettings) -> Option<i64> {
+        (content_kind == Some("vod")).then_some(settings.twitch_vod_retention_days)
+    }
+}
@@ -0,0 +1,117 @@
+use serde_json::Value;
+
+/// Longest video still treated as a short when it has a vertical frame
+const MAX_SHORT_DURATION_SECS: f64 = 180.0;
+
+/// Extra metadata from a full yt-dlp `--dump-json` response
+#[derive(Debug, Clone, Default)]
+pub struct VideoDetails {
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+    pub view_count: Option<i64>,
+    pub like_count: Option<i64>,
+    pub chapters: Vec<VideoChapter>,
+    /// Available video heights, best first (e.g. "2160p", "1080p")
+    pub resolutions: Vec<String>,
+    pub is_live: bool,
+    pub is_short: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoChapter {
+    pub title: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+impl VideoDetails {
+    pub fn from_json(v: &Value) -> Self {
+        let strings = |key: &str| -> Vec<String> {
+            v[key]
+                .as_array()
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|s| s.as_str())
+                        .map(|s| s.to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let chapters = v["chapters"]
+            .as_array()
+            .map(|chapters| {
+                chapters
+                    .iter()
+                    .filter_map(|c| {
+                        Some(VideoChapter {
+                            title: c["title"].as_str().unwrap_or_default().to_string(),
+                            start_time: c["start_time"].as_f64()?,
+                            end_time: c["end_time"].as_f64()?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // live_status is one of is_live, was_live, post_live, is_upcoming, not_live
+        let is_live = matches!(
+            v["live_status"].as_str(),
+            Some("is_live") | Some("was_live") | Some("post_live")
+        ) || v["is_live"].as_bool().unwrap_or(false)
+            || v["was_live"].as_bool().unwrap_or(false);
+
+        Self {
+            description: v["description"]
+                .as_str()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            tags: strings("tags"),
+            categories: strings("categories"),
+            view_count: v["view_count"].as_i64(),
+            like_count: v["like_count"].as_i64(),
+            chapters,
+            resolutions: resolutions(v),
+            is_live,
+            is_short: is_short(v),
+        }
+    }
+}
+
+/// Distinct heights of the video formats, highest first
+fn resolutions(v: &Value) -> Vec<String> {
+    let mut heights: Vec<i64> = v["formats"]
+        .as_array()
+        .map(|formats| {
+            formats
+                .iter()
+                .filter(|f| f["vcodec"].as_str() != Some("none"))
+                .filter_map(|f| f["height"].as_i64())
+                .filter(|h| *h > 0)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    heights.sort_unstable_by(|a, b| b.cmp(a));
+    heights.dedup();
+    heights.into_iter().map(|h| format!("{}p", h)).collect()
+}
+
+fn is_short(v: &Value) -> bool {
+    let url = v["webpage_url"].as_str().or_else(|| v["original_url"].as_str());
+    if url.is_some_and(|u| u.contains("/shorts/")) {
+        return true;
+    }
+
+    let vertical = match (v["width"].as_i64(), v["height"].as_i64()) {
+        (Some(width), Some(height)) => height > width,
+        _ => false,
+    };
+    let short = v["duration"]
+        .as_f64()
+        .is_some_and(|d| d <= MAX_SHORT_DURATION_SECS);
+
+    vertical && short
+}
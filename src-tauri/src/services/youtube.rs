@@ -1,12 +1,11 @@
+use crate::services::http;
+use crate::services::platform::{parse_upload_date, AuthRequirement, ItemMetadata, ListedItem, PlatformFetcher};
+use crate::services::rss::RssFetcher;
+use crate::services::video_details::VideoDetails;
+use crate::services::ytdlp::{dump_json_batch, flat_playlist, BatchEntry};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
-use std::process::Command;
-
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
-#[cfg(target_os = "windows")]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 #[derive(Debug, Deserialize)]
 pub struct YouTubeVideo {
@@ -15,154 +14,431 @@ pub struct YouTubeVideo {
     pub thumbnail: Option<String>,
     pub duration: Option<f64>,
     pub upload_date: Option<String>,
+    /// "video", "short", "stream" or "podcast", from the tab the video was listed on
+    #[serde(skip)]
+    pub content_kind: Option<String>,
+    /// Only filled by the full metadata fetch; flat playlists don't carry it
+    #[serde(skip)]
+    pub details: Option<VideoDetails>,
+}
+
+/// One flat listing to run for a source
+pub struct YouTubeListing {
+    pub url: String,
+    /// Kind of every item on the listing; None for playlists, which mix kinds
+    pub content_kind: Option<&'static str>,
 }
 
 pub struct YouTubeFetcher;
 
 impl YouTubeFetcher {
-    /// Fetch videos from a YouTube channel URL
+    /// Turn a channel, channel tab or playlist URL into the listings to sync.
+    ///
+    /// Tab URLs (/videos, /shorts, /streams, /podcasts) list that tab only and
+    /// a bare channel URL lists its videos, shorts and streams tabs. Playlists,
+    /// including watch URLs with a `list` parameter, become a playlist URL.
+    /// Any other URL (legacy custom names, other tabs, YouTube Music) is listed
+    /// as given, without a content kind.
+    pub fn normalize_url(channel_url: &str) -> Vec<YouTubeListing> {
+        let as_given = || {
+            vec![YouTubeListing {
+                url: channel_url.trim().to_string(),
+                content_kind: None,
+            }]
+        };
+
+        let trimmed = channel_url
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_start_matches("m.");
+        let Some(rest) = trimmed.strip_prefix("youtube.com") else {
+            return as_given();
+        };
+
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        if let Some(list_id) = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("list="))
+            .filter(|id| !id.is_empty())
+        {
+            return vec![YouTubeListing {
+                url: format!("https://www.youtube.com/playlist?list={}", list_id),
+                content_kind: None,
+            }];
+        }
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let base_len = match segments.first() {
+            Some(first) if first.starts_with('@') => 1,
+            Some(&"channel") | Some(&"c") | Some(&"user") if segments.len() >= 2 => 2,
+            _ => return as_given(),
+        };
+        let base = format!("https://www.youtube.com/{}", segments[..base_len].join("/"));
+
+        let tabs: &[(&str, &'static str)] = match segments.get(base_len).copied() {
+            None | Some("featured") => &[("videos", "video"), ("shorts", "short"), ("streams", "stream")],
+            Some("videos") => &[("videos", "video")],
+            Some("shorts") => &[("shorts", "short")],
+            Some("streams") => &[("streams", "stream")],
+            Some("podcasts") => &[("podcasts", "podcast")],
+            Some(_) => return as_given(),
+        };
+
+        tabs.iter()
+            .map(|(tab, content_kind)| YouTubeListing {
+                url: format!("{}/{}", base, tab),
+                content_kind: Some(content_kind),
+            })
+            .collect()
+    }
+
+    /// Fetch videos from a YouTube channel, channel tab or playlist URL
     /// Returns a list of video metadata
     pub fn fetch_channel(channel_url: &str, ytdlp_path: &Path) -> Result<Vec<YouTubeVideo>, String> {
-        // Use yt-dlp to get video list in JSON format
-        // --flat-playlist: don't download, just list
-        // --dump-json: output as JSON (one line per video)
-        // --no-warnings: suppress warnings
-        // No playlist limit - fetch all videos
-        let mut cmd = Command::new(ytdlp_path);
-        cmd.args([
-            "--flat-playlist",
-            "--dump-json",
-            "--no-warnings",
-            channel_url,
-        ]);
-
-        #[cfg(target_os = "windows")]
-        cmd.creation_flags(CREATE_NO_WINDOW);
-
-        let output = cmd.output()
-            .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("yt-dlp failed: {}", stderr));
+        let mut videos = Vec::new();
+        let mut errors = Vec::new();
+
+        // Channels without Shorts or streams have no such tab; that's not an error
+        let listings = Self::normalize_url(channel_url);
+        for listing in &listings {
+            let entries = match flat_playlist(ytdlp_path, &listing.url, &[]) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+
+            videos.extend(
+                entries
+                    .iter()
+                    .map(|v| Self::flat_video_from_json(v, listing.content_kind))
+                    .filter(|v| !v.id.is_empty() && !v.title.is_empty()),
+            );
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        // Parse each line as a separate JSON object
-        let videos: Vec<YouTubeVideo> = stdout
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .filter_map(|line| {
-                serde_json::from_str::<serde_json::Value>(line)
-                    .ok()
-                    .map(|v| {
-                        // Try multiple date fields - flat-playlist may use timestamp instead of upload_date
-                        let upload_date = v["upload_date"]
-                            .as_str()
-                            .map(|s| s.to_string())
-                            .or_else(|| {
-                                // Try timestamp (Unix seconds) and convert to YYYYMMDD
-                                v["timestamp"]
-                                    .as_i64()
-                                    .or_else(|| v["release_timestamp"].as_i64())
-                                    .map(|ts| {
-                                        chrono::DateTime::from_timestamp(ts, 0)
-                                            .map(|dt| dt.format("%Y%m%d").to_string())
-                                            .unwrap_or_default()
-                                    })
-                                    .filter(|s| !s.is_empty())
-                            });
-
-                        YouTubeVideo {
-                            id: v["id"].as_str().unwrap_or_default().to_string(),
-                            title: v["title"].as_str().unwrap_or_default().to_string(),
-                            thumbnail: v["thumbnail"].as_str().map(|s| s.to_string())
-                                .or_else(|| v["thumbnails"].as_array()
-                                    .and_then(|t| t.first())
-                                    .and_then(|t| t["url"].as_str())
-                                    .map(|s| s.to_string())),
-                            duration: v["duration"].as_f64(),
-                            upload_date,
-                        }
+        if errors.len() == listings.len() {
+            return Err(errors.remove(0));
+        }
+
+        Ok(videos)
+    }
+
+    fn flat_video_from_json(v: &serde_json::Value, content_kind: Option<&str>) -> YouTubeVideo {
+        // Try multiple date fields - flat-playlist may use timestamp instead of upload_date
+        let upload_date = v["upload_date"]
+            .as_str()
+            .map(|s| s.to_string())
+            .or_else(|| {
+                // Try timestamp (Unix seconds) and convert to YYYYMMDD
+                v["timestamp"]
+                    .as_i64()
+                    .or_else(|| v["release_timestamp"].as_i64())
+                    .map(|ts| {
+                        chrono::DateTime::from_timestamp(ts, 0)
+                            .map(|dt| dt.format("%Y%m%d").to_string())
+                            .unwrap_or_default()
                     })
+                    .filter(|s| !s.is_empty())
+            });
+
+        // Playlists mix kinds; Shorts entries link to /shorts/
+        let content_kind = content_kind.unwrap_or_else(|| {
+            if v["url"].as_str().is_some_and(|url| url.contains("/shorts/")) {
+                "short"
+            } else {
+                "video"
+            }
+        });
+
+        YouTubeVideo {
+            id: v["id"].as_str().unwrap_or_default().to_string(),
+            title: v["title"].as_str().unwrap_or_default().to_string(),
+            thumbnail: v["thumbnail"].as_str().map(|s| s.to_string())
+                .or_else(|| v["thumbnails"].as_array()
+                    .and_then(|t| t.first())
+                    .and_then(|t| t["url"].as_str())
+                    .map(|s| s.to_string())),
+            duration: v["duration"].as_f64(),
+            upload_date,
+            content_kind: Some(content_kind.to_string()),
+            details: None,
+        }
+    }
+
+    /// Fetch full metadata for many videos with a single yt-dlp run.
+    /// Returns a result per video ID; the outer error means yt-dlp couldn't run.
+    pub fn fetch_videos_metadata(
+        entries: &[BatchEntry],
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<YouTubeVideo, String>>, String> {
+        let results = dump_json_batch(ytdlp_path, entries, &[])?;
+
+        Ok(results
+            .into_iter()
+            .map(|(id, result)| {
+                let video = result.map(|v| Self::video_from_json(&v, &id));
+                (id, video)
             })
-            .filter(|v| !v.id.is_empty() && !v.title.is_empty())
-            .collect();
+            .collect())
+    }
 
-        Ok(videos)
+    /// Feed of the latest 15 uploads for a bare channel or playlist source, and whether
+    /// it's a playlist. Tab sources have none: the channel feed mixes all tabs.
+    fn recent_feed_url(channel_url: &str) -> Option<(String, bool)> {
+        let listings = Self::normalize_url(channel_url);
+        match listings.as_slice() {
+            [playlist] if playlist.content_kind.is_none() => {
+                let list_id = playlist.url.split_once("list=")?.1;
+                Some((format!("https://www.youtube.com/feeds/videos.xml?playlist_id={}", list_id), true))
+            }
+            [first, _, ..] => {
+                let base = first.url.rsplit_once('/')?.0;
+                let channel_id = match base.split_once("/channel/") {
+                    Some((_, id)) => id.to_string(),
+                    None => Self::find_channel_id(&http::get_text(base, None).ok()?)?,
+                };
+                Some((format!("https://www.youtube.com/feeds/videos.xml?channel_id={}", channel_id), false))
+            }
+            _ => None,
+        }
     }
 
-    /// Convert upload_date (YYYYMMDD) to ISO 8601 format
-    pub fn parse_upload_date(upload_date: &str) -> Option<String> {
-        if upload_date.len() != 8 {
-            return None;
+    /// Channel ID from a channel page, needed for @handle, /c/ and /user/ URLs
+    fn find_channel_id(html: &str) -> Option<String> {
+        ["<link rel=\"canonical\" href=\"https://www.youtube.com/channel/", "\"externalId\":\""]
+            .iter()
+            .find_map(|marker| {
+                let start = html.find(marker)? + marker.len();
+                let id: String = html[start..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                    .collect();
+                (id.starts_with("UC") && id.len() == 24).then_some(id)
+            })
+    }
+
+    fn video_from_json(v: &serde_json::Value, video_id: &str) -> YouTubeVideo {
+        // Try multiple date fields for better accuracy
+        let upload_date = v["upload_date"]
+            .as_str()
+            .map(|s| s.to_string())
+            .or_else(|| {
+                // Try timestamp (Unix seconds) and convert to YYYYMMDD
+                v["timestamp"]
+                    .as_i64()
+                    .or_else(|| v["release_timestamp"].as_i64())
+                    .map(|ts| {
+                        chrono::DateTime::from_timestamp(ts, 0)
+                            .map(|dt| dt.format("%Y%m%d").to_string())
+                            .unwrap_or_default()
+                    })
+                    .filter(|s| !s.is_empty())
+            });
+
+        YouTubeVideo {
+            id: v["id"].as_str().unwrap_or(video_id).to_string(),
+            title: v["title"].as_str().unwrap_or_default().to_string(),
+            thumbnail: v["thumbnail"].as_str().map(|s| s.to_string())
+                .or_else(|| v["thumbnails"].as_array()
+                    .and_then(|t| t.first())
+                    .and_then(|t| t["url"].as_str())
+                    .map(|s| s.to_string())),
+            duration: v["duration"].as_f64(),
+            upload_date,
+            content_kind: None,
+            details: Some(VideoDetails::from_json(v)),
         }
+    }
+}
 
-        let year = &upload_date[0..4];
-        let month = &upload_date[4..6];
-        let day = &upload_date[6..8];
+impl PlatformFetcher for YouTubeFetcher {
+    fn platform(&self) -> &'static str {
+        "youtube"
+    }
 
-        Some(format!("{}-{}-{}T00:00:00Z", year, month, day))
+    fn display_name(&self) -> &'static str {
+        "YouTube"
     }
 
-    /// Fetch full metadata for a single video by its ID
-    /// Returns metadata including accurate published_at date
-    pub fn fetch_video_metadata(video_id: &str, ytdlp_path: &Path) -> Result<YouTubeVideo, String> {
-        let url = format!("https://www.youtube.com/watch?v={}", video_id);
+    fn auth(&self) -> AuthRequirement {
+        AuthRequirement::None
+    }
 
-        let mut cmd = Command::new(ytdlp_path);
-        cmd.args([
-            "--dump-json",
-            "--no-download",
-            "--no-warnings",
-            &url,
-        ]);
+    fn list(&self, channel_url: &str, _cookie_path: Option<&str>, ytdlp_path: &Path) -> Result<Vec<ListedItem>, String> {
+        let videos = Self::fetch_channel(channel_url, ytdlp_path)?;
 
-        #[cfg(target_os = "windows")]
-        cmd.creation_flags(CREATE_NO_WINDOW);
+        // Flat playlists often lack dates, so the metadata worker fills them in
+        Ok(videos
+            .into_iter()
+            .map(|video| ListedItem {
+                published_at: video.upload_date.as_deref().and_then(parse_upload_date),
+                duration: video.duration.map(|d| d as i64),
+                external_id: video.id,
+                url: None,
+                title: video.title,
+                thumbnail_url: video.thumbnail,
+                description: None,
+                content_kind: video.content_kind,
+                game: None,
+                media: Vec::new(),
+                access: None,
+                metadata_complete: false,
+            })
+            .collect())
+    }
 
-        let output = cmd.output()
-            .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    /// Uploads from the channel or playlist RSS feed, without running yt-dlp
+    fn list_recent(&self, channel_url: &str) -> Option<Vec<ListedItem>> {
+        let (feed_url, is_playlist) = Self::recent_feed_url(channel_url)?;
+        let entries = RssFetcher::fetch_feed(&feed_url).ok()?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("yt-dlp failed: {}", stderr));
-        }
+        Some(
+            entries
+                .into_iter()
+                .filter_map(|entry| {
+                    // The feed links Shorts to /shorts/; on a channel, streams can't be
+                    // told from videos, so new ones make the sync run the full listing
+                    let is_short = entry.link.as_deref().is_some_and(|link| link.contains("/shorts/"));
+                    let content_kind = match (is_short, is_playlist) {
+                        (true, _) => Some("short".to_string()),
+                        (false, true) => Some("video".to_string()),
+                        (false, false) => None,
+                    };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        serde_json::from_str::<serde_json::Value>(&stdout)
-            .map_err(|e| format!("Failed to parse JSON: {}", e))
-            .map(|v| {
-                // Try multiple date fields for better accuracy
-                let upload_date = v["upload_date"]
-                    .as_str()
-                    .map(|s| s.to_string())
-                    .or_else(|| {
-                        // Try timestamp (Unix seconds) and convert to YYYYMMDD
-                        v["timestamp"]
-                            .as_i64()
-                            .or_else(|| v["release_timestamp"].as_i64())
-                            .map(|ts| {
-                                chrono::DateTime::from_timestamp(ts, 0)
-                                    .map(|dt| dt.format("%Y%m%d").to_string())
-                                    .unwrap_or_default()
-                            })
-                            .filter(|s| !s.is_empty())
-                    });
-
-                YouTubeVideo {
-                    id: v["id"].as_str().unwrap_or(video_id).to_string(),
-                    title: v["title"].as_str().unwrap_or_default().to_string(),
-                    thumbnail: v["thumbnail"].as_str().map(|s| s.to_string())
-                        .or_else(|| v["thumbnails"].as_array()
-                            .and_then(|t| t.first())
-                            .and_then(|t| t["url"].as_str())
-                            .map(|s| s.to_string())),
-                    duration: v["duration"].as_f64(),
-                    upload_date,
-                }
+                    Some(ListedItem {
+                        external_id: entry.youtube_video_id?,
+                        url: None,
+                        title: entry.title,
+                        thumbnail_url: entry.thumbnail,
+                        published_at: entry.published_at,
+                        duration: None,
+                        description: entry.description,
+                        content_kind,
+                        game: None,
+                        media: Vec::new(),
+                        access: None,
+                        metadata_complete: false,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn fetch_metadata(
+        &self,
+        items: &[BatchEntry],
+        _cookie_path: Option<&str>,
+        ytdlp_path: &Path,
+    ) -> Result<HashMap<String, Result<ItemMetadata, String>>, String> {
+        let videos = Self::fetch_videos_metadata(items, ytdlp_path)?;
+
+        Ok(videos
+            .into_iter()
+            .map(|(id, video)| {
+                let metadata = video.map(|video| ItemMetadata {
+                    published_at: video.upload_date.as_deref().and_then(parse_upload_date),
+                    duration: video.duration.map(|d| d as i64),
+                    thumbnail: video.thumbnail,
+                    // Keep titles current when a video is renamed after sync
+                    title: Some(video.title).filter(|t| !t.is_empty()),
+                    game: None,
+                    details: video.details,
+                    access: None,
+                });
+                (id, metadata)
             })
+            .collect())
+    }
+
+    fn canonical_url(&self, external_id: &str) -> Option<String> {
+        Some(format!("https://www.youtube.com/watch?v={}", external_id))
+    }
+
+    fn supports_sponsorblock(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listings(url: &str) -> Vec<(String, Option<&'static str>)> {
+        YouTubeFetcher::normalize_url(url)
+            .into_iter()
+            .map(|listing| (listing.url, listing.content_kind))
+            .collect()
+    }
+
+    fn channel_tabs(base: &str) -> Vec<(String, Option<&'static str>)> {
+        vec![
+            (format!("{}/videos", base), Some("video")),
+            (format!("{}/shorts", base), Some("short")),
+            (format!("{}/streams", base), Some("stream")),
+        ]
+    }
+
+    #[test]
+    fn bare_channels_list_their_tabs() {
+        assert_eq!(listings("https://www.youtube.com/@someone"), channel_tabs("https://www.youtube.com/@someone"));
+        assert_eq!(
+            listings("youtube.com/channel/UCabc123/"),
+            channel_tabs("https://www.youtube.com/channel/UCabc123")
+        );
+        assert_eq!(listings("http://m.youtube.com/c/Someone"), channel_tabs("https://www.youtube.com/c/Someone"));
+        assert_eq!(listings("https://youtube.com/user/someone"), channel_tabs("https://www.youtube.com/user/someone"));
+        assert_eq!(
+            listings("https://www.youtube.com/@someone/featured"),
+            channel_tabs("https://www.youtube.com/@someone")
+        );
+    }
+
+    #[test]
+    fn tabs_list_only_themselves() {
+        let base = "https://www.youtube.com/@someone";
+        for (tab, kind) in [("videos", "video"), ("shorts", "short"), ("streams", "stream"), ("podcasts", "podcast")] {
+            assert_eq!(
+                listings(&format!("{}/{}", base, tab)),
+                vec![(format!("{}/{}", base, tab), Some(kind))],
+                "{}",
+                tab
+            );
+        }
+    }
+
+    #[test]
+    fn list_parameter_makes_a_playlist() {
+        let playlist = vec![("https://www.youtube.com/playlist?list=PLxyz".to_string(), None)];
+        assert_eq!(listings("https://www.youtube.com/playlist?list=PLxyz"), playlist);
+        assert_eq!(listings("https://www.youtube.com/watch?v=abc&list=PLxyz#t=10"), playlist);
+    }
+
+    #[test]
+    fn query_and_fragment_are_dropped() {
+        assert_eq!(
+            listings("https://www.youtube.com/@someone/videos?view=0&sort=p"),
+            vec![("https://www.youtube.com/@someone/videos".to_string(), Some("video"))]
+        );
+        assert_eq!(
+            listings("https://www.youtube.com/@someone#about"),
+            channel_tabs("https://www.youtube.com/@someone")
+        );
+    }
+
+    #[test]
+    fn other_urls_are_listed_as_given() {
+        for url in [
+            "https://www.youtube.com/SomeLegacyName",
+            "https://www.youtube.com/@someone/live",
+            "https://www.youtube.com/@someone/playlists",
+            "https://music.youtube.com/channel/UCabc123",
+        ] {
+            assert_eq!(listings(url), vec![(url.to_string(), None)], "{}", url);
+        }
     }
 }
@@ -0,0 +1,134 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// An item to look up in a batch: our key for it and the URL handed to yt-dlp
+pub struct BatchEntry {
+    pub id: String,
+    pub url: String,
+}
+
+/// List the entries of a playlist or channel URL without resolving each one
+pub fn flat_playlist(ytdlp_path: &Path, url: &str, extra_args: &[&str]) -> Result<Vec<Value>, String> {
+    let mut cmd = Command::new(ytdlp_path);
+    cmd.args(["--flat-playlist", "--dump-json", "--no-warnings"])
+        .args(extra_args)
+        .arg(url);
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("yt-dlp failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    Ok(stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .collect())
+}
+
+/// Metadata of a playlist or channel URL itself, without any of its entries
+pub fn playlist_info(ytdlp_path: &Path, url: &str, extra_args: &[&str]) -> Result<Value, String> {
+    let mut cmd = Command::new(ytdlp_path);
+    cmd.args(["--flat-playlist", "--dump-single-json", "--playlist-items", "0", "--no-warnings"])
+        .args(extra_args)
+        .arg(url);
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("yt-dlp failed: {}", stderr.trim()));
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Failed to parse yt-dlp output: {}", e))
+}
+
+/// Run one yt-dlp process over many URLs and return the `--dump-json` output of each.
+///
+/// yt-dlp prints one JSON line per URL it could extract; lines are matched back
+/// to entries by `original_url`, falling back to the extracted `id`. Entries
+/// without output get the `ERROR:` line yt-dlp printed for them, or a retryable
+/// error when there is none. The outer error is only returned when yt-dlp
+/// couldn't be run at all.
+pub fn dump_json_batch(
+    ytdlp_path: &Path,
+    entries: &[BatchEntry],
+    extra_args: &[&str],
+) -> Result<HashMap<String, Result<Value, String>>, String> {
+    let mut cmd = Command::new(ytdlp_path);
+    cmd.args(["--dump-json", "--no-download", "--no-warnings", "--ignore-errors"])
+        .args(extra_args)
+        .args(entries.iter().map(|e| e.url.as_str()));
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let mut results: HashMap<String, Result<Value, String>> = HashMap::new();
+
+    for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+        let v = match serde_json::from_str::<Value>(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        let original_url = v["original_url"].as_str().unwrap_or_default();
+        let extracted_id = v["id"].as_str().unwrap_or_default();
+        let entry = entries
+            .iter()
+            .find(|e| e.url == original_url)
+            .or_else(|| entries.iter().find(|e| e.id == extracted_id));
+
+        // A URL can expand to several entries (e.g. multi-video posts); keep the first
+        if let Some(entry) = entry {
+            results.entry(entry.id.clone()).or_insert(Ok(v));
+        }
+    }
+
+    let error_lines: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("ERROR:"))
+        .collect();
+
+    for entry in entries {
+        if results.contains_key(&entry.id) {
+            continue;
+        }
+
+        let error = error_lines
+            .iter()
+            .find(|line| line.contains(&entry.id))
+            .map(|line| line.trim_start_matches("ERROR:").trim().to_string())
+            // Not the batch's stderr: other entries' errors in it could mark this
+            // one permanently unavailable
+            .unwrap_or_else(|| "No output from yt-dlp for this item".to_string());
+        results.insert(entry.id.clone(), Err(error));
+    }
+
+    Ok(results)
+}
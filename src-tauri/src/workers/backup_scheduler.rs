@@ -0,0 +1,90 @@
+use crate::commands::settings::load_settings;
+use crate::db::{database_path, Database};
+use crate::services::backup::create_backup;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::sleep;
+
+/// File name prefix of automatic backups; rotation never touches other files
+const AUTO_BACKUP_PREFIX: &str = "auto-backup-";
+
+/// How often the schedule is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Makes automatic backups every `backup_interval_hours` and keeps the newest
+/// `backup_keep_count` of them
+pub struct BackupScheduler;
+
+impl BackupScheduler {
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                // Give startup a head start before the first check
+                sleep(CHECK_INTERVAL).await;
+
+                let handle = app_handle.clone();
+                let result = tokio::task::spawn_blocking(move || Self::backup_if_due(&handle)).await;
+                if let Ok(Err(error)) = result {
+                    let _ = app_handle.emit("backup_failed", error);
+                }
+            }
+        });
+    }
+
+    fn backup_if_due(app_handle: &AppHandle) -> Result<(), String> {
+        let settings = {
+            let db = app_handle.state::<Database>();
+            let conn = db.conn.lock().map_err(|e| e.to_string())?;
+            load_settings(&conn).map_err(|e| e.to_string())?
+        };
+        if settings.backup_interval_hours <= 0 {
+            return Ok(());
+        }
+
+        let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+        let backup_dir = match settings.backup_directory.trim() {
+            "" => app_data_dir.join("backups"),
+            dir => PathBuf::from(dir),
+        };
+        std::fs::create_dir_all(&backup_dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+
+        let interval = Duration::from_secs(settings.backup_interval_hours as u64 * 3600);
+        let due = match Self::auto_backups(&backup_dir).first() {
+            Some((_, modified)) => modified.elapsed().map(|age| age >= interval).unwrap_or(true),
+            None => true,
+        };
+        if !due {
+            return Ok(());
+        }
+
+        let name = format!("{}{}.zip", AUTO_BACKUP_PREFIX, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        create_backup(
+            &database_path(&app_data_dir),
+            &backup_dir.join(name),
+            settings.backup_include_thumbnails,
+        )?;
+
+        let keep = settings.backup_keep_count.max(1) as usize;
+        for (path, _) in Self::auto_backups(&backup_dir).into_iter().skip(keep) {
+            let _ = std::fs::remove_file(path);
+        }
+        Ok(())
+    }
+
+    /// Automatic backups in a folder, newest first
+    fn auto_backups(dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+        let mut backups: Vec<(PathBuf, SystemTime)> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with(AUTO_BACKUP_PREFIX) && name.ends_with(".zip")
+            })
+            .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)))
+            .collect();
+        backups.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        backups
+    }
+}
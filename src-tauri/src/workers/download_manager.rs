@@ -1,6 +1,14 @@
+use crate::commands::credentials::get_cookie_path;
+use crate::commands::hooks::spawn_item_hooks;
 use crate::commands::notifications::{notify_download_completed, notify_download_failed};
+use crate::commands::settings::load_settings;
+use crate::commands::warehouse::{apply_verification, remove_warehouse_item, verify_file, write_item_sidecars};
 use crate::db::Database;
-use crate::services::{get_ffmpeg_path, get_ytdlp_path};
+use crate::models::{AppSettings, PostMedia, WarehouseItem};
+use crate::services::ffprobe::MediaProbe;
+use crate::services::platform::{item_url, require_cookies};
+use crate::services::sponsorblock::{SponsorBlockOptions, SponsorBlockSegment};
+use crate::services::{get_fetcher, get_ffmpeg_path, get_ytdlp_path, http};
 use std::collections::HashSet;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
@@ -39,82 +47,117 @@ pub struct DownloadErrorEvent {
     pub error: String,
 }
 
+#[derive(Clone, serde::Serialize)]
+pub struct DownloadCancelledEvent {
+    pub feed_item_id: String,
+}
+
 pub enum DownloadCommand {
     Download { feed_item_ids: Vec<String> },
     Cancel { feed_item_id: String },
 }
 
+/// A file of a post and where it's downloaded to
+#[derive(Clone)]
+struct PostFile {
+    media: PostMedia,
+    path: String,
+}
+
 struct DownloadInfo {
     external_id: String,
+    webpage_url: Option<String>,
     title: String,
     platform: String,
     creator_id: String,
     creator_name: String,
     published_at: Option<String>,
     duration: Option<i64>,
+    content_kind: Option<String>,
+    media: Vec<PostMedia>,
+    locked: bool,
+    required_tier: Option<String>,
     credential_id: Option<String>,
 }
 
 pub struct DownloadManager {
     sender: mpsc::Sender<DownloadCommand>,
-    #[allow(dead_code)]
-    cancelled: Arc<Mutex<HashSet<String>>>,
 }
 
 impl DownloadManager {
     pub fn new(app_handle: AppHandle) -> Self {
         let (sender, rx) = mpsc::channel::<DownloadCommand>(100);
-        let cancelled: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
 
-        let manager = Self {
-            sender,
-            cancelled: cancelled.clone(),
-        };
+        let manager = Self { sender };
+
+        // Downloads still marked as downloading were interrupted by a crash or quit
+        let interrupted = Self::get_interrupted_downloads(&app_handle);
 
         // Start the background worker
-        manager.start_worker(app_handle, rx, cancelled);
+        manager.start_worker(app_handle, rx);
+
+        // Resume them from their partial files
+        if !interrupted.is_empty() {
+            let _ = manager.queue_downloads(interrupted);
+        }
 
         manager
     }
 
-    fn start_worker(
-        &self,
-        app_handle: AppHandle,
-        mut rx: mpsc::Receiver<DownloadCommand>,
-        cancelled: Arc<Mutex<HashSet<String>>>,
-    ) {
+    fn start_worker(&self, app_handle: AppHandle, mut rx: mpsc::Receiver<DownloadCommand>) {
         tauri::async_runtime::spawn(async move {
             // Limit concurrent downloads to 2
             let semaphore = Arc::new(Semaphore::new(2));
+            // Items that are waiting for a permit or currently downloading
+            let queued: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+            // Queued or running items the user asked to cancel
+            let cancelled: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+
+            // Remove stale fragments before any download can resume from them
+            let cleanup_handle = app_handle.clone();
+            let _ = tokio::task::spawn_blocking(move || Self::cleanup_stale_partials(&cleanup_handle)).await;
 
             loop {
                 match rx.recv().await {
                     Some(DownloadCommand::Download { feed_item_ids }) => {
                         for feed_item_id in feed_item_ids {
+                            // Re-queueing an item revokes any earlier cancel request
+                            cancelled.lock().unwrap().remove(&feed_item_id);
+
+                            // Skip items that already have a task waiting or running
+                            if !queued.lock().unwrap().insert(feed_item_id.clone()) {
+                                continue;
+                            }
+
                             let app_handle = app_handle.clone();
                             let semaphore = semaphore.clone();
+                            let queued = queued.clone();
                             let cancelled = cancelled.clone();
 
                             tauri::async_runtime::spawn(async move {
                                 // Acquire semaphore permit
                                 let _permit = semaphore.acquire().await.unwrap();
 
-                                // Check if cancelled before starting
-                                {
-                                    let cancelled_set = cancelled.lock().unwrap();
-                                    if cancelled_set.contains(&feed_item_id) {
-                                        return;
-                                    }
+                                // Check if cancelled while waiting in the queue
+                                if Self::is_cancelled(&cancelled, &feed_item_id) {
+                                    Self::handle_cancelled(&app_handle, &feed_item_id, None, &[]);
+                                } else {
+                                    Self::process_download(&app_handle, &feed_item_id, &cancelled)
+                                        .await;
                                 }
 
-                                Self::process_download(&app_handle, &feed_item_id, &cancelled)
-                                    .await;
+                                // Release the item so it can be queued again
+                                cancelled.lock().unwrap().remove(&feed_item_id);
+                                queued.lock().unwrap().remove(&feed_item_id);
                             });
                         }
                     }
                     Some(DownloadCommand::Cancel { feed_item_id }) => {
-                        let mut cancelled_set = cancelled.lock().unwrap();
-                        cancelled_set.insert(feed_item_id);
+                        // Only remember cancellations for items that are actually queued,
+                        // otherwise a stale entry would block the next download
+                        if queued.lock().unwrap().contains(&feed_item_id) {
+                            cancelled.lock().unwrap().insert(feed_item_id);
+                        }
                     }
                     None => {
                         // Channel closed, exit worker
@@ -150,8 +193,70 @@ impl DownloadManager {
             }
         };
 
+        // Locked posts would only fail in yt-dlp with a less helpful error
+        if info.locked {
+            let error = match &info.required_tier {
+                Some(tier) => format!("This post is locked. It requires the \"{}\" tier.", tier),
+                None => "This post is locked for the source's credential.".to_string(),
+            };
+            Self::emit_error(app_handle, feed_item_id, &error);
+            Self::update_feed_item_status(app_handle, feed_item_id, "error");
+            return;
+        }
+
+        // The same video may already be in the library through another source
+        if let Some(warehouse_item_id) = Self::find_archived_item(app_handle, &info) {
+            Self::link_feed_item(app_handle, feed_item_id, &warehouse_item_id);
+
+            let _ = app_handle.emit(
+                "download_completed",
+                DownloadCompletedEvent {
+                    feed_item_id: feed_item_id.to_string(),
+                    warehouse_item_id,
+                },
+            );
+            return;
+        }
+
+        let fetcher = match get_fetcher(&info.platform) {
+            Ok(fetcher) => fetcher,
+            Err(e) => {
+                Self::emit_error(app_handle, feed_item_id, &e);
+                Self::update_feed_item_status(app_handle, feed_item_id, "error");
+                return;
+            }
+        };
+
+        // Build video URL
+        let video_url = match item_url(fetcher, &info.external_id, info.webpage_url.as_deref()) {
+            Ok(url) => url,
+            Err(e) => {
+                Self::emit_error(app_handle, feed_item_id, &e);
+                Self::update_feed_item_status(app_handle, feed_item_id, "error");
+                return;
+            }
+        };
+
+        // Get cookie path if needed
+        let cookie_path = Self::get_cookie_path(app_handle, &info.platform, info.credential_id.as_deref());
+        let cookie_path = match require_cookies(fetcher, cookie_path) {
+            Ok(path) => path,
+            Err(e) => {
+                Self::emit_error(app_handle, feed_item_id, &e);
+                Self::update_feed_item_status(app_handle, feed_item_id, "error");
+                return;
+            }
+        };
+
+        // Direct downloads keep the file's own format; yt-dlp merges into mp4
+        let extension = if fetcher.downloads_directly() {
+            Self::direct_file_extension(&video_url, info.content_kind.as_deref())
+        } else {
+            "mp4".to_string()
+        };
+
         // Build output path
-        let output_path = match Self::build_output_path(app_handle, &info) {
+        let output_path = match Self::build_output_path(app_handle, &info, &extension) {
             Ok(path) => path,
             Err(e) => {
                 Self::emit_error(app_handle, feed_item_id, &e);
@@ -160,6 +265,31 @@ impl DownloadManager {
             }
         };
 
+        // Posts with files download into a folder of their own, holding the video if there
+        // is one; the warehouse item points at the video or else the first audio file
+        let current_media = if info.media.is_empty() {
+            None
+        } else {
+            let external_id = info.external_id.clone();
+            let cookie_path = cookie_path.clone();
+            tokio::task::spawn_blocking(move || fetcher.post_media(&external_id, cookie_path.as_deref()))
+                .await
+                .ok()
+                .flatten()
+        };
+        let post_files = Self::post_files(&info, current_media, &output_path);
+        let downloads_video = post_files.is_empty() || info.content_kind.as_deref() == Some("video");
+        let output_path = match (post_files.is_empty(), downloads_video) {
+            (true, _) => output_path,
+            (false, true) => Self::post_video_path(&output_path),
+            (false, false) => post_files
+                .iter()
+                .find(|file| file.media.kind == "audio")
+                .unwrap_or(&post_files[0])
+                .path
+                .clone(),
+        };
+
         // Create directory if needed
         if let Some(parent) = std::path::Path::new(&output_path).parent() {
             if let Err(e) = std::fs::create_dir_all(parent) {
@@ -169,65 +299,167 @@ impl DownloadManager {
             }
         }
 
+        // A retry replaces a download that failed verification
+        Self::discard_corrupt_download(app_handle, feed_item_id);
+
         // Update status to downloading
         Self::update_feed_item_status(app_handle, feed_item_id, "downloading");
 
-        // Build video URL and get cookie path if needed
-        let (video_url, cookie_path) = match info.platform.as_str() {
-            "youtube" => (format!("https://www.youtube.com/watch?v={}", info.external_id), None),
-            "patreon" => {
-                let cookie_path = Self::get_cookie_path(app_handle, info.credential_id.as_deref());
-                if cookie_path.is_none() {
-                    Self::emit_error(app_handle, feed_item_id, "No credential configured for Patreon. Please add a cookie file in Settings.");
+        // Get ffmpeg path (optional, for merging video+audio)
+        let ffmpeg_path = get_ffmpeg_path(app_handle).ok();
+        let settings = Self::get_settings(app_handle);
+
+        // SponsorBlock only covers some platforms and needs ffmpeg to mark or cut segments
+        let sponsorblock = match &settings {
+            Some(settings) if fetcher.supports_sponsorblock() && ffmpeg_path.is_some() => {
+                SponsorBlockOptions::from_settings(settings)
+            }
+            _ => None,
+        };
+        let segments_file = format!("{}.sponsorblock.json", output_path);
+
+        let output_path_for_download = output_path.clone();
+        let result = if fetcher.downloads_directly() {
+            // Plain media files (e.g. podcast enclosures) don't need yt-dlp
+            tokio::task::spawn_blocking({
+                let app_handle = app_handle.clone();
+                let feed_item_id = feed_item_id.to_string();
+                let cancelled = cancelled.clone();
+                move || {
+                    Self::run_direct_download(&app_handle, &feed_item_id, &video_url, cookie_path.as_deref(), &output_path_for_download, &cancelled)
+                }
+            })
+            .await
+        } else if !downloads_video {
+            tokio::task::spawn_blocking({
+                let app_handle = app_handle.clone();
+                let feed_item_id = feed_item_id.to_string();
+                let cancelled = cancelled.clone();
+                let post_files = post_files.clone();
+                move || Self::run_post_downloads(&app_handle, &feed_item_id, &post_files, cookie_path.as_deref(), &cancelled)
+            })
+            .await
+        } else {
+            // Get yt-dlp path
+            let ytdlp_path = match get_ytdlp_path(app_handle) {
+                Ok(path) => path,
+                Err(e) => {
+                    Self::emit_error(app_handle, feed_item_id, &e);
                     Self::update_feed_item_status(app_handle, feed_item_id, "error");
                     return;
                 }
-                (format!("https://www.patreon.com/posts/{}", info.external_id), cookie_path)
+            };
+
+            // Extra yt-dlp arguments for post-processing
+            let mut extra_args = fetcher.download_args(cookie_path.as_deref());
+
+            // Embedding metadata, chapters and the thumbnail needs ffmpeg
+            if ffmpeg_path.is_some() && settings.as_ref().is_some_and(|s| s.embed_metadata) {
+                extra_args.extend(Self::embed_metadata_args(&info.creator_name));
             }
-            _ => {
-                Self::emit_error(app_handle, feed_item_id, &format!("Unsupported platform: {}", info.platform));
-                Self::update_feed_item_status(app_handle, feed_item_id, "error");
-                return;
+
+            // Let yt-dlp write the full info.json; NFO files are written after the download
+            if settings.as_ref().is_some_and(|s| s.write_info_json) {
+                extra_args.push("--write-info-json".to_string());
             }
-        };
 
-        // Get yt-dlp path
-        let ytdlp_path = match get_ytdlp_path(app_handle) {
-            Ok(path) => path,
-            Err(e) => {
-                Self::emit_error(app_handle, feed_item_id, &e);
-                Self::update_feed_item_status(app_handle, feed_item_id, "error");
-                return;
+            if let Some(options) = &sponsorblock {
+                extra_args.extend(options.ytdlp_args(&segments_file));
             }
-        };
 
-        // Get ffmpeg path (optional, for merging video+audio)
-        let ffmpeg_path = get_ffmpeg_path(app_handle).ok();
+            // Run yt-dlp download
+            tokio::task::spawn_blocking({
+                let app_handle = app_handle.clone();
+                let feed_item_id = feed_item_id.to_string();
+                let cancelled = cancelled.clone();
+                let post_files = post_files.clone();
+                move || {
+                    Self::run_ytdlp_download(&app_handle, &feed_item_id, &video_url, &output_path_for_download, &cancelled, &ytdlp_path, ffmpeg_path.as_ref(), &extra_args)?;
+                    Self::run_post_downloads(&app_handle, &feed_item_id, &post_files, cookie_path.as_deref(), &cancelled)
+                }
+            })
+            .await
+        };
 
-        // Run yt-dlp download
-        let output_path_for_download = output_path.clone();
-        let result = tokio::task::spawn_blocking({
-            let app_handle = app_handle.clone();
-            let feed_item_id = feed_item_id.to_string();
-            let cancelled = cancelled.clone();
-            move || {
-                Self::run_ytdlp_download(&app_handle, &feed_item_id, &video_url, &output_path_for_download, &cancelled, &ytdlp_path, cookie_path.as_deref(), ffmpeg_path.as_ref())
-            }
-        })
-        .await;
+        // A cancelled download ends with a killed yt-dlp, which is not an error.
+        // If yt-dlp finished before the cancel took effect, keep the file.
+        if !matches!(result, Ok(Ok(()))) && Self::is_cancelled(cancelled, feed_item_id) {
+            Self::handle_cancelled(app_handle, feed_item_id, Some(&output_path), &post_files);
+            return;
+        }
 
         match result {
             Ok(Ok(())) => {
-                // Get file size
-                let file_size = std::fs::metadata(&output_path)
-                    .map(|m| m.len() as i64)
-                    .unwrap_or(0);
-
-                // Create warehouse item
-                match Self::create_warehouse_item(app_handle, &info, &output_path, file_size) {
-                    Ok(warehouse_item_id) => {
+                // Get file size, including the other files of a post
+                let file_size = match std::fs::metadata(&output_path) {
+                    Ok(metadata) => {
+                        let other_files: u64 = post_files
+                            .iter()
+                            .filter(|file| file.path != output_path)
+                            .filter_map(|file| std::fs::metadata(&file.path).ok())
+                            .map(|metadata| metadata.len())
+                            .sum();
+                        (metadata.len() + other_files) as i64
+                    }
+                    Err(e) => {
+                        let err_msg = format!("Downloaded file is missing: {}", e);
+                        notify_download_failed(app_handle, &info.title, &err_msg);
+                        Self::emit_error(app_handle, feed_item_id, &err_msg);
+                        Self::update_feed_item_status(app_handle, feed_item_id, "error");
+                        return;
+                    }
+                };
+
+                // yt-dlp exiting 0 doesn't guarantee a playable file.
+                // Images and documents of a post have nothing for ffprobe to check.
+                let is_media_file = downloads_video || post_files.iter().any(|f| f.path == output_path && f.media.kind == "audio");
+                let verification = if is_media_file {
+                    let app_handle = app_handle.clone();
+                    let output_path = output_path.clone();
+                    tokio::task::spawn_blocking(move || verify_file(&app_handle, &output_path))
+                        .await
+                        .ok()
+                        .flatten()
+                } else {
+                    None
+                };
+                let corrupt_reason = verification.as_ref().and_then(|v| v.as_ref().err()).cloned();
+
+                // Create warehouse item; a corrupt file is kept but flagged, like an import
+                match Self::create_warehouse_item(app_handle, &info, &output_path, file_size, verification) {
+                    Ok(mut warehouse_item) => {
+                        let warehouse_item_id = warehouse_item.id.clone();
+
                         // Update feed item with warehouse_item_id and status
                         Self::update_feed_item_completed(app_handle, feed_item_id, &warehouse_item_id);
+                        warehouse_item.feed_item_id = Some(feed_item_id.to_string());
+                        if corrupt_reason.is_none() {
+                            Self::add_to_archive(app_handle, &info, &warehouse_item_id);
+                        }
+
+                        if !post_files.is_empty() {
+                            let video_path = downloads_video.then_some(output_path.as_str());
+                            Self::save_item_files(app_handle, &warehouse_item_id, video_path, &post_files);
+                        }
+
+                        // Store the segments left in the file so the player can skip them
+                        if let Some(options) = &sponsorblock {
+                            let segments = options.read_kept_segments(std::path::Path::new(&segments_file));
+                            Self::save_sponsor_segments(app_handle, &warehouse_item_id, &segments);
+                        }
+                        let _ = std::fs::remove_file(&segments_file);
+
+                        // Sidecar files are a convenience; a failure doesn't fail the download
+                        let creator_dir = Self::creator_dir(app_handle, &info).ok();
+                        let _ = write_item_sidecars(&app_handle.state::<Database>(), &warehouse_item, creator_dir.as_deref());
+
+                        if let Some(reason) = corrupt_reason {
+                            Self::handle_corrupt(app_handle, feed_item_id, &info, &reason);
+                            return;
+                        }
+
+                        // Hooks may take long (e.g. re-encoding), so they don't hold a download slot
+                        spawn_item_hooks(app_handle, &warehouse_item, "download");
 
                         // Send OS notification
                         notify_download_completed(app_handle, &info.title);
@@ -265,11 +497,171 @@ impl DownloadManager {
                 Self::update_feed_item_status(app_handle, feed_item_id, "error");
             }
         }
+    }
+
+    /// Mark a download whose file failed verification as corrupt. The file stays
+    /// in the library, flagged, until the download is retried.
+    fn handle_corrupt(app_handle: &AppHandle, feed_item_id: &str, info: &DownloadInfo, reason: &str) {
+        let err_msg = format!("Downloaded file failed verification: {}", reason);
+        notify_download_failed(app_handle, &info.title, &err_msg);
+
+        Self::emit_error(app_handle, feed_item_id, &err_msg);
+        Self::update_feed_item_status(app_handle, feed_item_id, "corrupt");
+    }
+
+    /// Remove the flagged file and warehouse item of an earlier download that
+    /// failed verification, otherwise yt-dlp would find the file and skip the retry
+    fn discard_corrupt_download(app_handle: &AppHandle, feed_item_id: &str) {
+        let db = app_handle.state::<Database>();
+        let Ok(conn) = db.conn.lock() else {
+            return;
+        };
+
+        let corrupt = conn
+            .query_row(
+                "SELECT w.id, w.file_path FROM feed_items f
+                 JOIN warehouse_items w ON w.id = f.warehouse_item_id
+                 WHERE f.id = ? AND w.verification_status = 'corrupt'",
+                [feed_item_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .ok();
+
+        if let Some((warehouse_item_id, file_path)) = corrupt {
+            let _ = std::fs::remove_file(&file_path);
+            let _ = conn.execute("UPDATE feed_items SET warehouse_item_id = NULL WHERE id = ?", [feed_item_id]);
+            let _ = remove_warehouse_item(&conn, &warehouse_item_id);
+        }
+    }
+
+    fn is_cancelled(cancelled: &Arc<Mutex<HashSet<String>>>, feed_item_id: &str) -> bool {
+        cancelled.lock().unwrap().contains(feed_item_id)
+    }
+
+    /// Mark a download as cancelled, remove its partial files and notify the frontend.
+    /// Only interrupted or failed downloads keep their fragments to resume from.
+    fn handle_cancelled(app_handle: &AppHandle, feed_item_id: &str, output_path: Option<&str>, post_files: &[PostFile]) {
+        if let Some(path) = output_path {
+            Self::remove_partial_files(path);
+        }
+        // A post's other files download directly, each to its own `.part` file
+        for file in post_files {
+            let _ = std::fs::remove_file(format!("{}.part", file.path));
+        }
+
+        Self::update_feed_item_status(app_handle, feed_item_id, "cancelled");
+
+        let _ = app_handle.emit(
+            "download_cancelled",
+            DownloadCancelledEvent {
+                feed_item_id: feed_item_id.to_string(),
+            },
+        );
+    }
+
+    /// Remove files left behind by an interrupted yt-dlp run.
+    /// yt-dlp names them after the output file: `.part`, `.ytdl`, per-format
+    /// streams (`.f137.mp4`) and merge temporaries (`.temp.mp4`). A finished file
+    /// and sidecars of the same name are kept.
+    fn remove_partial_files(output_path: &str) {
+        let path = std::path::Path::new(output_path);
+        let (parent, stem) = match (path.parent(), path.file_stem().and_then(|s| s.to_str())) {
+            (Some(parent), Some(stem)) => (parent, stem),
+            _ => return,
+        };
+
+        let entries = match std::fs::read_dir(parent) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            if Self::is_partial_file(&entry.file_name().to_string_lossy(), stem) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Whether `name` is a yt-dlp fragment of the download named `stem`
+    fn is_partial_file(name: &str, stem: &str) -> bool {
+        let Some(rest) = name.strip_prefix(stem).and_then(|rest| rest.strip_prefix('.')) else {
+            return false;
+        };
+
+        // `.f137.mp4` or `.f251-drc.webm`
+        let is_format_stream = rest
+            .strip_prefix('f')
+            .and_then(|rest| rest.split_once('.'))
+            .is_some_and(|(format_id, _)| {
+                format_id.starts_with(|c: char| c.is_ascii_digit())
+                    && format_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+
+        rest.ends_with(".part")
+            || rest.ends_with(".ytdl")
+            || rest.contains(".part-Frag")
+            || rest.starts_with("temp.")
+            || is_format_stream
+    }
+
+    fn get_interrupted_downloads(app_handle: &AppHandle) -> Vec<String> {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut stmt = match conn.prepare("SELECT id FROM feed_items WHERE download_status = 'downloading'") {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        stmt.query_map([], |row| row.get(0))
+            .ok()
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove `.part`/`.ytdl` fragments in the library that are older than the
+    /// configured maximum age. They are only useful while a download may resume.
+    fn cleanup_stale_partials(app_handle: &AppHandle) {
+        let (library_path, max_age_days) = match (Self::get_library_path(app_handle), Self::get_settings(app_handle)) {
+            (Ok(path), Some(settings)) => (path, settings.partial_download_max_age_days),
+            _ => return,
+        };
 
-        // Remove from cancelled set if present
-        {
-            let mut cancelled_set = cancelled.lock().unwrap();
-            cancelled_set.remove(feed_item_id);
+        let max_age = std::time::Duration::from_secs(max_age_days.max(0) as u64 * 24 * 60 * 60);
+        Self::remove_stale_partials_in(std::path::Path::new(&library_path), max_age);
+    }
+
+    fn remove_stale_partials_in(dir: &std::path::Path, max_age: std::time::Duration) {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                Self::remove_stale_partials_in(&path, max_age);
+                continue;
+            }
+
+            // Fragments are `name.part`, `name.ytdl` or `name.part-Frag12` for HLS/DASH pieces
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !(name.ends_with(".part") || name.ends_with(".ytdl") || name.contains(".part-Frag")) {
+                continue;
+            }
+
+            let is_stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age >= max_age);
+            if is_stale {
+                let _ = std::fs::remove_file(&path);
+            }
         }
     }
 
@@ -280,8 +672,8 @@ impl DownloadManager {
         output_path: &str,
         cancelled: &Arc<Mutex<HashSet<String>>>,
         ytdlp_path: &PathBuf,
-        cookie_path: Option<&str>,
         ffmpeg_path: Option<&PathBuf>,
+        extra_args: &[String],
     ) -> Result<(), String> {
         let mut cmd = Command::new(ytdlp_path);
 
@@ -308,12 +700,13 @@ impl DownloadManager {
             output_path,
             "--newline",
             "--no-warnings",
+            // Resume from .part files left by an earlier cancelled or crashed run
+            "--continue",
+            "--part",
         ]);
 
-        // Add cookie authentication if provided
-        if let Some(cookies) = cookie_path {
-            cmd.args(["--cookies", cookies]);
-        }
+        // Platform arguments (e.g. cookies) and post-processing options
+        cmd.args(extra_args);
 
         cmd.arg(video_url)
             .stdout(Stdio::piped())
@@ -334,6 +727,8 @@ impl DownloadManager {
                 let cancelled_set = cancelled.lock().unwrap();
                 if cancelled_set.contains(feed_item_id) {
                     let _ = child.kill();
+                    // Reap the process so it releases the partial files before cleanup
+                    let _ = child.wait();
                     return Err("Download cancelled".to_string());
                 }
             }
@@ -365,6 +760,148 @@ impl DownloadManager {
         Ok(())
     }
 
+    /// Download a plain media file over HTTP, resuming a `.part` file left by an
+    /// earlier run and emitting the same progress events as yt-dlp downloads
+    fn run_direct_download(
+        app_handle: &AppHandle,
+        feed_item_id: &str,
+        url: &str,
+        cookie_path: Option<&str>,
+        output_path: &str,
+        cancelled: &Arc<Mutex<HashSet<String>>>,
+    ) -> Result<(), String> {
+        let started = std::time::Instant::now();
+        let mut last_emit: Option<std::time::Instant> = None;
+        let mut start_bytes: Option<u64> = None;
+
+        http::download_file(url, cookie_path, output_path, |written, total| {
+            if Self::is_cancelled(cancelled, feed_item_id) {
+                return false;
+            }
+
+            // Resumed bytes don't count towards the speed
+            let start_bytes = *start_bytes.get_or_insert(written);
+            if last_emit.is_some_and(|at| at.elapsed() < std::time::Duration::from_millis(500)) {
+                return true;
+            }
+            last_emit = Some(std::time::Instant::now());
+
+            let elapsed = started.elapsed().as_secs_f64().max(0.001);
+            let speed = (written - start_bytes) as f64 / elapsed / (1024.0 * 1024.0);
+            let _ = app_handle.emit(
+                "download_progress",
+                DownloadProgressEvent {
+                    feed_item_id: feed_item_id.to_string(),
+                    percent: total.map_or(0.0, |total| written as f32 / total.max(1) as f32 * 100.0),
+                    speed: format!("{:.2}MiB/s", speed),
+                },
+            );
+            true
+        })
+    }
+
+    /// Download the files of a post one after another. Files finished by an earlier
+    /// run are kept, so a retry only fetches what's missing.
+    fn run_post_downloads(
+        app_handle: &AppHandle,
+        feed_item_id: &str,
+        files: &[PostFile],
+        cookie_path: Option<&str>,
+        cancelled: &Arc<Mutex<HashSet<String>>>,
+    ) -> Result<(), String> {
+        for file in files {
+            if std::path::Path::new(&file.path).exists() {
+                continue;
+            }
+            Self::run_direct_download(app_handle, feed_item_id, &file.media.url, cookie_path, &file.path, cancelled)?;
+        }
+        Ok(())
+    }
+
+    /// Where each file of a post goes: a folder named like the item's usual output
+    /// path, with files numbered in post order. Empty for items without files.
+    /// `current_media` is the post's file list fetched just now, which replaces the
+    /// synced one since download links expire.
+    fn post_files(info: &DownloadInfo, current_media: Option<Vec<PostMedia>>, output_path: &str) -> Vec<PostFile> {
+        if info.media.is_empty() {
+            return Vec::new();
+        }
+
+        let media = current_media
+            .filter(|media| !media.is_empty())
+            .unwrap_or_else(|| info.media.clone());
+        let folder = std::path::Path::new(output_path).with_extension("");
+
+        media
+            .into_iter()
+            .enumerate()
+            .map(|(index, media)| {
+                let name = media
+                    .file_name
+                    .clone()
+                    .or_else(|| {
+                        let path = media.url.split(['?', '#']).next().unwrap_or_default();
+                        path.rsplit('/').next().map(|name| name.to_string())
+                    })
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| media.kind.clone());
+                let file_name = format!("{:02}_{}", index + 1, Self::sanitize_filename(&name));
+
+                PostFile {
+                    path: folder.join(file_name).to_string_lossy().to_string(),
+                    media,
+                }
+            })
+            .collect()
+    }
+
+    /// Path of a post's video inside the post folder
+    fn post_video_path(output_path: &str) -> String {
+        let path = std::path::Path::new(output_path);
+        match path.file_name() {
+            Some(file_name) => path.with_extension("").join(file_name).to_string_lossy().to_string(),
+            None => output_path.to_string(),
+        }
+    }
+
+    /// Extension of a directly downloaded file, from its URL or else its content kind
+    fn direct_file_extension(url: &str, content_kind: Option<&str>) -> String {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let file_name = path.rsplit('/').next().unwrap_or_default();
+
+        match file_name.rsplit_once('.') {
+            Some((_, ext)) if (1..=5).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                ext.to_lowercase()
+            }
+            _ if content_kind == Some("audio") => "mp3".to_string(),
+            _ => "mp4".to_string(),
+        }
+    }
+
+    /// yt-dlp arguments that embed title, artist, date, description, chapters and
+    /// cover art into the downloaded file. The artist is set to the creator name
+    /// rather than the uploader so files group by creator in other players.
+    fn embed_metadata_args(creator_name: &str) -> Vec<String> {
+        // --parse-metadata takes FROM:TO; a FROM containing "%(" is an output template,
+        // so the creator name becomes a literal template with a field that never exists.
+        // Literal '%' and ':' must be escaped in the template.
+        let creator_template = format!(
+            "{}%(n3m_unset|)s",
+            creator_name.replace('%', "%%").replace(':', "\\:")
+        );
+
+        vec![
+            "--embed-metadata".to_string(),
+            "--embed-chapters".to_string(),
+            "--embed-thumbnail".to_string(),
+            // webp thumbnails can't be embedded into mp4
+            "--convert-thumbnails".to_string(),
+            "jpg".to_string(),
+            "--parse-metadata".to_string(),
+            format!("{}:%(meta_artist)s", creator_template),
+        ]
+    }
+
     fn parse_progress_line(line: &str) -> Option<(f32, String)> {
         // Parse lines like: "[download]  50.0% of 100.00MiB at 5.00MiB/s ETA 00:10"
         // Or: "[download]  50.0% of ~100.00MiB at 5.00MiB/s ETA 00:10"
@@ -403,7 +940,12 @@ impl DownloadManager {
                 s.platform,
                 s.creator_id,
                 c.name,
-                s.credential_id
+                s.credential_id,
+                fi.webpage_url,
+                fi.content_kind,
+                fi.media,
+                fi.locked,
+                fi.required_tier
              FROM feed_items fi
              JOIN sources s ON fi.source_id = s.id
              JOIN creators c ON s.creator_id = c.id
@@ -419,20 +961,32 @@ impl DownloadManager {
                     row.get::<_, String>(5)?,
                     row.get::<_, String>(6)?,
                     row.get::<_, Option<String>>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, Option<String>>(9)?,
+                    row.get::<_, Option<String>>(10)?,
+                    row.get::<_, bool>(11)?,
+                    row.get::<_, Option<String>>(12)?,
                 ))
             },
         );
 
         match result {
-            Ok((external_id, title, published_at, duration, platform, creator_id, creator_name, credential_id)) => {
+            Ok((external_id, title, published_at, duration, platform, creator_id, creator_name, credential_id, webpage_url, content_kind, media, locked, required_tier)) => {
                 Ok(DownloadInfo {
                     external_id,
+                    webpage_url,
                     title,
                     platform,
                     creator_id,
                     creator_name,
                     published_at,
                     duration,
+                    content_kind,
+                    media: media
+                        .and_then(|m| serde_json::from_str(&m).ok())
+                        .unwrap_or_default(),
+                    locked,
+                    required_tier,
                     credential_id,
                 })
             }
@@ -452,26 +1006,16 @@ impl DownloadManager {
         .map_err(|e| format!("Failed to get library_path: {}", e))
     }
 
-    fn get_cookie_path(app_handle: &AppHandle, credential_id: Option<&str>) -> Option<String> {
+    fn get_settings(app_handle: &AppHandle) -> Option<AppSettings> {
         let db = app_handle.state::<Database>();
         let conn = db.conn.lock().ok()?;
+        load_settings(&conn).ok()
+    }
 
-        match credential_id {
-            Some(cred_id) => conn
-                .query_row(
-                    "SELECT cookie_path FROM credentials WHERE id = ?",
-                    [cred_id],
-                    |row| row.get(0),
-                )
-                .ok(),
-            None => conn
-                .query_row(
-                    "SELECT cookie_path FROM credentials WHERE platform = 'patreon' AND is_default = 1",
-                    [],
-                    |row| row.get(0),
-                )
-                .ok(),
-        }
+    fn get_cookie_path(app_handle: &AppHandle, platform: &str, credential_id: Option<&str>) -> Option<String> {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().ok()?;
+        get_cookie_path(&conn, platform, credential_id)
     }
 
     fn sanitize_filename(name: &str) -> String {
@@ -496,18 +1040,21 @@ impl DownloadManager {
         Self::sanitize_filename(name)
     }
 
-    fn build_output_path(app_handle: &AppHandle, info: &DownloadInfo) -> Result<String, String> {
+    /// `{library_path}/{creator_name}`
+    fn creator_dir(app_handle: &AppHandle, info: &DownloadInfo) -> Result<std::path::PathBuf, String> {
         let library_path = Self::get_library_path(app_handle)?;
-        let creator_folder = Self::sanitize_path_component(&info.creator_name);
+        Ok(std::path::Path::new(&library_path).join(Self::sanitize_path_component(&info.creator_name)))
+    }
+
+    fn build_output_path(app_handle: &AppHandle, info: &DownloadInfo, extension: &str) -> Result<String, String> {
         let platform_folder = Self::sanitize_path_component(&info.platform);
         let sanitized_title = Self::sanitize_filename(&info.title);
 
-        // Build filename: {external_id}__{sanitized_title}.mp4
-        let filename = format!("{}__{}.mp4", info.external_id, sanitized_title);
+        // Build filename: {external_id}__{sanitized_title}.{extension}
+        let filename = format!("{}__{}.{}", Self::sanitize_filename(&info.external_id), sanitized_title, extension);
 
         // Build full path: {library_path}/{creator_name}/{platform}/{filename}
-        let path = std::path::Path::new(&library_path)
-            .join(creator_folder)
+        let path = Self::creator_dir(app_handle, info)?
             .join(platform_folder)
             .join(filename);
 
@@ -521,41 +1068,192 @@ impl DownloadManager {
         info: &DownloadInfo,
         file_path: &str,
         file_size: i64,
-    ) -> Result<String, String> {
+        verification: Option<Result<MediaProbe, String>>,
+    ) -> Result<WarehouseItem, String> {
         let db = app_handle.state::<Database>();
         let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
-        let id = uuid::Uuid::new_v4().to_string();
-        let now = chrono::Utc::now().to_rfc3339();
-        let original_url = match info.platform.as_str() {
-            "youtube" => Some(format!("https://www.youtube.com/watch?v={}", info.external_id)),
-            "patreon" => Some(format!("https://www.patreon.com/posts/{}", info.external_id)),
-            _ => None,
+        let original_url = get_fetcher(&info.platform)
+            .ok()
+            .and_then(|fetcher| item_url(fetcher, &info.external_id, info.webpage_url.as_deref()).ok());
+
+        let mut item = WarehouseItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            creator_id: info.creator_id.clone(),
+            feed_item_id: None, // set via update_feed_item_completed
+            title: info.title.clone(),
+            file_path: file_path.to_string(),
+            thumbnail_path: None,
+            platform: Some(info.platform.clone()),
+            original_url,
+            published_at: info.published_at.clone(),
+            duration: info.duration,
+            file_size,
+            imported_at: chrono::Utc::now().to_rfc3339(),
+            is_manual_import: false,
+            width: None,
+            height: None,
+            video_codec: None,
+            audio_codec: None,
+            bitrate: None,
+            fps: None,
+            verification_status: "unverified".to_string(),
+            verification_error: None,
         };
+        apply_verification(&mut item, verification);
 
         conn.execute(
             "INSERT INTO warehouse_items (
                 id, creator_id, feed_item_id, title, file_path, thumbnail_path,
                 platform, original_url, published_at, duration, file_size,
-                imported_at, is_manual_import
-            ) VALUES (?, ?, ?, ?, ?, NULL, ?, ?, ?, ?, ?, ?, 0)",
+                imported_at, is_manual_import,
+                width, height, video_codec, audio_codec, bitrate, fps,
+                verification_status, verification_error
+            ) VALUES (?, ?, NULL, ?, ?, NULL, ?, ?, ?, ?, ?, ?, 0, ?, ?, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
-                id,
-                info.creator_id,
-                Option::<String>::None, // feed_item_id will be set via update
-                info.title,
-                file_path,
-                info.platform,
-                original_url,
-                info.published_at,
-                info.duration,
-                file_size,
-                now,
+                item.id,
+                item.creator_id,
+                item.title,
+                item.file_path,
+                item.platform,
+                item.original_url,
+                item.published_at,
+                item.duration,
+                item.file_size,
+                item.imported_at,
+                item.width,
+                item.height,
+                item.video_codec,
+                item.audio_codec,
+                item.bitrate,
+                item.fps,
+                item.verification_status,
+                item.verification_error,
             ],
         )
         .map_err(|e| format!("Failed to create warehouse item: {}", e))?;
 
-        Ok(id)
+        Ok(item)
+    }
+
+    /// Record every file of a post download, the video first
+    fn save_item_files(app_handle: &AppHandle, warehouse_item_id: &str, video_path: Option<&str>, files: &[PostFile]) {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let video = video_path.map(|path| (path, "video", None));
+        let others = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.media.kind.as_str(), file.media.mime_type.as_deref()));
+
+        for (position, (path, kind, mime_type)) in video.into_iter().chain(others).enumerate() {
+            let file_size = std::fs::metadata(path).map(|m| m.len() as i64).unwrap_or(0);
+            let _ = conn.execute(
+                "INSERT INTO warehouse_item_files (id, warehouse_item_id, position, file_path, kind, mime_type, file_size)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    warehouse_item_id,
+                    position as i64,
+                    path,
+                    kind,
+                    mime_type,
+                    file_size,
+                ],
+            );
+        }
+    }
+
+    fn save_sponsor_segments(app_handle: &AppHandle, warehouse_item_id: &str, segments: &[SponsorBlockSegment]) {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        for segment in segments {
+            let _ = conn.execute(
+                "INSERT INTO sponsor_segments (id, warehouse_item_id, category, action_type, start_time, end_time)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    warehouse_item_id,
+                    segment.category,
+                    segment.action_type,
+                    segment.start_time,
+                    segment.end_time,
+                ],
+            );
+        }
+    }
+
+    /// Look up a previous download of the same platform video.
+    /// Archive entries whose warehouse item or file is gone, or whose file is
+    /// known to be corrupt, are dropped.
+    fn find_archived_item(app_handle: &AppHandle, info: &DownloadInfo) -> Option<String> {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().ok()?;
+
+        let archived = conn
+            .query_row(
+                "SELECT a.warehouse_item_id, w.file_path, w.verification_status
+                 FROM download_archive a
+                 LEFT JOIN warehouse_items w ON a.warehouse_item_id = w.id
+                 WHERE a.platform = ? AND a.external_id = ?",
+                [&info.platform, &info.external_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            )
+            .ok()?;
+
+        match archived {
+            (warehouse_item_id, Some(file_path), status)
+                if status.as_deref() != Some("corrupt") && std::path::Path::new(&file_path).exists() =>
+            {
+                Some(warehouse_item_id)
+            }
+            _ => {
+                let _ = conn.execute(
+                    "DELETE FROM download_archive WHERE platform = ? AND external_id = ?",
+                    [&info.platform, &info.external_id],
+                );
+                None
+            }
+        }
+    }
+
+    fn add_to_archive(app_handle: &AppHandle, info: &DownloadInfo, warehouse_item_id: &str) {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock();
+        if let Ok(conn) = conn {
+            let now = chrono::Utc::now().to_rfc3339();
+            let _ = conn.execute(
+                "INSERT OR REPLACE INTO download_archive (platform, external_id, warehouse_item_id, created_at)
+                 VALUES (?, ?, ?, ?)",
+                [&info.platform, &info.external_id, warehouse_item_id, &now],
+            );
+        }
+    }
+
+    /// Point a feed item at an existing warehouse item without re-downloading.
+    /// The warehouse item keeps its link to the feed item it was downloaded from.
+    fn link_feed_item(app_handle: &AppHandle, feed_item_id: &str, warehouse_item_id: &str) {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock();
+        if let Ok(conn) = conn {
+            let _ = conn.execute(
+                "UPDATE feed_items SET download_status = 'downloaded', warehouse_item_id = ? WHERE id = ?",
+                [warehouse_item_id, feed_item_id],
+            );
+        }
     }
 
     fn update_feed_item_status(app_handle: &AppHandle, feed_item_id: &str, status: &str) {
@@ -607,3 +1305,33 @@ impl DownloadManager {
             .map_err(|e| format!("Failed to cancel download: {}", e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_files_are_only_ytdlp_fragments() {
+        let stem = "2024-01-01 - Video";
+        for name in [
+            "2024-01-01 - Video.mp4.part",
+            "2024-01-01 - Video.mp4.ytdl",
+            "2024-01-01 - Video.mp4.part-Frag12",
+            "2024-01-01 - Video.f137.mp4",
+            "2024-01-01 - Video.f251-drc.webm.part",
+            "2024-01-01 - Video.temp.mp4",
+        ] {
+            assert!(DownloadManager::is_partial_file(name, stem), "{}", name);
+        }
+        for name in [
+            "2024-01-01 - Video.mp4",
+            "2024-01-01 - Video.nfo",
+            "2024-01-01 - Video.info.json",
+            "2024-01-01 - Video.jpg",
+            "2024-01-01 - Video.fr.vtt",
+            "2024-01-01 - Video 2.mp4.part",
+        ] {
+            assert!(!DownloadManager::is_partial_file(name, stem), "{}", name);
+        }
+    }
+}
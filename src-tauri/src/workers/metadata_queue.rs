@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Priority tiers of the metadata queue, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataPriority {
+    /// Items currently on screen
+    Visible,
+    /// Items the user explicitly asked for (fetch or retry)
+    Requested,
+    /// Incomplete items picked up by the background scan
+    Background,
+    /// Complete items due for a refresh under the refresh policy
+    Refresh,
+}
+
+impl MetadataPriority {
+    const ALL: [MetadataPriority; 4] = [
+        MetadataPriority::Visible,
+        MetadataPriority::Requested,
+        MetadataPriority::Background,
+        MetadataPriority::Refresh,
+    ];
+
+    /// Tiers that stop while the worker is paused
+    fn is_background(self) -> bool {
+        matches!(self, MetadataPriority::Background | MetadataPriority::Refresh)
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Snapshot of what the metadata worker is waiting on
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataQueueStatus {
+    pub visible: Vec<String>,
+    pub requested: Vec<String>,
+    pub background: Vec<String>,
+    pub refresh: Vec<String>,
+    pub in_flight: Vec<String>,
+    /// Background and refresh fetching is paused; visible and requested items still run
+    pub paused: bool,
+}
+
+/// Feed item ids waiting for metadata, one FIFO per priority tier.
+/// An item is only ever in one tier or in flight.
+#[derive(Default)]
+pub struct MetadataQueue {
+    tiers: [VecDeque<String>; 4],
+    queued: HashMap<String, MetadataPriority>,
+    in_flight: HashSet<String>,
+    pub paused: bool,
+}
+
+impl MetadataQueue {
+    /// Queue items at a priority. Items already waiting at the same or a higher
+    /// priority, or being fetched, are dropped; items waiting lower are promoted.
+    pub fn push(&mut self, feed_item_ids: Vec<String>, priority: MetadataPriority) {
+        for id in feed_item_ids {
+            if self.in_flight.contains(&id) {
+                continue;
+            }
+
+            match self.queued.get(&id) {
+                Some(existing) if *existing <= priority => continue,
+                Some(existing) => self.tiers[existing.index()].retain(|queued| queued != &id),
+                None => {}
+            }
+
+            self.queued.insert(id.clone(), priority);
+            self.tiers[priority.index()].push_back(id);
+        }
+    }
+
+    /// Take up to `max` items from the highest non-empty tier and mark them in flight.
+    /// The background and refresh tiers are skipped while paused.
+    pub fn pop_batch(&mut self, max: usize) -> Option<(MetadataPriority, Vec<String>)> {
+        let priority = MetadataPriority::ALL.into_iter().find(|p| {
+            !self.tiers[p.index()].is_empty() && (!self.paused || !p.is_background())
+        })?;
+
+        let tier = &mut self.tiers[priority.index()];
+        let count = max.min(tier.len());
+        let ids: Vec<String> = tier.drain(..count).collect();
+
+        for id in &ids {
+            self.queued.remove(id);
+            self.in_flight.insert(id.clone());
+        }
+
+        Some((priority, ids))
+    }
+
+    /// Put in-flight items back at the front of their tier, e.g. when out of rate budget
+    pub fn requeue_front(&mut self, feed_item_ids: Vec<String>, priority: MetadataPriority) {
+        for id in feed_item_ids.into_iter().rev() {
+            self.in_flight.remove(&id);
+            if self.queued.contains_key(&id) {
+                continue;
+            }
+            self.queued.insert(id.clone(), priority);
+            self.tiers[priority.index()].push_front(id);
+        }
+    }
+
+    pub fn finish(&mut self, feed_item_ids: &[String]) {
+        for id in feed_item_ids {
+            self.in_flight.remove(id);
+        }
+    }
+
+    pub fn is_tier_empty(&self, priority: MetadataPriority) -> bool {
+        self.tiers[priority.index()].is_empty()
+    }
+
+    pub fn status(&self) -> MetadataQueueStatus {
+        let tier = |priority: MetadataPriority| self.tiers[priority.index()].iter().cloned().collect();
+
+        MetadataQueueStatus {
+            visible: tier(MetadataPriority::Visible),
+            requested: tier(MetadataPriority::Requested),
+            background: tier(MetadataPriority::Background),
+            refresh: tier(MetadataPriority::Refresh),
+            in_flight: self.in_flight.iter().cloned().collect(),
+            paused: self.paused,
+        }
+    }
+}
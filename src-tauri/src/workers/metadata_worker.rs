@@ -1,11 +1,40 @@
+use crate::commands::credentials::get_cookie_path;
+use crate::commands::settings::load_settings;
 use crate::db::Database;
-use crate::services::{get_ytdlp_path, PatreonFetcher, YouTubeFetcher};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use crate::services::rate_limit::{parse_rate_limits, RateBudget, DEFAULT_REQUESTS_PER_MINUTE};
+use crate::services::refresh_policy::parse_refresh_policy;
+use crate::services::video_details::VideoDetails;
+use crate::services::platform::{expires_at, item_url, require_cookies, ItemMetadata};
+use crate::services::ytdlp::BatchEntry;
+use crate::services::{get_fetcher, get_ytdlp_path, PlatformFetcher};
+use crate::workers::metadata_queue::{MetadataPriority, MetadataQueue, MetadataQueueStatus};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::mpsc;
-use tokio::time::{interval, sleep};
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+/// Most items passed to a single yt-dlp run
+const METADATA_BATCH_SIZE: usize = 25;
+
+/// Failed fetches before an item is marked metadata_unavailable
+const MAX_METADATA_ATTEMPTS: i64 = 6;
+
+/// Delay before the first retry; doubles with every further failure
+const RETRY_BASE_DELAY_SECS: i64 = 5 * 60;
+
+/// yt-dlp errors that won't go away by retrying
+const PERMANENT_ERRORS: &[&str] = &[
+    "Private video",
+    "Video unavailable",
+    "This video has been removed",
+    "This video is no longer available",
+    "account associated with this video has been terminated",
+    "HTTP Error 404",
+    "HTTP Error 410",
+];
 
 #[derive(Clone, serde::Serialize)]
 pub struct MetadataEvent {
@@ -14,120 +43,276 @@ pub struct MetadataEvent {
     pub message: Option<String>,
 }
 
-pub enum MetadataCommand {
-    FetchImmediate(Vec<String>),
-    Pause,
-    Resume,
-}
+/// Items sharing a platform and credential
+type ItemGroup = ((String, Option<String>), Vec<FeedItemInfo>);
 
 struct FeedItemInfo {
     id: String,
     external_id: String,
+    webpage_url: Option<String>,
+    content_kind: Option<String>,
     platform: String,
     credential_id: Option<String>,
 }
 
 pub struct MetadataWorker {
-    sender: mpsc::Sender<MetadataCommand>,
+    queue: Arc<Mutex<MetadataQueue>>,
+    wake: Arc<Notify>,
 }
 
 impl MetadataWorker {
     pub fn new(app_handle: AppHandle) -> Self {
-        let (sender, rx) = mpsc::channel::<MetadataCommand>(100);
-
-        let worker = Self { sender };
+        let worker = Self {
+            queue: Arc::new(Mutex::new(MetadataQueue::default())),
+            wake: Arc::new(Notify::new()),
+        };
 
         // Start the background worker
-        worker.start_worker(app_handle, rx);
+        worker.start_worker(app_handle);
 
         worker
     }
 
-    fn start_worker(&self, app_handle: AppHandle, mut rx: mpsc::Receiver<MetadataCommand>) {
+    fn start_worker(&self, app_handle: AppHandle) {
+        let queue = self.queue.clone();
+        let wake = self.wake.clone();
+
         tauri::async_runtime::spawn(async move {
-            // Background processing interval (5 seconds)
-            let mut background_interval = interval(Duration::from_secs(5));
-            let paused = Arc::new(AtomicBool::new(false));
+            let mut budget = RateBudget::default();
 
             loop {
-                tokio::select! {
-                    _ = background_interval.tick() => {
-                        // Only process background items if not paused
-                        if !paused.load(Ordering::SeqCst) {
-                            Self::process_background_batch(&app_handle).await;
-                        }
-                    }
-                    Some(cmd) = rx.recv() => {
-                        match cmd {
-                            MetadataCommand::FetchImmediate(feed_item_ids) => {
-                                // Pause background processing during immediate fetch
-                                paused.store(true, Ordering::SeqCst);
-
-                                // Process immediate requests with 500ms delay between items
-                                for feed_item_id in feed_item_ids {
-                                    Self::fetch_single_item_metadata(&app_handle, &feed_item_id).await;
-                                    sleep(Duration::from_millis(500)).await;
-                                }
-
-                                // Resume background processing
-                                paused.store(false, Ordering::SeqCst);
-                            }
-                            MetadataCommand::Pause => {
-                                paused.store(true, Ordering::SeqCst);
-                            }
-                            MetadataCommand::Resume => {
-                                paused.store(false, Ordering::SeqCst);
-                            }
-                        }
+                // Work through the queue; when it's empty or out of budget, wait for
+                // new requests or re-scan for background items every 5 seconds
+                if !Self::process_next_batch(&app_handle, &queue, &mut budget).await {
+                    tokio::select! {
+                        _ = wake.notified() => {}
+                        _ = sleep(Duration::from_secs(5)) => {}
                     }
                 }
             }
         });
     }
 
-    /// Process a batch of incomplete items in the background
-    async fn process_background_batch(app_handle: &AppHandle) {
-        // Get up to 5 items with incomplete metadata
-        let items = Self::get_incomplete_items(app_handle, 5);
+    /// Fetch the next batch from the highest-priority tier.
+    /// Returns false if there was nothing to do within the rate budget.
+    async fn process_next_batch(
+        app_handle: &AppHandle,
+        queue: &Mutex<MetadataQueue>,
+        budget: &mut RateBudget,
+    ) -> bool {
+        Self::refill_background(app_handle, queue);
+        Self::refill_refresh(app_handle, queue);
+
+        let next = queue.lock().ok().and_then(|mut q| q.pop_batch(METADATA_BATCH_SIZE));
+        let (priority, feed_item_ids) = match next {
+            Some(next) => next,
+            None => return false,
+        };
+
+        let mut items = Vec::new();
+        for feed_item_id in &feed_item_ids {
+            match Self::get_feed_item_info(app_handle, feed_item_id) {
+                Some(info) => items.push(info),
+                None => Self::emit_error(app_handle, feed_item_id, "Feed item not found"),
+            }
+        }
+
+        let limits = Self::get_rate_limits(app_handle);
+        let mut deferred = Vec::new();
+        let mut fetched = false;
+
+        for ((platform, credential_id), mut batch) in Self::group_items(items) {
+            let per_minute = Self::requests_per_minute(&limits, &platform);
+            let allowed = budget.available(&platform, per_minute).min(batch.len());
+
+            // Whatever doesn't fit in the budget goes back to the front of its tier
+            deferred.extend(batch.split_off(allowed).into_iter().map(|item| item.id));
+            if batch.is_empty() {
+                continue;
+            }
+
+            budget.record(&platform, batch.len());
+            Self::fetch_batch(app_handle, &platform, credential_id.as_deref(), batch).await;
+            fetched = true;
+        }
+
+        if let Ok(mut q) = queue.lock() {
+            let done: Vec<String> = feed_item_ids
+                .into_iter()
+                .filter(|id| !deferred.contains(id))
+                .collect();
+            q.finish(&done);
+            q.requeue_front(deferred, priority);
+        }
+
+        fetched
+    }
+
+    /// Top up the background tier with incomplete items once it has drained
+    fn refill_background(app_handle: &AppHandle, queue: &Mutex<MetadataQueue>) {
+        let needs_refill = queue
+            .lock()
+            .map(|q| !q.paused && q.is_tier_empty(MetadataPriority::Background))
+            .unwrap_or(false);
+        if !needs_refill {
+            return;
+        }
+
+        let feed_item_ids = Self::get_incomplete_items(app_handle, METADATA_BATCH_SIZE);
+        if let Ok(mut q) = queue.lock() {
+            q.push(feed_item_ids, MetadataPriority::Background);
+        }
+    }
+
+    /// Queue complete items that are due under the refresh policy once all other work has drained
+    fn refill_refresh(app_handle: &AppHandle, queue: &Mutex<MetadataQueue>) {
+        let needs_refill = queue
+            .lock()
+            .map(|q| {
+                !q.paused
+                    && q.is_tier_empty(MetadataPriority::Background)
+                    && q.is_tier_empty(MetadataPriority::Refresh)
+            })
+            .unwrap_or(false);
+        if !needs_refill {
+            return;
+        }
+
+        let feed_item_ids = Self::get_items_due_for_refresh(app_handle, METADATA_BATCH_SIZE);
+        if let Ok(mut q) = queue.lock() {
+            q.push(feed_item_ids, MetadataPriority::Refresh);
+        }
+    }
+
+    /// Group items by platform and credential, the units a single yt-dlp run can cover
+    fn group_items(items: Vec<FeedItemInfo>) -> Vec<ItemGroup> {
+        let mut groups: Vec<ItemGroup> = Vec::new();
 
         for item in items {
-            Self::fetch_single_item_metadata(app_handle, &item.id).await;
-            // 1 second delay between background items (rate limiting)
-            sleep(Duration::from_secs(1)).await;
+            let key = (item.platform.clone(), item.credential_id.clone());
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push(item),
+                None => groups.push((key, vec![item])),
+            }
         }
+
+        groups
+    }
+
+    fn get_rate_limits(app_handle: &AppHandle) -> HashMap<String, usize> {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return HashMap::new(),
+        };
+
+        load_settings(&conn)
+            .ok()
+            .and_then(|settings| parse_rate_limits(&settings.metadata_requests_per_minute).ok())
+            .unwrap_or_default()
+    }
+
+    fn requests_per_minute(limits: &HashMap<String, usize>, platform: &str) -> usize {
+        limits.get(platform).copied().unwrap_or(DEFAULT_REQUESTS_PER_MINUTE)
     }
 
     /// Get feed items with incomplete metadata
-    fn get_incomplete_items(app_handle: &AppHandle, limit: usize) -> Vec<FeedItemInfo> {
+    fn get_incomplete_items(app_handle: &AppHandle, limit: usize) -> Vec<String> {
         let db = app_handle.state::<Database>();
         let conn = match db.conn.lock() {
             Ok(c) => c,
             Err(_) => return Vec::new(),
         };
 
+        // Items waiting for a retry are skipped until their backoff has passed,
+        // so a few failing items can't starve the rest
         let mut stmt = match conn.prepare(
-            "SELECT fi.id, fi.external_id, s.platform, s.credential_id
+            "SELECT fi.id
              FROM feed_items fi
              JOIN sources s ON fi.source_id = s.id
              WHERE fi.metadata_complete = 0
-             ORDER BY fi.created_at DESC
+               AND fi.metadata_unavailable = 0
+               AND (fi.metadata_next_attempt_at IS NULL OR fi.metadata_next_attempt_at <= ?)
+             ORDER BY fi.metadata_attempts ASC, fi.created_at DESC
              LIMIT ?"
         ) {
             Ok(s) => s,
             Err(_) => return Vec::new(),
         };
 
-        stmt.query_map([limit as i64], |row| {
-            Ok(FeedItemInfo {
-                id: row.get(0)?,
-                external_id: row.get(1)?,
-                platform: row.get(2)?,
-                credential_id: row.get(3)?,
-            })
-        })
-        .ok()
-        .map(|rows| rows.filter_map(|r| r.ok()).collect())
-        .unwrap_or_default()
+        let now = chrono::Utc::now().to_rfc3339();
+        stmt.query_map(rusqlite::params![now, limit as i64], |row| row.get(0))
+            .ok()
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get complete feed items whose metadata is older than their refresh rule allows.
+    /// Each rule covers items published between its max age and the previous rule's.
+    fn get_items_due_for_refresh(app_handle: &AppHandle, limit: usize) -> Vec<String> {
+        let db = app_handle.state::<Database>();
+        let conn = match db.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+
+        let rules = load_settings(&conn)
+            .ok()
+            .and_then(|settings| parse_refresh_policy(&settings.metadata_refresh_policy).ok())
+            .unwrap_or_default();
+        if rules.is_empty() {
+            return Vec::new();
+        }
+
+        let now = chrono::Utc::now();
+        let days_ago = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+
+        let mut params = vec![now.to_rfc3339()];
+        let mut clauses = Vec::new();
+        let mut newer_bound: Option<String> = None;
+
+        for rule in &rules {
+            let older_bound = days_ago(rule.max_age_days);
+            let mut clause = String::from("(COALESCE(fi.published_at, fi.created_at) >= ?");
+            params.push(older_bound.clone());
+
+            if let Some(newer_bound) = newer_bound {
+                clause.push_str(" AND COALESCE(fi.published_at, fi.created_at) < ?");
+                params.push(newer_bound);
+            }
+
+            clause.push_str(" AND (fi.metadata_fetched_at IS NULL OR fi.metadata_fetched_at <= ?))");
+            params.push(days_ago(rule.interval_days));
+
+            clauses.push(clause);
+            newer_bound = Some(older_bound);
+        }
+
+        // Posts of files without a video are only known from the listing; locked posts
+        // are re-checked when their credential changes
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT fi.id
+             FROM feed_items fi
+             JOIN sources s ON fi.source_id = s.id
+             WHERE fi.metadata_complete = 1
+               AND fi.metadata_unavailable = 0
+               AND (fi.metadata_next_attempt_at IS NULL OR fi.metadata_next_attempt_at <= ?)
+               AND (fi.media IS NULL OR fi.content_kind = 'video')
+               AND fi.locked = 0
+               AND ({})
+             ORDER BY fi.metadata_fetched_at ASC
+             LIMIT {}",
+            clauses.join(" OR "),
+            limit
+        )) {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| row.get(0))
+            .ok()
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
     }
 
     /// Get feed items with incomplete metadata for a specific creator
@@ -147,6 +332,8 @@ impl MetadataWorker {
              FROM feed_items fi
              JOIN sources s ON fi.source_id = s.id
              WHERE s.creator_id = ? AND fi.metadata_complete = 0
+               AND fi.metadata_unavailable = 0
+               AND (fi.metadata_next_attempt_at IS NULL OR fi.metadata_next_attempt_at <= ?)
              ORDER BY fi.created_at DESC
              LIMIT ?"
         ) {
@@ -154,128 +341,200 @@ impl MetadataWorker {
             Err(_) => return Vec::new(),
         };
 
-        stmt.query_map(rusqlite::params![creator_id, limit as i64], |row| row.get(0))
+        let now = chrono::Utc::now().to_rfc3339();
+        stmt.query_map(rusqlite::params![creator_id, now, limit as i64], |row| row.get(0))
             .ok()
             .map(|rows| rows.filter_map(|r| r.ok()).collect())
             .unwrap_or_default()
     }
 
-    /// Fetch metadata for a single feed item
-    async fn fetch_single_item_metadata(app_handle: &AppHandle, feed_item_id: &str) {
-        // Emit started event
-        let _ = app_handle.emit(
-            "metadata_update",
-            MetadataEvent {
-                feed_item_id: feed_item_id.to_string(),
-                status: "started".to_string(),
-                message: None,
-            },
-        );
+    /// Fetch metadata for a batch of feed items from one platform with a single yt-dlp run
+    async fn fetch_batch(
+        app_handle: &AppHandle,
+        platform: &str,
+        credential_id: Option<&str>,
+        mut items: Vec<FeedItemInfo>,
+    ) {
+        for item in &items {
+            let _ = app_handle.emit(
+                "metadata_update",
+                MetadataEvent {
+                    feed_item_id: item.id.clone(),
+                    status: "started".to_string(),
+                    message: None,
+                },
+            );
+        }
 
-        // Get feed item info
-        let item_info = Self::get_feed_item_info(app_handle, feed_item_id);
-        let info = match item_info {
-            Some(info) => info,
-            None => {
-                Self::emit_error(app_handle, feed_item_id, "Feed item not found");
+        // Get yt-dlp path
+        let ytdlp_path = match get_ytdlp_path(app_handle) {
+            Ok(path) => path,
+            Err(e) => {
+                Self::fail_all(app_handle, &items, &e);
                 return;
             }
         };
 
-        // Get yt-dlp path
-        let ytdlp_path = match get_ytdlp_path(app_handle) {
-            Ok(path) => path,
+        let fetcher = match get_fetcher(platform) {
+            Ok(fetcher) => fetcher,
             Err(e) => {
-                Self::emit_error(app_handle, feed_item_id, &e);
+                Self::fail_all(app_handle, &items, &e);
                 return;
             }
         };
 
-        // Fetch metadata based on platform
-        let result = match info.platform.as_str() {
-            "youtube" => {
-                let ytdlp = ytdlp_path.clone();
-                let external_id = info.external_id.clone();
-                tokio::task::spawn_blocking(move || {
-                    YouTubeFetcher::fetch_video_metadata(&external_id, &ytdlp)
-                        .map(|video| {
-                            (
-                                video.upload_date.and_then(|d| YouTubeFetcher::parse_upload_date(&d)),
-                                video.duration.map(|d| d as i64),
-                                video.thumbnail,
-                                None, // YouTube already has real titles from sync
-                            )
-                        })
-                })
-                .await
-                .map_err(|e| format!("Task panicked: {}", e))
-                .and_then(|r| r)
+        // Items without a usable URL fail on their own instead of failing the batch
+        let mut entries: Vec<BatchEntry> = Vec::new();
+        items.retain(|item| {
+            if entries.iter().any(|e| e.id == item.external_id) {
+                return true;
             }
-            "patreon" => {
-                // Get cookie path from credential
-                let cookie_path = match Self::get_cookie_path(app_handle, info.credential_id.as_deref()) {
-                    Some(path) => path,
-                    None => {
-                        Self::emit_error(
-                            app_handle,
-                            feed_item_id,
-                            "No credential configured for Patreon source",
-                        );
-                        return;
-                    }
-                };
-
-                let ytdlp = ytdlp_path.clone();
-                let external_id = info.external_id.clone();
-                tokio::task::spawn_blocking(move || {
-                    PatreonFetcher::fetch_post_metadata(&external_id, &cookie_path, &ytdlp)
-                        .map(|post| {
-                            // Include title if it's a real title (not fallback)
-                            let title = if post.title_is_fallback { None } else { Some(post.title) };
-                            (
-                                post.upload_date.and_then(|d| PatreonFetcher::parse_upload_date(&d)),
-                                post.duration.map(|d| d as i64),
-                                post.thumbnail,
-                                title,
-                            )
-                        })
-                })
-                .await
-                .map_err(|e| format!("Task panicked: {}", e))
-                .and_then(|r| r)
+            match item_url(fetcher, &item.external_id, item.webpage_url.as_deref()) {
+                Ok(url) => {
+                    entries.push(BatchEntry {
+                        id: item.external_id.clone(),
+                        url,
+                    });
+                    true
+                }
+                Err(e) => {
+                    Self::handle_failure(app_handle, &item.id, &e);
+                    false
+                }
             }
-            _ => {
-                Self::emit_error(app_handle, feed_item_id, &format!("Unknown platform: {}", info.platform));
+        });
+        if entries.is_empty() {
+            return;
+        }
+
+        // Get cookie path from the source's credential or the platform default
+        let cookie_path = Self::get_cookie_path(app_handle, platform, credential_id);
+        let cookie_path = match require_cookies(fetcher, cookie_path) {
+            Ok(path) => path,
+            Err(e) => {
+                Self::fail_all(app_handle, &items, &e);
                 return;
             }
         };
 
-        match result {
-            Ok((published_at, duration, thumbnail, title)) => {
-                // Update feed item with metadata (and title if available)
-                Self::update_feed_item_metadata(app_handle, feed_item_id, published_at, duration, thumbnail, title);
-
-                let _ = app_handle.emit(
-                    "metadata_update",
-                    MetadataEvent {
-                        feed_item_id: feed_item_id.to_string(),
-                        status: "completed".to_string(),
-                        message: None,
-                    },
-                );
-            }
+        let results = tokio::task::spawn_blocking(move || {
+            fetcher.fetch_metadata(&entries, cookie_path.as_deref(), &ytdlp_path)
+        })
+        .await
+        .map_err(|e| format!("Task panicked: {}", e))
+        .and_then(|r| r);
+
+        let results = match results {
+            Ok(results) => results,
             Err(e) => {
-                Self::emit_error(app_handle, feed_item_id, &e);
+                Self::fail_all(app_handle, &items, &e);
+                return;
+            }
+        };
+
+        for item in &items {
+            match results.get(&item.external_id) {
+                Some(Ok(metadata)) => {
+                    // Update feed item with metadata (and title if available)
+                    Self::update_feed_item_metadata(app_handle, fetcher, item, metadata);
+
+                    let _ = app_handle.emit(
+                        "metadata_update",
+                        MetadataEvent {
+                            feed_item_id: item.id.clone(),
+                            status: "completed".to_string(),
+                            message: None,
+                        },
+                    );
+                }
+                Some(Err(e)) => Self::handle_failure(app_handle, &item.id, e),
+                None => Self::handle_failure(app_handle, &item.id, "yt-dlp returned no metadata"),
             }
         }
     }
 
+    fn fail_all(app_handle: &AppHandle, items: &[FeedItemInfo], error: &str) {
+        for item in items {
+            Self::handle_failure(app_handle, &item.id, error);
+        }
+    }
+
+    /// Record a failed fetch and schedule the next attempt with exponential backoff.
+    /// Permanent errors and exhausted retries mark the item metadata_unavailable.
+    fn handle_failure(app_handle: &AppHandle, feed_item_id: &str, error: &str) {
+        let unavailable = Self::record_failure(app_handle, feed_item_id, error).unwrap_or(false);
+
+        let message = if unavailable {
+            format!("Metadata unavailable: {}", error)
+        } else {
+            error.to_string()
+        };
+        Self::emit_error(app_handle, feed_item_id, &message);
+    }
+
+    fn record_failure(app_handle: &AppHandle, feed_item_id: &str, error: &str) -> Option<bool> {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().ok()?;
+
+        let attempts: i64 = conn
+            .query_row(
+                "SELECT metadata_attempts FROM feed_items WHERE id = ?",
+                [feed_item_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .ok()?
+            + 1;
+
+        let permanent = PERMANENT_ERRORS.iter().any(|p| error.contains(p));
+        let unavailable = permanent || attempts >= MAX_METADATA_ATTEMPTS;
+
+        let next_attempt_at = if unavailable {
+            None
+        } else {
+            let delay = RETRY_BASE_DELAY_SECS << (attempts - 1).min(10);
+            Some((chrono::Utc::now() + chrono::Duration::seconds(delay)).to_rfc3339())
+        };
+
+        let _ = conn.execute(
+            "UPDATE feed_items SET
+                metadata_attempts = ?,
+                metadata_last_error = ?,
+                metadata_next_attempt_at = ?,
+                metadata_unavailable = ?
+             WHERE id = ?",
+            rusqlite::params![attempts, error, next_attempt_at, unavailable, feed_item_id],
+        );
+
+        Some(unavailable)
+    }
+
+    /// Clear the failure state of items so they are fetched again
+    pub fn reset_failures(app_handle: &AppHandle, feed_item_ids: &[String]) -> Result<(), String> {
+        let db = app_handle.state::<Database>();
+        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+        for feed_item_id in feed_item_ids {
+            conn.execute(
+                "UPDATE feed_items SET
+                    metadata_attempts = 0,
+                    metadata_last_error = NULL,
+                    metadata_next_attempt_at = NULL,
+                    metadata_unavailable = 0
+                 WHERE id = ?",
+                [feed_item_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
     fn get_feed_item_info(app_handle: &AppHandle, feed_item_id: &str) -> Option<FeedItemInfo> {
         let db = app_handle.state::<Database>();
         let conn = db.conn.lock().ok()?;
 
         conn.query_row(
-            "SELECT fi.id, fi.external_id, s.platform, s.credential_id
+            "SELECT fi.id, fi.external_id, fi.webpage_url, fi.content_kind, s.platform, s.credential_id
              FROM feed_items fi
              JOIN sources s ON fi.source_id = s.id
              WHERE fi.id = ?",
@@ -284,50 +543,41 @@ impl MetadataWorker {
                 Ok(FeedItemInfo {
                     id: row.get(0)?,
                     external_id: row.get(1)?,
-                    platform: row.get(2)?,
-                    credential_id: row.get(3)?,
+                    webpage_url: row.get(2)?,
+                    content_kind: row.get(3)?,
+                    platform: row.get(4)?,
+                    credential_id: row.get(5)?,
                 })
             },
         )
         .ok()
     }
 
-    fn get_cookie_path(app_handle: &AppHandle, credential_id: Option<&str>) -> Option<String> {
+    fn get_cookie_path(app_handle: &AppHandle, platform: &str, credential_id: Option<&str>) -> Option<String> {
         let db = app_handle.state::<Database>();
         let conn = db.conn.lock().ok()?;
-
-        match credential_id {
-            Some(cred_id) => conn
-                .query_row(
-                    "SELECT cookie_path FROM credentials WHERE id = ?",
-                    [cred_id],
-                    |row| row.get(0),
-                )
-                .ok(),
-            None => conn
-                .query_row(
-                    "SELECT cookie_path FROM credentials WHERE platform = 'patreon' AND is_default = 1",
-                    [],
-                    |row| row.get(0),
-                )
-                .ok(),
-        }
+        get_cookie_path(&conn, platform, credential_id)
     }
 
     fn update_feed_item_metadata(
         app_handle: &AppHandle,
-        feed_item_id: &str,
-        published_at: Option<String>,
-        duration: Option<i64>,
-        thumbnail: Option<String>,
-        title: Option<String>,
+        fetcher: &dyn PlatformFetcher,
+        item: &FeedItemInfo,
+        metadata: &ItemMetadata,
     ) {
         let db = app_handle.state::<Database>();
         let conn = match db.conn.lock() {
             Ok(c) => c,
             Err(_) => return,
         };
-        // Update with new metadata and mark as complete
+        let feed_item_id = item.id.as_str();
+
+        // Expiry follows the publish date, which flat listings often lack
+        let expires_at = load_settings(&conn).ok().and_then(|settings| {
+            expires_at(fetcher, item.content_kind.as_deref(), metadata.published_at.as_deref(), &settings)
+        });
+
+        // Update with new metadata, mark as complete and clear any earlier failures
         // Also update title if provided (for Patreon where initial title was from URL slug)
         let _ = conn.execute(
             "UPDATE feed_items SET
@@ -335,10 +585,79 @@ impl MetadataWorker {
                 duration = COALESCE(?, duration),
                 thumbnail_url = COALESCE(?, thumbnail_url),
                 title = COALESCE(?, title),
-                metadata_complete = 1
+                game = COALESCE(?, game),
+                expires_at = COALESCE(?, expires_at),
+                locked = COALESCE(?, locked),
+                required_tier = COALESCE(?, required_tier),
+                metadata_complete = 1,
+                metadata_attempts = 0,
+                metadata_last_error = NULL,
+                metadata_next_attempt_at = NULL,
+                metadata_unavailable = 0,
+                metadata_fetched_at = ?
              WHERE id = ?",
-            rusqlite::params![published_at, duration, thumbnail, title, feed_item_id],
+            rusqlite::params![
+                metadata.published_at,
+                metadata.duration,
+                metadata.thumbnail,
+                metadata.title,
+                metadata.game,
+                expires_at,
+                metadata.access.as_ref().map(|access| access.locked),
+                metadata.access.as_ref().and_then(|access| access.required_tier.clone()),
+                chrono::Utc::now().to_rfc3339(),
+                feed_item_id
+            ],
         );
+
+        if let Some(details) = &metadata.details {
+            Self::update_feed_item_details(&conn, feed_item_id, details);
+        }
+    }
+
+    /// Store description, counts, flags and chapters, replacing earlier values
+    fn update_feed_item_details(conn: &rusqlite::Connection, feed_item_id: &str, details: &VideoDetails) {
+        let to_json = |values: &Vec<String>| serde_json::to_string(values).ok();
+
+        let _ = conn.execute(
+            "UPDATE feed_items SET
+                description = COALESCE(?, description),
+                tags = ?,
+                categories = ?,
+                view_count = COALESCE(?, view_count),
+                like_count = COALESCE(?, like_count),
+                resolutions = ?,
+                is_live = ?,
+                is_short = ?
+             WHERE id = ?",
+            rusqlite::params![
+                details.description,
+                to_json(&details.tags),
+                to_json(&details.categories),
+                details.view_count,
+                details.like_count,
+                to_json(&details.resolutions),
+                details.is_live as i32,
+                details.is_short as i32,
+                feed_item_id
+            ],
+        );
+
+        let _ = conn.execute("DELETE FROM feed_item_chapters WHERE feed_item_id = ?", [feed_item_id]);
+        for (position, chapter) in details.chapters.iter().enumerate() {
+            let _ = conn.execute(
+                "INSERT INTO feed_item_chapters (id, feed_item_id, position, title, start_time, end_time)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                (
+                    Uuid::new_v4().to_string(),
+                    feed_item_id,
+                    position as i64,
+                    &chapter.title,
+                    chapter.start_time,
+                    chapter.end_time,
+                ),
+            );
+        }
     }
 
     fn emit_error(app_handle: &AppHandle, feed_item_id: &str, message: &str) {
@@ -352,21 +671,29 @@ impl MetadataWorker {
         );
     }
 
-    pub fn fetch_immediate(&self, feed_item_ids: Vec<String>) -> Result<(), String> {
-        self.sender
-            .try_send(MetadataCommand::FetchImmediate(feed_item_ids))
-            .map_err(|e| format!("Failed to queue metadata fetch: {}", e))
+    /// Queue feed items for metadata at the given priority
+    pub fn enqueue(&self, feed_item_ids: Vec<String>, priority: MetadataPriority) -> Result<(), String> {
+        self.queue
+            .lock()
+            .map_err(|e| e.to_string())?
+            .push(feed_item_ids, priority);
+        self.wake.notify_one();
+        Ok(())
+    }
+
+    pub fn queue_status(&self) -> Result<MetadataQueueStatus, String> {
+        Ok(self.queue.lock().map_err(|e| e.to_string())?.status())
     }
 
+    /// Pause background fetching; visible and requested items are still fetched
     pub fn pause(&self) -> Result<(), String> {
-        self.sender
-            .try_send(MetadataCommand::Pause)
-            .map_err(|e| format!("Failed to pause metadata worker: {}", e))
+        self.queue.lock().map_err(|e| e.to_string())?.paused = true;
+        Ok(())
     }
 
     pub fn resume(&self) -> Result<(), String> {
-        self.sender
-            .try_send(MetadataCommand::Resume)
-            .map_err(|e| format!("Failed to resume metadata worker: {}", e))
+        self.queue.lock().map_err(|e| e.to_string())?.paused = false;
+        self.wake.notify_one();
+        Ok(())
     }
 }
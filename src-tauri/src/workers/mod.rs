@@ -1,7 +1,10 @@
+pub mod backup_scheduler;
 pub mod download_manager;
+pub mod metadata_queue;
 pub mod metadata_worker;
 pub mod sync_manager;
 
+pub use backup_scheduler::BackupScheduler;
 pub use download_manager::DownloadManager;
 pub use metadata_worker::MetadataWorker;
 pub use sync_manager::SyncManager;
@@ -1,6 +1,9 @@
+use crate::commands::credentials::get_cookie_path;
 use crate::commands::notifications::notify_sync_completed;
+use crate::commands::settings::load_settings;
 use crate::db::Database;
-use crate::services::{get_ytdlp_path, PatreonFetcher, YouTubeFetcher};
+use crate::services::platform::{expires_at, require_cookies, ListedItem};
+use crate::services::{get_fetcher, get_ytdlp_path, PlatformFetcher};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc;
 
@@ -19,6 +22,8 @@ pub struct SyncManager {
 #[allow(dead_code)]
 pub enum SyncCommand {
     SyncSource(String),
+    /// Sync a source with the full listing, e.g. to re-check items already in the feed
+    SyncSourceFull(String),
     SyncAllForCreator(String),
     SyncAll,
     Stop,
@@ -43,7 +48,10 @@ impl SyncManager {
                 match rx.recv().await {
                     Some(cmd) => match cmd {
                         SyncCommand::SyncSource(source_id) => {
-                            Self::do_sync_source(&app_handle, &source_id).await;
+                            Self::do_sync_source(&app_handle, &source_id, false).await;
+                        }
+                        SyncCommand::SyncSourceFull(source_id) => {
+                            Self::do_sync_source(&app_handle, &source_id, true).await;
                         }
                         SyncCommand::SyncAllForCreator(creator_id) => {
                             Self::do_sync_creator_sources(&app_handle, &creator_id).await;
@@ -104,7 +112,7 @@ impl SyncManager {
         let source_ids = Self::get_all_source_ids(app_handle);
 
         for source_id in source_ids {
-            Self::do_sync_source(app_handle, &source_id).await;
+            Self::do_sync_source(app_handle, &source_id, false).await;
         }
     }
 
@@ -113,11 +121,11 @@ impl SyncManager {
         let source_ids = Self::get_creator_source_ids(app_handle, creator_id);
 
         for source_id in source_ids {
-            Self::do_sync_source(app_handle, &source_id).await;
+            Self::do_sync_source(app_handle, &source_id, false).await;
         }
     }
 
-    async fn do_sync_source(app_handle: &AppHandle, source_id: &str) {
+    async fn do_sync_source(app_handle: &AppHandle, source_id: &str, full_listing: bool) {
         // Emit sync started event
         let _ = app_handle.emit(
             "sync_started",
@@ -129,7 +137,7 @@ impl SyncManager {
             },
         );
 
-        // Get source info including credential_id for Patreon
+        // Get source info including credential_id for platforms that need cookies
         let source_info = {
             let db = app_handle.state::<Database>();
             let conn = match db.conn.lock() {
@@ -164,13 +172,12 @@ impl SyncManager {
         // Use channel_name or fall back to channel_url for notification
         let source_display_name = channel_name.unwrap_or_else(|| channel_url.clone());
 
-        // Fetch based on platform
-        let result = match platform.as_str() {
-            "youtube" => Self::fetch_youtube(app_handle, source_id, &channel_url).await,
-            "patreon" => {
-                Self::fetch_patreon(app_handle, source_id, &channel_url, credential_id.as_deref()).await
+        let result = match get_fetcher(&platform) {
+            Ok(fetcher) => {
+                Self::fetch_source(app_handle, source_id, fetcher, &channel_url, credential_id.as_deref(), full_listing)
+                    .await
             }
-            _ => Err("Unknown platform".to_string()),
+            Err(e) => Err(e),
         };
 
         match result {
@@ -180,7 +187,7 @@ impl SyncManager {
                 if let Ok(conn) = db.conn.lock() {
                     let now = chrono::Utc::now().to_rfc3339();
                     let _ = conn.execute(
-                        "UPDATE sources SET last_synced_at = ?, status = 'validated' WHERE id = ?",
+                        "UPDATE sources SET last_synced_at = ?, status = 'validated', status_reason = NULL WHERE id = ?",
                         (&now, source_id),
                     );
                 }
@@ -202,150 +209,171 @@ impl SyncManager {
                 Self::emit_error(app_handle, source_id, &error);
 
                 // Update source status to error
-                Self::update_source_status_error(app_handle, source_id);
+                Self::update_source_status_error(app_handle, source_id, &error);
             }
         }
     }
 
-    fn update_source_status_error(app_handle: &AppHandle, source_id: &str) {
+    fn update_source_status_error(app_handle: &AppHandle, source_id: &str, reason: &str) {
         let db = app_handle.state::<Database>();
         let conn = match db.conn.lock() {
             Ok(c) => c,
             Err(_) => return,
         };
         let _ = conn.execute(
-            "UPDATE sources SET status = 'error' WHERE id = ?",
-            [source_id],
+            "UPDATE sources SET status = 'error', status_reason = ? WHERE id = ?",
+            [reason, source_id],
         );
     }
 
-    async fn fetch_youtube(
+    async fn fetch_source(
         app_handle: &AppHandle,
         source_id: &str,
+        fetcher: &'static dyn PlatformFetcher,
         channel_url: &str,
+        credential_id: Option<&str>,
+        full_listing: bool,
     ) -> Result<i32, String> {
         // Get yt-dlp path
         let ytdlp_path = get_ytdlp_path(app_handle)?;
 
+        // Get cookie path from the source's credential or the platform default
+        let cookie_path = {
+            let db = app_handle.state::<Database>();
+            let conn = db.conn.lock().map_err(|e| e.to_string())?;
+            get_cookie_path(&conn, fetcher.platform(), credential_id)
+        };
+        let cookie_path = require_cookies(fetcher, cookie_path)?;
+
+        // Try the platform's quick listing first; it only covers the latest uploads, so
+        // it's enough when at least one of them is already known (nothing was missed).
+        // New items it can't tell the kind of need the full listing, or the kind
+        // filter would hide them. Re-checks of older items skip it, as it wouldn't list them.
+        let quick_url = channel_url.to_string();
+        let recent = if full_listing {
+            None
+        } else {
+            tokio::task::spawn_blocking(move || fetcher.list_recent(&quick_url))
+                .await
+                .map_err(|e| format!("Task panicked: {}", e))?
+        };
+
+        if let Some(recent) = recent.filter(|items| !items.is_empty()) {
+            let db = app_handle.state::<Database>();
+            let conn = db.conn.lock().map_err(|e| e.to_string())?;
+            let has_known = recent.iter().any(|item| Self::is_known(&conn, source_id, item));
+            let has_unclassified = recent
+                .iter()
+                .any(|item| item.content_kind.is_none() && !Self::is_known(&conn, source_id, item));
+            if has_known && !has_unclassified {
+                return Self::insert_items(&conn, source_id, fetcher, recent);
+            }
+        }
+
         // Run yt-dlp in a blocking task to not block the async runtime
         let channel_url = channel_url.to_string();
-        let videos = tokio::task::spawn_blocking(move || {
-            YouTubeFetcher::fetch_channel(&channel_url, &ytdlp_path)
+        let items = tokio::task::spawn_blocking(move || {
+            fetcher.list(&channel_url, cookie_path.as_deref(), &ytdlp_path)
         })
         .await
         .map_err(|e| format!("Task panicked: {}", e))??;
 
-        if videos.is_empty() {
+        if items.is_empty() {
             return Ok(0);
         }
 
         // Insert feed items into database
         let db = app_handle.state::<Database>();
         let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        Self::insert_items(&conn, source_id, fetcher, items)
+    }
 
-        let now = chrono::Utc::now().to_rfc3339();
-        let mut inserted = 0;
-
-        for video in videos {
-            let id = uuid::Uuid::new_v4().to_string();
-            let published_at = video
-                .upload_date
-                .as_ref()
-                .and_then(|d| YouTubeFetcher::parse_upload_date(d));
-            let duration = video.duration.map(|d| d as i64);
-
-            let result = conn.execute(
-                "INSERT OR IGNORE INTO feed_items (id, source_id, external_id, title, thumbnail_url, published_at, duration, download_status, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, 'not_downloaded', ?)",
-                (&id, source_id, &video.id, &video.title, &video.thumbnail, &published_at, &duration, &now),
-            );
-
-            if let Ok(rows) = result {
-                inserted += rows as i32;
-            }
-        }
-
-        Ok(inserted)
+    /// Whether a listed item was synced for the source before
+    fn is_known(conn: &rusqlite::Connection, source_id: &str, item: &ListedItem) -> bool {
+        conn.query_row(
+            "SELECT 1 FROM feed_items WHERE source_id = ? AND external_id = ?",
+            (source_id, &item.external_id),
+            |_| Ok(()),
+        )
+        .is_ok()
     }
 
-    async fn fetch_patreon(
-        app_handle: &AppHandle,
+    /// Insert listed items that aren't in the feed yet, returning how many were new
+    fn insert_items(
+        conn: &rusqlite::Connection,
         source_id: &str,
-        creator_url: &str,
-        credential_id: Option<&str>,
+        fetcher: &'static dyn PlatformFetcher,
+        items: Vec<ListedItem>,
     ) -> Result<i32, String> {
-        // Get yt-dlp path
-        let ytdlp_path = get_ytdlp_path(app_handle)?;
-
-        // Get cookie path from credential
-        let cookie_path = match credential_id {
-            Some(cred_id) => {
-                let db = app_handle.state::<Database>();
-                let conn = db.conn.lock().map_err(|e| e.to_string())?;
-                conn.query_row(
-                    "SELECT cookie_path FROM credentials WHERE id = ?",
-                    [cred_id],
-                    |row| row.get::<_, String>(0),
-                )
-                .map_err(|_| "Credential not found".to_string())?
-            }
-            None => {
-                // Try to get default credential for patreon
-                let db = app_handle.state::<Database>();
-                let conn = db.conn.lock().map_err(|e| e.to_string())?;
-                match conn.query_row(
-                    "SELECT cookie_path FROM credentials WHERE platform = 'patreon' AND is_default = 1",
-                    [],
-                    |row| row.get::<_, String>(0),
-                ) {
-                    Ok(path) => path,
-                    Err(_) => {
-                        return Err(
-                            "No credential configured for this Patreon source. Please add a cookie file in Settings."
-                                .to_string(),
-                        )
-                    }
-                }
-            }
-        };
-
-        // Run yt-dlp in a blocking task to not block the async runtime
-        let creator_url = creator_url.to_string();
-        let posts = tokio::task::spawn_blocking(move || {
-            PatreonFetcher::fetch_creator(&creator_url, &cookie_path, &ytdlp_path)
-        })
-        .await
-        .map_err(|e| format!("Task panicked: {}", e))??;
-
-        if posts.is_empty() {
-            return Ok(0);
-        }
-
-        // Insert feed items into database
-        let db = app_handle.state::<Database>();
-        let conn = db.conn.lock().map_err(|e| e.to_string())?;
+        let settings = load_settings(conn).map_err(|e| e.to_string())?;
 
         let now = chrono::Utc::now().to_rfc3339();
         let mut inserted = 0;
 
-        for post in posts {
+        for item in items {
             let id = uuid::Uuid::new_v4().to_string();
-            let published_at = post
-                .upload_date
-                .as_ref()
-                .and_then(|d| PatreonFetcher::parse_upload_date(d));
-            let duration = post.duration.map(|d| d as i64);
-            // If title is from URL slug fallback, mark as incomplete so metadata worker fetches real title
-            let metadata_complete = !post.title_is_fallback;
+            let expires_at = expires_at(fetcher, item.content_kind.as_deref(), item.published_at.as_deref(), &settings);
+            let media = if item.media.is_empty() { None } else { serde_json::to_string(&item.media).ok() };
+            let locked = item.access.as_ref().is_some_and(|access| access.locked);
+            let required_tier = item.access.as_ref().and_then(|access| access.required_tier.clone());
 
             let result = conn.execute(
-                "INSERT OR IGNORE INTO feed_items (id, source_id, external_id, title, thumbnail_url, published_at, duration, download_status, metadata_complete, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, 'not_downloaded', ?, ?)",
-                (&id, source_id, &post.id, &post.title, &post.thumbnail, &published_at, &duration, &metadata_complete, &now),
+                "INSERT OR IGNORE INTO feed_items (id, source_id, external_id, webpage_url, title, thumbnail_url, published_at, duration, description, content_kind, game, expires_at, media, locked, required_tier, download_status, metadata_complete, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'not_downloaded', ?, ?)",
+                rusqlite::params![
+                    &id,
+                    source_id,
+                    &item.external_id,
+                    &item.url,
+                    &item.title,
+                    &item.thumbnail_url,
+                    &item.published_at,
+                    &item.duration,
+                    &item.description,
+                    &item.content_kind,
+                    &item.game,
+                    &expires_at,
+                    &media,
+                    &locked,
+                    &required_tier,
+                    &item.metadata_complete,
+                    &now,
+                ],
             );
 
-            if let Ok(rows) = result {
-                inserted += rows as i32;
+            match result {
+                Ok(0) => {
+                    // Items synced before their source had a kind pick it up now
+                    if item.content_kind.is_some() {
+                        let _ = conn.execute(
+                            "UPDATE feed_items SET content_kind = ?
+                             WHERE source_id = ? AND external_id = ? AND content_kind IS NULL",
+                            (&item.content_kind, source_id, &item.external_id),
+                        );
+                    }
+
+                    // Posts can gain or lose files after they're published
+                    if media.is_some() {
+                        let _ = conn.execute(
+                            "UPDATE feed_items SET media = ? WHERE source_id = ? AND external_id = ?",
+                            (&media, source_id, &item.external_id),
+                        );
+                    }
+
+                    // A post unlocks when the pledge changes; its details weren't visible before
+                    if item.access.is_some() {
+                        let _ = conn.execute(
+                            "UPDATE feed_items SET
+                                metadata_complete = CASE WHEN locked = 1 AND ?1 = 0 THEN ?2 ELSE metadata_complete END,
+                                locked = ?1,
+                                required_tier = ?3
+                             WHERE source_id = ?4 AND external_id = ?5",
+                            rusqlite::params![locked, item.metadata_complete, required_tier, source_id, item.external_id],
+                        );
+                    }
+                }
+                Ok(rows) => inserted += rows as i32,
+                Err(_) => {}
             }
         }
 
@@ -368,6 +396,11 @@ impl SyncManager {
         let _ = self.tx.try_send(SyncCommand::SyncSource(source_id));
     }
 
+    /// Sync a source without the quick listing, so every item is fetched again
+    pub fn sync_source_full(&self, source_id: String) {
+        let _ = self.tx.try_send(SyncCommand::SyncSourceFull(source_id));
+    }
+
     pub fn sync_creator(&self, creator_id: String) {
         let _ = self.tx.try_send(SyncCommand::SyncAllForCreator(creator_id));
     }
@@ -0,0 +1,120 @@
+import { useState } from "react";
+import { ArchiveRestore, Loader2, Save } from "lucide-react";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { api } from "@/lib/tauri";
+import type { BackupManifest } from "@/types/backup";
+
+interface BackupActionsProps {
+  includeThumbnails: boolean;
+}
+
+export function BackupActions({ includeThumbnails }: BackupActionsProps) {
+  const [backingUp, setBackingUp] = useState(false);
+  const [restoring, setRestoring] = useState(false);
+  const [pendingRestore, setPendingRestore] = useState<{ path: string; manifest: BackupManifest } | null>(null);
+
+  const handleBackup = async () => {
+    const date = new Date().toISOString().slice(0, 10);
+    const path = await save({
+      defaultPath: `n3ms-backup-${date}.zip`,
+      filters: [{ name: "Backup", extensions: ["zip"] }],
+    });
+    if (!path) return;
+
+    try {
+      setBackingUp(true);
+      await api.backup.create(path, includeThumbnails);
+      toast.success("Backup created");
+    } catch (err) {
+      toast.error(`Backup failed: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setBackingUp(false);
+    }
+  };
+
+  const handleSelectRestore = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "Backup", extensions: ["zip"] }],
+    });
+    if (!selected || typeof selected !== "string") return;
+
+    try {
+      const manifest = await api.backup.inspect(selected);
+      setPendingRestore({ path: selected, manifest });
+    } catch (err) {
+      toast.error(`Can't restore this backup: ${err instanceof Error ? err.message : String(err)}`);
+    }
+  };
+
+  const handleRestore = async () => {
+    if (!pendingRestore) return;
+
+    try {
+      setRestoring(true);
+      await api.backup.restore(pendingRestore.path);
+      toast.success("Backup restored");
+      // Everything on screen came from the old database
+      window.location.reload();
+    } catch (err) {
+      toast.error(`Restore failed: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setRestoring(false);
+      setPendingRestore(null);
+    }
+  };
+
+  return (
+    <>
+      <div className="flex gap-2">
+        <Button variant="outline" onClick={handleBackup} disabled={backingUp}>
+          {backingUp ? <Loader2 className="h-4 w-4 mr-2 animate-spin" /> : <Save className="h-4 w-4 mr-2" />}
+          Back Up Now
+        </Button>
+        <Button variant="outline" onClick={handleSelectRestore} disabled={restoring}>
+          <ArchiveRestore className="h-4 w-4 mr-2" />
+          Restore...
+        </Button>
+      </div>
+
+      <AlertDialog open={!!pendingRestore} onOpenChange={(open) => !open && setPendingRestore(null)}>
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Restore Backup</AlertDialogTitle>
+            <AlertDialogDescription>
+              {pendingRestore && (
+                <>
+                  This backup was made on {new Date(pendingRestore.manifest.created_at).toLocaleString()} by
+                  version {pendingRestore.manifest.app_version}. Restoring replaces all creators, sources,
+                  feeds, warehouse records and settings with the ones in the backup.
+                </>
+              )}
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <AlertDialogFooter>
+            <AlertDialogCancel disabled={restoring}>Cancel</AlertDialogCancel>
+            <AlertDialogAction
+              onClick={handleRestore}
+              disabled={restoring}
+              className="bg-destructive hover:bg-destructive/90"
+            >
+              {restoring ? "Restoring..." : "Restore"}
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+    </>
+  );
+}
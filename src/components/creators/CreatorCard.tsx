@@ -1,4 +1,5 @@
 import { Link } from "react-router-dom";
+import { convertFileSrc } from "@tauri-apps/api/core";
 import { Card } from "@/components/ui/card";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { Video, ChevronRight } from "lucide-react";
@@ -40,7 +41,7 @@ export function CreatorCard({ creator, index = 0 }: CreatorCardProps) {
           <div className="relative">
             <div className="absolute inset-0 bg-glow/20 blur-2xl rounded-full scale-75 opacity-0 group-hover:opacity-100 group-hover:scale-100 transition-all duration-500" />
             <Avatar className="h-24 w-24 border-2 border-border/50 group-hover:border-glow/30 transition-colors relative">
-              <AvatarImage src={creator.photo_path || undefined} className="object-cover" />
+              <AvatarImage src={creator.photo_path ? convertFileSrc(creator.photo_path) : undefined} className="object-cover" />
               <AvatarFallback className="text-2xl font-display font-bold bg-gradient-to-br from-surface to-surface-elevated text-muted-foreground">
                 {initials}
               </AvatarFallback>
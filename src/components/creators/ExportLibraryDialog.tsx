@@ -0,0 +1,131 @@
+import { useState } from "react";
+import { Download, Loader2 } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+  DialogFooter,
+  DialogDescription,
+} from "@/components/ui/dialog";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { api } from "@/lib/tauri";
+import type { ExportFormat } from "@/types/import";
+
+const FORMAT_LABELS: Record<ExportFormat, string> = {
+  opml: "OPML",
+  json: "JSON",
+  csv: "CSV",
+};
+
+export function ExportLibraryDialog() {
+  const [dialogOpen, setDialogOpen] = useState(false);
+  const [format, setFormat] = useState<ExportFormat>("json");
+  const [includeFeedItems, setIncludeFeedItems] = useState(false);
+  const [includeWarehouseItems, setIncludeWarehouseItems] = useState(false);
+  const [loading, setLoading] = useState(false);
+
+  // OPML only holds subscriptions
+  const canIncludeItems = format !== "opml";
+
+  const handleExport = async () => {
+    const path = await save({
+      defaultPath: `n3ms-library.${format}`,
+      filters: [{ name: FORMAT_LABELS[format], extensions: [format] }],
+    });
+    if (!path) return;
+
+    try {
+      setLoading(true);
+      const written = await api.creators.exportLibrary({
+        path,
+        format,
+        include_feed_items: canIncludeItems && includeFeedItems,
+        include_warehouse_items: canIncludeItems && includeWarehouseItems,
+      });
+      toast.success(`Exported ${written.length} file${written.length === 1 ? "" : "s"}`);
+      setDialogOpen(false);
+    } catch (err) {
+      toast.error(`Export failed: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <Dialog open={dialogOpen} onOpenChange={setDialogOpen}>
+      <DialogTrigger asChild>
+        <Button variant="outline">
+          <Download className="h-4 w-4 mr-2" />
+          Export
+        </Button>
+      </DialogTrigger>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Export Library</DialogTitle>
+          <DialogDescription>
+            Creators, sources and credential names. Cookie files are never exported.
+          </DialogDescription>
+        </DialogHeader>
+        <div className="py-4 space-y-4">
+          <div className="space-y-2">
+            <Label htmlFor="format">Format</Label>
+            <Select value={format} onValueChange={(v) => setFormat(v as ExportFormat)}>
+              <SelectTrigger id="format">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="json">JSON</SelectItem>
+                <SelectItem value="csv">CSV (one file per list)</SelectItem>
+                <SelectItem value="opml">OPML (subscriptions only)</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="includeFeedItems"
+              checked={canIncludeItems && includeFeedItems}
+              disabled={!canIncludeItems}
+              onCheckedChange={(checked) => setIncludeFeedItems(checked === true)}
+            />
+            <Label htmlFor="includeFeedItems" className="text-sm font-normal">
+              Include feed items
+            </Label>
+          </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="includeWarehouseItems"
+              checked={canIncludeItems && includeWarehouseItems}
+              disabled={!canIncludeItems}
+              onCheckedChange={(checked) => setIncludeWarehouseItems(checked === true)}
+            />
+            <Label htmlFor="includeWarehouseItems" className="text-sm font-normal">
+              Include warehouse items
+            </Label>
+          </div>
+        </div>
+        <DialogFooter>
+          <Button type="button" variant="ghost" onClick={() => setDialogOpen(false)}>
+            Cancel
+          </Button>
+          <Button onClick={handleExport} disabled={loading}>
+            {loading && <Loader2 className="h-4 w-4 mr-2 animate-spin" />}
+            Export
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,159 @@
+import { useState } from "react";
+import { FileUp, FolderOpen, Loader2 } from "lucide-react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+  DialogFooter,
+  DialogDescription,
+} from "@/components/ui/dialog";
+import { Input } from "@/components/ui/input";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { api } from "@/lib/tauri";
+import type { ImportEntry, ImportFormat, ImportReport } from "@/types/import";
+
+interface ImportSubscriptionsDialogProps {
+  onImported: () => void;
+}
+
+const FORMAT_LABELS: Record<ImportFormat, string> = {
+  opml: "OPML",
+  takeout_csv: "YouTube Takeout CSV",
+  url_list: "URL list",
+};
+
+function getActionBadge(entry: ImportEntry) {
+  switch (entry.action) {
+    case "create":
+      return entry.existing_creator_id ? (
+        <Badge variant="secondary">Add to creator</Badge>
+      ) : (
+        <Badge variant="default" className="bg-green-600">New</Badge>
+      );
+    case "duplicate":
+      return <Badge variant="outline">Duplicate</Badge>;
+    case "invalid":
+      return <Badge variant="destructive" title={entry.message ?? undefined}>Invalid</Badge>;
+  }
+}
+
+export function ImportSubscriptionsDialog({ onImported }: ImportSubscriptionsDialogProps) {
+  const [dialogOpen, setDialogOpen] = useState(false);
+  const [filePath, setFilePath] = useState("");
+  const [report, setReport] = useState<ImportReport | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  const handleSelectFile = async () => {
+    try {
+      const selected = await open({
+        multiple: false,
+        filters: [
+          { name: "Subscriptions", extensions: ["opml", "xml", "csv", "txt"] },
+          { name: "All files", extensions: ["*"] },
+        ],
+      });
+      if (!selected || typeof selected !== "string") return;
+
+      setFilePath(selected);
+      setReport(null);
+      setLoading(true);
+      setReport(await api.creators.importSubscriptions(selected, true));
+    } catch (err) {
+      toast.error(`Failed to read subscriptions: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleImport = async () => {
+    if (!filePath) return;
+
+    try {
+      setLoading(true);
+      const result = await api.creators.importSubscriptions(filePath, false);
+      toast.success(
+        `Imported ${result.sources_created} source${result.sources_created === 1 ? "" : "s"} and ${result.creators_created} new creator${result.creators_created === 1 ? "" : "s"}`
+      );
+      onImported();
+      handleOpenChange(false);
+    } catch (err) {
+      toast.error(`Import failed: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleOpenChange = (open: boolean) => {
+    setDialogOpen(open);
+    if (!open) {
+      setFilePath("");
+      setReport(null);
+    }
+  };
+
+  return (
+    <Dialog open={dialogOpen} onOpenChange={handleOpenChange}>
+      <DialogTrigger asChild>
+        <Button variant="outline">
+          <FileUp className="h-4 w-4 mr-2" />
+          Import
+        </Button>
+      </DialogTrigger>
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Import Subscriptions</DialogTitle>
+          <DialogDescription>
+            OPML, a YouTube Takeout subscriptions.csv, or a text file with one channel URL per line
+          </DialogDescription>
+        </DialogHeader>
+        <div className="py-4 space-y-4">
+          <div className="flex gap-2">
+            <Input value={filePath} readOnly placeholder="Select a file..." className="flex-1" />
+            <Button type="button" variant="outline" onClick={handleSelectFile} disabled={loading}>
+              <FolderOpen className="h-4 w-4" />
+            </Button>
+          </div>
+
+          {report && (
+            <>
+              <p className="text-sm text-muted-foreground">
+                {FORMAT_LABELS[report.format]}: {report.sources_created} new source
+                {report.sources_created === 1 ? "" : "s"}, {report.creators_created} new creator
+                {report.creators_created === 1 ? "" : "s"}, {report.duplicates} duplicate
+                {report.duplicates === 1 ? "" : "s"}, {report.invalid} invalid
+              </p>
+              <ScrollArea className="h-[300px] rounded-md border">
+                <div className="divide-y">
+                  {report.entries.map((entry, index) => (
+                    <div key={index} className="flex items-center gap-3 px-3 py-2 text-sm">
+                      {getActionBadge(entry)}
+                      <span className="font-medium truncate max-w-[180px]">{entry.name}</span>
+                      <span className="text-muted-foreground truncate flex-1" title={entry.channel_url}>
+                        {entry.channel_url}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              </ScrollArea>
+            </>
+          )}
+        </div>
+        <DialogFooter>
+          <Button type="button" variant="ghost" onClick={() => handleOpenChange(false)}>
+            Cancel
+          </Button>
+          <Button onClick={handleImport} disabled={!report || report.sources_created === 0 || loading}>
+            {loading && <Loader2 className="h-4 w-4 mr-2 animate-spin" />}
+            {report ? `Import ${report.sources_created} source${report.sources_created === 1 ? "" : "s"}` : "Import"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
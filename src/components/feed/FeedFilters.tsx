@@ -15,7 +15,9 @@ export type FeedItemStatus =
   | "not_downloaded"
   | "downloading"
   | "downloaded"
-  | "error";
+  | "error"
+  | "cancelled"
+  | "corrupt";
 
 interface FeedFiltersProps {
   sources: Source[];
@@ -23,6 +25,9 @@ interface FeedFiltersProps {
   onSourceChange: (id: string | null) => void;
   selectedStatus: string | null;
   onStatusChange: (status: string | null) => void;
+  contentKinds: string[];
+  selectedKind: string | null;
+  onKindChange: (kind: string | null) => void;
   searchQuery: string;
   onSearchChange: (query: string) => void;
   onClearFilters: () => void;
@@ -34,14 +39,32 @@ const STATUS_OPTIONS: { value: FeedItemStatus | "all"; label: string }[] = [
   { value: "downloading", label: "Downloading" },
   { value: "downloaded", label: "Downloaded" },
   { value: "error", label: "Error" },
+  { value: "cancelled", label: "Cancelled" },
+  { value: "corrupt", label: "Corrupt" },
 ];
 
+const CONTENT_KIND_LABELS: Record<string, string> = {
+  video: "Videos",
+  short: "Shorts",
+  stream: "Streams",
+  podcast: "Podcasts",
+  vod: "Past Broadcasts",
+  highlight: "Highlights",
+  clip: "Clips",
+  audio: "Audio",
+  image: "Images",
+  text: "Text Posts",
+};
+
 export function FeedFilters({
   sources,
   selectedSourceId,
   onSourceChange,
   selectedStatus,
   onStatusChange,
+  contentKinds,
+  selectedKind,
+  onKindChange,
   searchQuery,
   onSearchChange,
   onClearFilters,
@@ -49,6 +72,7 @@ export function FeedFilters({
   const hasActiveFilters =
     selectedSourceId !== null ||
     selectedStatus !== null ||
+    selectedKind !== null ||
     searchQuery.length > 0;
 
   return (
@@ -107,6 +131,31 @@ export function FeedFilters({
         </SelectContent>
       </Select>
 
+      {/* Content Kind Filter */}
+      {contentKinds.length > 1 && (
+        <Select
+          value={selectedKind ?? "all"}
+          onValueChange={(value) =>
+            onKindChange(value === "all" ? null : value)
+          }
+        >
+          <SelectTrigger className={cn(
+            "w-[160px] bg-surface border-border/50",
+            selectedKind && "border-glow/30 text-glow"
+          )}>
+            <SelectValue placeholder="All Kinds" />
+          </SelectTrigger>
+          <SelectContent className="glass border-border/50">
+            <SelectItem value="all">All Kinds</SelectItem>
+            {contentKinds.map((kind) => (
+              <SelectItem key={kind} value={kind}>
+                {CONTENT_KIND_LABELS[kind] ?? kind}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      )}
+
       {/* Search Input */}
       <div className="relative flex-1 min-w-[180px] max-w-[280px]">
         <Search className="absolute left-3 top-1/2 -translate-y-1/2 h-4 w-4 text-muted-foreground pointer-events-none" />
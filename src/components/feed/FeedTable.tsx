@@ -1,6 +1,6 @@
 import { useRef, useMemo, useEffect } from "react";
 import { useVirtualizer } from "@tanstack/react-virtual";
-import { CheckCircle2, Circle, Loader2, XCircle, ImageOff, Clock } from "lucide-react";
+import { CheckCircle2, Circle, Loader2, XCircle, Ban, FileWarning, ImageOff, Clock, Paperclip, Lock } from "lucide-react";
 import { Checkbox } from "@/components/ui/checkbox";
 import { Badge } from "@/components/ui/badge";
 import { cn } from "@/lib/utils";
@@ -90,6 +90,18 @@ function getStatusIcon(status: FeedItem["download_status"], progress?: DownloadP
           <XCircle className="h-5 w-5 text-destructive" />
         </span>
       );
+    case "cancelled":
+      return (
+        <span title="Cancelled" className="flex items-center justify-center w-6 h-6">
+          <Ban className="h-5 w-5 text-muted-foreground" />
+        </span>
+      );
+    case "corrupt":
+      return (
+        <span title="Corrupt download" className="flex items-center justify-center w-6 h-6">
+          <FileWarning className="h-5 w-5 text-destructive" />
+        </span>
+      );
   }
 }
 
@@ -107,20 +119,44 @@ function getPlatformBadge(platform: Source["platform"]) {
           Patreon
         </Badge>
       );
+    case "twitch":
+      return (
+        <Badge variant="outline" className="border-purple-500 text-purple-500 text-xs px-2 py-0">
+          Twitch
+        </Badge>
+      );
+    case "rss":
+      return (
+        <Badge variant="outline" className="border-amber-500 text-amber-500 text-xs px-2 py-0">
+          RSS
+        </Badge>
+      );
+    case "generic":
+      return <Badge variant="outline" className="text-xs px-2 py-0">yt-dlp</Badge>;
     default:
       return <Badge variant="outline" className="text-xs px-2 py-0">Unknown</Badge>;
   }
 }
 
+function formatExpiry(expiresAt: string): string {
+  const days = Math.ceil((new Date(expiresAt).getTime() - Date.now()) / (1000 * 60 * 60 * 24));
+  if (days <= 0) return "Expired";
+  return days === 1 ? "Expires in 1 day" : `Expires in ${days} days`;
+}
+
 function formatRelativeDate(
   dateString: string | null,
   metadataComplete: boolean,
-  isLoadingMetadata: boolean
+  isLoadingMetadata: boolean,
+  metadataUnavailable = false
 ): { text: string; status: "loaded" | "loading" | "pending" } {
   if (!dateString) {
     if (metadataComplete) {
       return { text: "Unknown", status: "loaded" };
     }
+    if (metadataUnavailable) {
+      return { text: "Unavailable", status: "loaded" };
+    }
     return {
       text: isLoadingMetadata ? "Loading..." : "Pending",
       status: isLoadingMetadata ? "loading" : "pending",
@@ -272,7 +308,12 @@ export function FeedTable({
             const source = sourceMap.get(item.source_id);
             const isSelected = selectedIds.has(item.id);
             const isLoadingMetadata = loadingMetadataIds?.has(item.id) ?? false;
-            const dateInfo = formatRelativeDate(item.published_at, item.metadata_complete, isLoadingMetadata);
+            const dateInfo = formatRelativeDate(
+              item.published_at,
+              item.metadata_complete,
+              isLoadingMetadata,
+              item.metadata_unavailable
+            );
 
             return (
               <div
@@ -311,10 +352,48 @@ export function FeedTable({
                   <span className="truncate text-sm font-medium" title={item.title}>
                     {item.title}
                   </span>
+                  {item.is_live && (
+                    <Badge variant="outline" className="text-xs px-1.5 py-0 flex-shrink-0">Live</Badge>
+                  )}
+                  {item.is_short && (
+                    <Badge variant="outline" className="text-xs px-1.5 py-0 flex-shrink-0">Short</Badge>
+                  )}
+                  {item.locked && (
+                    <Badge
+                      variant="outline"
+                      className="text-xs px-1.5 py-0 flex-shrink-0 gap-1 border-orange-500 text-orange-500"
+                      title={item.required_tier ? `Requires ${item.required_tier}` : "Locked for this credential"}
+                    >
+                      <Lock className="h-3 w-3" />
+                      {item.required_tier ?? "Locked"}
+                    </Badge>
+                  )}
+                  {item.media.length > 0 && (
+                    <Badge
+                      variant="outline"
+                      className="text-xs px-1.5 py-0 flex-shrink-0 gap-1"
+                      title={item.media.map((m) => m.file_name ?? m.kind).join("\n")}
+                    >
+                      <Paperclip className="h-3 w-3" />
+                      {item.media.length}
+                    </Badge>
+                  )}
+                  {item.expires_at && item.download_status !== "downloaded" && (
+                    <Badge
+                      variant="outline"
+                      className="text-xs px-1.5 py-0 flex-shrink-0 border-amber-500 text-amber-500"
+                      title={`Expires ${new Date(item.expires_at).toLocaleString()}`}
+                    >
+                      {formatExpiry(item.expires_at)}
+                    </Badge>
+                  )}
                 </div>
 
                 {/* Published */}
-                <div className="w-28 flex-shrink-0 text-right pr-4">
+                <div
+                  className="w-28 flex-shrink-0 text-right pr-4"
+                  title={item.metadata_last_error ?? undefined}
+                >
                   {dateInfo.status === "loading" ? (
                     <span className="flex items-center justify-end gap-1.5 text-muted-foreground/50">
                       <Clock className="h-3 w-3 animate-pulse" />
@@ -1,8 +1,31 @@
+import { useEffect } from "react";
 import { Outlet } from "react-router-dom";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 import { Sidebar } from "./Sidebar";
 import { ScrollArea } from "@/components/ui/scroll-area";
+import type { HookRun } from "@/types/hook";
 
 export function AppShell() {
+  // Hooks and automatic backups run in the background; surface failures wherever the user is
+  useEffect(() => {
+    const unlistenPromises = [
+      listen<HookRun>("hook_failed", (event) => {
+        const run = event.payload;
+        toast.warning(`Hook "${run.hook_name}" failed`, {
+          description: run.exit_code !== null ? `Exited with code ${run.exit_code}` : run.output,
+        });
+      }),
+      listen<string>("backup_failed", (event) => {
+        toast.warning("Automatic backup failed", { description: event.payload });
+      }),
+    ];
+
+    return () => {
+      unlistenPromises.forEach((promise) => promise.then((unlisten) => unlisten()));
+    };
+  }, []);
+
   return (
     <div className="flex h-screen noise">
       <Sidebar />
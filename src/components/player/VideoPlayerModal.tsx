@@ -1,6 +1,6 @@
 "use client";
 
-import { useRef, useEffect } from "react";
+import { useRef, useEffect, useState } from "react";
 import { ExternalLink, FolderOpen } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 
@@ -15,7 +15,7 @@ import { BassBoostPanel } from "@/components/player/BassBoostPanel";
 import { useBassBoost, type BassBoostPresetName } from "@/hooks/useBassBoost";
 import { api } from "@/lib/tauri";
 import { formatDuration, formatFileSize } from "@/lib/utils";
-import type { WarehouseItem } from "@/types/warehouse-item";
+import type { WarehouseItem, SponsorSegment, WarehouseItemFile } from "@/types/warehouse-item";
 
 interface VideoPlayerModalProps {
   item: WarehouseItem | null;
@@ -31,9 +31,52 @@ export function VideoPlayerModal({
   const videoRef = useRef<HTMLVideoElement>(null);
   const bassBoost = useBassBoost();
 
+  const [sponsorSegments, setSponsorSegments] = useState<SponsorSegment[]>([]);
+  const [files, setFiles] = useState<WarehouseItemFile[]>([]);
+
   // Convert file path to a playable src URL
   const videoSrc = item?.file_path ? convertFileSrc(item.file_path) : null;
 
+  // Load SponsorBlock segments that were marked (not cut) in the file
+  useEffect(() => {
+    setSponsorSegments([]);
+    if (!item || !open) return;
+    api.warehouse
+      .getSponsorSegments(item.id)
+      .then(setSponsorSegments)
+      .catch((error) => console.error("Failed to load sponsor segments:", error));
+  }, [item, open]);
+
+  // Load the other files of items downloaded from a post with several files
+  useEffect(() => {
+    setFiles([]);
+    if (!item || !open) return;
+    api.warehouse
+      .getFiles(item.id)
+      .then(setFiles)
+      .catch((error) => console.error("Failed to load item files:", error));
+  }, [item, open]);
+
+  const handleOpenFile = async (filePath: string) => {
+    try {
+      await api.shell.openInDefaultApp(filePath);
+    } catch (error) {
+      console.error("Failed to open file:", error);
+    }
+  };
+
+  // Skip over marked segments during playback
+  const handleTimeUpdate = () => {
+    const video = videoRef.current;
+    if (!video) return;
+    const segment = sponsorSegments.find(
+      (s) => s.action_type === "skip" && video.currentTime >= s.start_time && video.currentTime < s.end_time - 0.25
+    );
+    if (segment) {
+      video.currentTime = segment.end_time;
+    }
+  };
+
   // Connect video to bass boost when video loads
   useEffect(() => {
     if (videoRef.current && open && videoSrc) {
@@ -83,6 +126,7 @@ export function VideoPlayerModal({
               src={videoSrc}
               controls
               autoPlay
+              onTimeUpdate={handleTimeUpdate}
               className="w-full h-full object-contain"
             />
           ) : (
@@ -130,6 +174,24 @@ export function VideoPlayerModal({
           </div>
         </div>
 
+        {/* Post files */}
+        {files.length > 1 && (
+          <div className="flex flex-wrap gap-2 pt-2">
+            {files.map((file) => (
+              <Button
+                key={file.id}
+                variant="ghost"
+                size="sm"
+                onClick={() => handleOpenFile(file.file_path)}
+                title={file.file_path}
+              >
+                <ExternalLink className="h-4 w-4 mr-2" />
+                {file.file_path.split(/[\\/]/).pop()} ({formatFileSize(file.file_size)})
+              </Button>
+            ))}
+          </div>
+        )}
+
         {/* Metadata */}
         {item && (
           <div className="flex flex-wrap gap-4 text-sm text-muted-foreground pt-2 border-t border-border">
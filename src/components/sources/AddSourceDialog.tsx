@@ -19,10 +19,22 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import { useCredentials } from "@/hooks/useCredentials";
+import type { SourcePlatform } from "@/types/source";
+
+// Radix Select items can't have an empty value
+const NO_CREDENTIAL = "none";
+
+const URL_PLACEHOLDERS: Record<SourcePlatform, string> = {
+  youtube: "https://youtube.com/@channel",
+  patreon: "https://patreon.com/creator",
+  twitch: "https://twitch.tv/channel",
+  rss: "https://example.com/podcast.xml",
+  generic: "https://vimeo.com/channel",
+};
 
 interface AddSourceDialogProps {
   onSubmit: (
-    platform: "youtube" | "patreon",
+    platform: SourcePlatform,
     channelUrl: string,
     credentialId?: string
   ) => Promise<void>;
@@ -30,22 +42,27 @@ interface AddSourceDialogProps {
 
 export function AddSourceDialog({ onSubmit }: AddSourceDialogProps) {
   const [open, setOpen] = useState(false);
-  const [platform, setPlatform] = useState<"youtube" | "patreon">("youtube");
+  const [platform, setPlatform] = useState<SourcePlatform>("youtube");
   const [channelUrl, setChannelUrl] = useState("");
   const [credentialId, setCredentialId] = useState<string>("");
   const [loading, setLoading] = useState(false);
 
-  const { credentials, loading: credentialsLoading } = useCredentials("patreon");
+  const { credentials: allCredentials, loading: credentialsLoading } = useCredentials();
+  // Twitch and generic sources may use any cookie file, or none
+  const credentials = platform === "patreon"
+    ? allCredentials.filter((c) => c.platform === "patreon")
+    : allCredentials;
 
   // Set default credential when credentials load or platform changes
   useEffect(() => {
-    if (platform === "patreon" && credentials.length > 0) {
-      const defaultCred = credentials.find((c) => c.is_default);
-      setCredentialId(defaultCred?.id || credentials[0].id);
+    const patreonCredentials = allCredentials.filter((c) => c.platform === "patreon");
+    if (platform === "patreon" && patreonCredentials.length > 0) {
+      const defaultCred = patreonCredentials.find((c) => c.is_default);
+      setCredentialId(defaultCred?.id || patreonCredentials[0].id);
     } else {
       setCredentialId("");
     }
-  }, [platform, credentials]);
+  }, [platform, allCredentials]);
 
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
@@ -56,7 +73,7 @@ export function AddSourceDialog({ onSubmit }: AddSourceDialogProps) {
       await onSubmit(
         platform,
         channelUrl.trim(),
-        platform === "patreon" ? credentialId || undefined : undefined
+        platform !== "youtube" && credentialId !== NO_CREDENTIAL ? credentialId || undefined : undefined
       );
       setChannelUrl("");
       setPlatform("youtube");
@@ -89,7 +106,7 @@ export function AddSourceDialog({ onSubmit }: AddSourceDialogProps) {
               <Label htmlFor="platform">Platform</Label>
               <Select
                 value={platform}
-                onValueChange={(v) => setPlatform(v as "youtube" | "patreon")}
+                onValueChange={(v) => setPlatform(v as SourcePlatform)}
               >
                 <SelectTrigger>
                   <SelectValue placeholder="Select platform" />
@@ -97,26 +114,54 @@ export function AddSourceDialog({ onSubmit }: AddSourceDialogProps) {
                 <SelectContent>
                   <SelectItem value="youtube">YouTube</SelectItem>
                   <SelectItem value="patreon">Patreon</SelectItem>
+                  <SelectItem value="twitch">Twitch</SelectItem>
+                  <SelectItem value="rss">RSS / Podcast feed</SelectItem>
+                  <SelectItem value="generic">Other (any yt-dlp URL)</SelectItem>
                 </SelectContent>
               </Select>
             </div>
 
             <div className="space-y-2">
               <Label htmlFor="channelUrl">
-                {platform === "youtube" ? "Channel URL" : "Creator URL"}
+                {platform === "patreon"
+                  ? "Creator URL"
+                  : platform === "youtube"
+                    ? "Channel, Tab or Playlist URL"
+                    : platform === "rss"
+                      ? "Feed URL"
+                      : "Channel URL"}
               </Label>
               <Input
                 id="channelUrl"
                 value={channelUrl}
                 onChange={(e) => setChannelUrl(e.target.value)}
-                placeholder={
-                  platform === "youtube"
-                    ? "https://youtube.com/@channel"
-                    : "https://patreon.com/creator"
-                }
+                placeholder={URL_PLACEHOLDERS[platform]}
               />
             </div>
 
+            {(platform === "twitch" || platform === "generic") && (
+              <div className="space-y-2">
+                <Label htmlFor="credential">Cookie Credential (optional)</Label>
+                <Select
+                  value={credentialId || NO_CREDENTIAL}
+                  onValueChange={setCredentialId}
+                  disabled={credentialsLoading}
+                >
+                  <SelectTrigger>
+                    <SelectValue placeholder="No credential" />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={NO_CREDENTIAL}>No credential</SelectItem>
+                    {credentials.map((cred) => (
+                      <SelectItem key={cred.id} value={cred.id}>
+                        {cred.label} ({cred.platform})
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+            )}
+
             {platform === "patreon" && (
               <div className="space-y-2">
                 <Label htmlFor="credential">Cookie Credential</Label>
@@ -1,4 +1,4 @@
-import { Trash2, ExternalLink, RefreshCw, Loader2 } from "lucide-react";
+import { Trash2, ExternalLink, RefreshCw, Loader2, ShieldCheck } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
 import {
@@ -14,39 +14,65 @@ import type { Source } from "@/types/source";
 interface SourcesTableProps {
   sources: Source[];
   syncingSourceIds: Set<string>;
+  validatingSourceIds: Set<string>;
   onDelete: (id: string) => Promise<void>;
   onSync: (id: string) => Promise<void>;
+  onValidate: (id: string) => Promise<void>;
 }
 
-function getStatusBadge(status: Source["status"], isSyncing: boolean) {
+function getStatusBadge(source: Source, isSyncing: boolean, isValidating: boolean) {
   if (isSyncing) {
     return <Badge variant="secondary"><Loader2 className="h-3 w-3 animate-spin mr-1" />Syncing</Badge>;
   }
+  if (isValidating) {
+    return <Badge variant="secondary"><Loader2 className="h-3 w-3 animate-spin mr-1" />Validating</Badge>;
+  }
 
-  switch (status) {
+  switch (source.status) {
     case "validated":
       return <Badge variant="default" className="bg-green-600">Validated</Badge>;
     case "pending":
       return <Badge variant="secondary">Pending</Badge>;
     case "error":
-      return <Badge variant="destructive">Error</Badge>;
+      return (
+        <Badge variant="destructive" title={source.status_reason ?? undefined}>
+          Error
+        </Badge>
+      );
   }
 }
 
+function formatSubscribers(count: number) {
+  return `${new Intl.NumberFormat(undefined, { notation: "compact" }).format(count)} subscribers`;
+}
+
 function getPlatformBadge(platform: Source["platform"]) {
   switch (platform) {
     case "youtube":
       return <Badge variant="outline" className="border-red-500 text-red-500">YouTube</Badge>;
     case "patreon":
       return <Badge variant="outline" className="border-orange-500 text-orange-500">Patreon</Badge>;
+    case "twitch":
+      return <Badge variant="outline" className="border-purple-500 text-purple-500">Twitch</Badge>;
+    case "rss":
+      return <Badge variant="outline" className="border-amber-500 text-amber-500">RSS</Badge>;
+    case "generic":
+      return <Badge variant="outline">yt-dlp</Badge>;
   }
 }
 
-export function SourcesTable({ sources, syncingSourceIds, onDelete, onSync }: SourcesTableProps) {
+export function SourcesTable({
+  sources,
+  syncingSourceIds,
+  validatingSourceIds,
+  onDelete,
+  onSync,
+  onValidate,
+}: SourcesTableProps) {
   if (sources.length === 0) {
     return (
       <div className="text-center py-8 text-muted-foreground">
-        No sources added yet. Add a YouTube or Patreon channel to get started.
+        No sources added yet. Add a YouTube, Patreon or other yt-dlp channel to get started.
       </div>
     );
   }
@@ -59,20 +85,35 @@ export function SourcesTable({ sources, syncingSourceIds, onDelete, onSync }: So
           <TableHead>Channel</TableHead>
           <TableHead>Status</TableHead>
           <TableHead>Last Synced</TableHead>
-          <TableHead className="w-[120px]">Actions</TableHead>
+          <TableHead className="w-[160px]">Actions</TableHead>
         </TableRow>
       </TableHeader>
       <TableBody>
         {sources.map((source) => {
           const isSyncing = syncingSourceIds.has(source.id);
+          const isValidating = validatingSourceIds.has(source.id);
           return (
             <TableRow key={source.id}>
               <TableCell>{getPlatformBadge(source.platform)}</TableCell>
               <TableCell>
                 <div className="flex items-center gap-2">
-                  <span className="truncate max-w-[300px]">
-                    {source.channel_name || source.channel_url}
-                  </span>
+                  {source.avatar_url && (
+                    <img src={source.avatar_url} alt="" className="h-6 w-6 rounded-full object-cover" />
+                  )}
+                  <div className="min-w-0">
+                    <div className="truncate max-w-[300px]">
+                      {source.channel_name || source.channel_url}
+                    </div>
+                    {source.status === "error" && source.status_reason ? (
+                      <div className="truncate max-w-[300px] text-xs text-destructive" title={source.status_reason}>
+                        {source.status_reason}
+                      </div>
+                    ) : source.subscriber_count != null && (
+                      <div className="text-xs text-muted-foreground">
+                        {formatSubscribers(source.subscriber_count)}
+                      </div>
+                    )}
+                  </div>
                   <a
                     href={source.channel_url}
                     target="_blank"
@@ -83,7 +124,7 @@ export function SourcesTable({ sources, syncingSourceIds, onDelete, onSync }: So
                   </a>
                 </div>
               </TableCell>
-              <TableCell>{getStatusBadge(source.status, isSyncing)}</TableCell>
+              <TableCell>{getStatusBadge(source, isSyncing, isValidating)}</TableCell>
               <TableCell className="text-muted-foreground">
                 {source.last_synced_at
                   ? new Date(source.last_synced_at).toLocaleDateString()
@@ -91,11 +132,20 @@ export function SourcesTable({ sources, syncingSourceIds, onDelete, onSync }: So
               </TableCell>
               <TableCell>
                 <div className="flex items-center gap-1">
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    title="Validate"
+                    onClick={() => onValidate(source.id)}
+                    disabled={isSyncing || isValidating}
+                  >
+                    <ShieldCheck className="h-4 w-4" />
+                  </Button>
                   <Button
                     variant="ghost"
                     size="icon"
                     onClick={() => onSync(source.id)}
-                    disabled={isSyncing}
+                    disabled={isSyncing || isValidating}
                   >
                     {isSyncing ? (
                       <Loader2 className="h-4 w-4 animate-spin" />
@@ -24,6 +24,7 @@ const PLATFORM_OPTIONS = [
   { value: "all", label: "All Platforms" },
   { value: "youtube", label: "YouTube" },
   { value: "patreon", label: "Patreon" },
+  { value: "twitch", label: "Twitch" },
   { value: "other", label: "Other" },
 ] as const;
 
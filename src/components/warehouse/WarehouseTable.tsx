@@ -58,6 +58,8 @@ function getPlatformBadge(platform: string | null, isManualImport: boolean) {
       return <Badge variant="outline" className="platform-youtube platform-badge-filled text-xs">YouTube</Badge>;
     case "patreon":
       return <Badge variant="outline" className="platform-patreon platform-badge-filled text-xs">Patreon</Badge>;
+    case "twitch":
+      return <Badge variant="outline" className="border-purple-500 text-purple-500 text-xs">Twitch</Badge>;
     default:
       return <Badge variant="outline" className="text-xs">Other</Badge>;
   }
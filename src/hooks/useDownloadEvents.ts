@@ -5,6 +5,7 @@ import type {
   DownloadProgressEvent,
   DownloadCompletedEvent,
   DownloadErrorEvent,
+  DownloadCancelledEvent,
 } from "@/types/download";
 
 interface UseDownloadEventsOptions {
@@ -12,10 +13,12 @@ interface UseDownloadEventsOptions {
   onDownloadProgress?: (event: DownloadProgressEvent) => void;
   onDownloadCompleted?: (event: DownloadCompletedEvent) => void;
   onDownloadError?: (event: DownloadErrorEvent) => void;
+  onDownloadCancelled?: (event: DownloadCancelledEvent) => void;
 }
 
 export function useDownloadEvents(options: UseDownloadEventsOptions) {
-  const { onDownloadStarted, onDownloadProgress, onDownloadCompleted, onDownloadError } = options;
+  const { onDownloadStarted, onDownloadProgress, onDownloadCompleted, onDownloadError, onDownloadCancelled } =
+    options;
 
   useEffect(() => {
     const unlistenFns: UnlistenFn[] = [];
@@ -48,6 +51,13 @@ export function useDownloadEvents(options: UseDownloadEventsOptions) {
         });
         unlistenFns.push(unlisten);
       }
+
+      if (onDownloadCancelled) {
+        const unlisten = await listen<DownloadCancelledEvent>("download_cancelled", (event) => {
+          onDownloadCancelled(event.payload);
+        });
+        unlistenFns.push(unlisten);
+      }
     };
 
     setupListeners();
@@ -55,7 +65,7 @@ export function useDownloadEvents(options: UseDownloadEventsOptions) {
     return () => {
       unlistenFns.forEach((unlisten) => unlisten());
     };
-  }, [onDownloadStarted, onDownloadProgress, onDownloadCompleted, onDownloadError]);
+  }, [onDownloadStarted, onDownloadProgress, onDownloadCompleted, onDownloadError, onDownloadCancelled]);
 }
 
 // Hook for triggering downloads
@@ -1,7 +1,7 @@
 import { useCallback, useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { api } from "@/lib/tauri";
-import type { MetadataEvent } from "@/types/feed-item";
+import type { MetadataEvent, MetadataPriority } from "@/types/feed-item";
 
 interface UseMetadataEventsOptions {
   onMetadataStarted?: (event: MetadataEvent) => void;
@@ -10,11 +10,13 @@ interface UseMetadataEventsOptions {
 }
 
 interface UseMetadataEventsReturn {
-  /** Fetch metadata for specific feed items (pauses background worker during fetch) */
-  fetchMetadata: (feedItemIds: string[]) => Promise<void>;
+  /** Queue metadata fetches ahead of the background scan (defaults to the "requested" tier) */
+  fetchMetadata: (feedItemIds: string[], priority?: MetadataPriority) => Promise<void>;
+  /** Clear failure state (including unavailable) and fetch metadata again */
+  retryMetadata: (feedItemIds: string[]) => Promise<void>;
   /** Get incomplete metadata items for a creator */
   getIncompleteItems: (creatorId: string, limit?: number) => Promise<string[]>;
-  /** Pause background metadata worker */
+  /** Pause background metadata fetching (visible and requested items still run) */
   pauseWorker: () => Promise<void>;
   /** Resume background metadata worker */
   resumeWorker: () => Promise<void>;
@@ -51,8 +53,12 @@ export function useMetadataEvents(
     };
   }, [onMetadataStarted, onMetadataCompleted, onMetadataError]);
 
-  const fetchMetadata = useCallback(async (feedItemIds: string[]) => {
-    await api.metadata.fetch(feedItemIds);
+  const fetchMetadata = useCallback(async (feedItemIds: string[], priority?: MetadataPriority) => {
+    await api.metadata.fetch(feedItemIds, priority);
+  }, []);
+
+  const retryMetadata = useCallback(async (feedItemIds: string[]) => {
+    await api.metadata.retry(feedItemIds);
   }, []);
 
   const getIncompleteItems = useCallback(
@@ -72,6 +78,7 @@ export function useMetadataEvents(
 
   return {
     fetchMetadata,
+    retryMetadata,
     getIncompleteItems,
     pauseWorker,
     resumeWorker,
@@ -38,6 +38,12 @@ export function useSources(creatorId: string) {
     return source;
   };
 
+  const validateSource = async (id: string) => {
+    const source = await api.sources.validate(id);
+    setSources((prev) => prev.map((s) => (s.id === id ? source : s)));
+    return source;
+  };
+
   const deleteSource = async (id: string) => {
     await api.sources.delete(id);
     setSources((prev) => prev.filter((s) => s.id !== id));
@@ -50,6 +56,7 @@ export function useSources(creatorId: string) {
     refetch: fetchSources,
     createSource,
     updateSource,
+    validateSource,
     deleteSource,
   };
 }
@@ -1,10 +1,21 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { Creator, CreateCreatorRequest, UpdateCreatorRequest } from "@/types/creator";
 import type { Source, CreateSourceRequest, UpdateSourceRequest } from "@/types/source";
+import type { ImportReport, ExportRequest } from "@/types/import";
+import type { BackupManifest } from "@/types/backup";
 import type { Credential, CreateCredentialRequest, UpdateCredentialRequest } from "@/types/credential";
-import type { FeedItem, CreateFeedItemRequest, UpdateFeedItemRequest, FeedItemCounts } from "@/types/feed-item";
-import type { WarehouseItem, CreateWarehouseItemRequest } from "@/types/warehouse-item";
+import type {
+  FeedItem,
+  CreateFeedItemRequest,
+  UpdateFeedItemRequest,
+  FeedItemCounts,
+  FeedItemChapter,
+  MetadataPriority,
+  MetadataQueueStatus,
+} from "@/types/feed-item";
+import type { WarehouseItem, CreateWarehouseItemRequest, SponsorSegment, WarehouseItemFile } from "@/types/warehouse-item";
 import type { AppSettings, UpdateAppSettingsRequest } from "@/types/app-settings";
+import type { Hook, CreateHookRequest, UpdateHookRequest, HookRun } from "@/types/hook";
 import type {
   FeedItemSearchResult,
   WarehouseItemSearchResult,
@@ -19,12 +30,16 @@ export const api = {
     create: (request: CreateCreatorRequest) => invoke<Creator>("create_creator", { request }),
     update: (id: string, request: UpdateCreatorRequest) => invoke<Creator>("update_creator", { id, request }),
     delete: (id: string) => invoke<void>("delete_creator", { id }),
+    importSubscriptions: (path: string, dryRun: boolean) =>
+      invoke<ImportReport>("import_subscriptions", { path, dryRun }),
+    exportLibrary: (request: ExportRequest) => invoke<string[]>("export_library", { request }),
   },
   sources: {
     getByCreator: (creatorId: string) => invoke<Source[]>("get_sources_by_creator", { creatorId }),
     create: (request: CreateSourceRequest) => invoke<Source>("create_source", { request }),
     update: (id: string, request: UpdateSourceRequest) => invoke<Source>("update_source", { id, request }),
     delete: (id: string) => invoke<void>("delete_source", { id }),
+    validate: (id: string) => invoke<Source>("validate_source", { id }),
   },
   credentials: {
     getAll: () => invoke<Credential[]>("get_credentials"),
@@ -41,6 +56,7 @@ export const api = {
     create: (request: CreateFeedItemRequest) => invoke<FeedItem>("create_feed_item", { request }),
     update: (id: string, request: UpdateFeedItemRequest) => invoke<FeedItem>("update_feed_item", { id, request }),
     getCounts: (creatorId: string) => invoke<FeedItemCounts>("get_feed_item_counts", { creatorId }),
+    getChapters: (feedItemId: string) => invoke<FeedItemChapter[]>("get_feed_item_chapters", { feedItemId }),
   },
   sync: {
     source: (sourceId: string) => invoke<void>("sync_source", { sourceId }),
@@ -57,11 +73,24 @@ export const api = {
     delete: (id: string) => invoke<void>("delete_warehouse_item", { id }),
     import: (request: { source_path: string; creator_id: string; title: string; platform?: string }) =>
       invoke<WarehouseItem>("import_video", { request }),
+    getSponsorSegments: (warehouseItemId: string) =>
+      invoke<SponsorSegment[]>("get_sponsor_segments", { warehouseItemId }),
+    getFiles: (warehouseItemId: string) =>
+      invoke<WarehouseItemFile[]>("get_warehouse_item_files", { warehouseItemId }),
+    backfillSidecarFiles: (creatorId?: string) => invoke<number>("backfill_sidecar_files", { creatorId }),
+    verify: (id: string) => invoke<WarehouseItem>("verify_warehouse_item", { id }),
   },
   shell: {
     openInDefaultApp: (filePath: string) => invoke<void>("open_file_in_default_app", { filePath }),
     showInFolder: (filePath: string) => invoke<void>("show_in_folder", { filePath }),
   },
+  hooks: {
+    getAll: () => invoke<Hook[]>("get_hooks"),
+    create: (request: CreateHookRequest) => invoke<Hook>("create_hook", { request }),
+    update: (id: string, request: UpdateHookRequest) => invoke<Hook>("update_hook", { id, request }),
+    delete: (id: string) => invoke<void>("delete_hook", { id }),
+    getRuns: (warehouseItemId: string) => invoke<HookRun[]>("get_hook_runs", { warehouseItemId }),
+  },
   settings: {
     get: () => invoke<AppSettings>("get_app_settings"),
     update: (request: UpdateAppSettingsRequest) => invoke<AppSettings>("update_app_settings", { request }),
@@ -82,10 +111,19 @@ export const api = {
     sendTest: () => invoke<void>("send_test_notification"),
   },
   metadata: {
-    fetch: (feedItemIds: string[]) => invoke<void>("fetch_feed_items_metadata", { feedItemIds }),
+    fetch: (feedItemIds: string[], priority?: MetadataPriority) =>
+      invoke<void>("fetch_feed_items_metadata", { feedItemIds, priority }),
+    retry: (feedItemIds: string[]) => invoke<void>("retry_feed_items_metadata", { feedItemIds }),
     getIncomplete: (creatorId: string, limit?: number) =>
       invoke<string[]>("get_incomplete_metadata_items", { creatorId, limit }),
     pauseWorker: () => invoke<void>("pause_metadata_worker"),
     resumeWorker: () => invoke<void>("resume_metadata_worker"),
+    getQueueStatus: () => invoke<MetadataQueueStatus>("get_metadata_queue_status"),
+  },
+  backup: {
+    create: (path: string, includeThumbnails: boolean) =>
+      invoke<BackupManifest>("create_backup", { path, includeThumbnails }),
+    inspect: (path: string) => invoke<BackupManifest>("inspect_backup", { path }),
+    restore: (path: string) => invoke<BackupManifest>("restore_backup", { path }),
   },
 };
@@ -1,10 +1,12 @@
 import { useCreators } from "@/hooks/useCreators";
 import { CreatorCard } from "@/components/creators/CreatorCard";
 import { CreateCreatorDialog } from "@/components/creators/CreateCreatorDialog";
+import { ImportSubscriptionsDialog } from "@/components/creators/ImportSubscriptionsDialog";
+import { ExportLibraryDialog } from "@/components/creators/ExportLibraryDialog";
 import { Loader2, Users, Sparkles } from "lucide-react";
 
 export function CreatorList() {
-  const { creators, loading, error, createCreator } = useCreators();
+  const { creators, loading, error, createCreator, refetch } = useCreators();
 
   const handleCreateCreator = async (name: string) => {
     await createCreator({ name });
@@ -49,7 +51,11 @@ export function CreatorList() {
               : `Managing ${creators.length} creator${creators.length === 1 ? "" : "s"}`}
           </p>
         </div>
-        <CreateCreatorDialog onSubmit={handleCreateCreator} />
+        <div className="flex items-center gap-2">
+          <ImportSubscriptionsDialog onImported={refetch} />
+          {creators.length > 0 && <ExportLibraryDialog />}
+          <CreateCreatorDialog onSubmit={handleCreateCreator} />
+        </div>
       </div>
 
       {creators.length === 0 ? (
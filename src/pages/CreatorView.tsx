@@ -1,5 +1,6 @@
 import { useEffect, useState } from "react";
 import { useParams, useNavigate, Link } from "react-router-dom";
+import { convertFileSrc } from "@tauri-apps/api/core";
 import { ArrowLeft, Loader2 } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
@@ -95,7 +96,7 @@ export function CreatorView() {
         <div className="relative">
           <div className="absolute inset-0 bg-glow/20 blur-2xl rounded-full" />
           <Avatar className="h-20 w-20 border-2 border-glow/30 relative">
-            <AvatarImage src={creator.photo_path || undefined} className="object-cover" />
+            <AvatarImage src={creator.photo_path ? convertFileSrc(creator.photo_path) : undefined} className="object-cover" />
             <AvatarFallback className="text-2xl font-display font-bold bg-gradient-to-br from-surface to-surface-elevated text-muted-foreground">
               {initials}
             </AvatarFallback>
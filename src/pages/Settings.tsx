@@ -25,6 +25,7 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import { AddCredentialDialog, CredentialsList } from "@/components/credentials";
+import { BackupActions } from "@/components/backup/BackupActions";
 import {
   Loader2,
   FolderOpen,
@@ -37,6 +38,7 @@ import {
   Save,
   BellRing,
   Key,
+  Archive,
   Settings as SettingsIcon,
 } from "lucide-react";
 import { cn } from "@/lib/utils";
@@ -57,6 +59,15 @@ const SYNC_INTERVAL_OPTIONS = [
   { value: 3600, label: "1 hour" },
 ];
 
+const BACKUP_INTERVAL_OPTIONS = [
+  { value: 0, label: "Off" },
+  { value: 6, label: "Every 6 hours" },
+  { value: 24, label: "Daily" },
+  { value: 168, label: "Weekly" },
+];
+
+const BACKUP_KEEP_OPTIONS = [3, 7, 14, 30];
+
 const THEME_OPTIONS = [
   { value: "dark", label: "Dark" },
   { value: "light", label: "Light" },
@@ -132,6 +143,16 @@ export function Settings() {
     }
   };
 
+  const handleSelectBackupDirectory = async () => {
+    const { open } = await import("@tauri-apps/plugin-dialog");
+    const selected = await open({ directory: true });
+    if (selected) {
+      setLocalSettings((prev) =>
+        prev ? { ...prev, backup_directory: selected } : null
+      );
+    }
+  };
+
   const handleSelectLibraryPath = async () => {
     const { open } = await import("@tauri-apps/plugin-dialog");
     const selected = await open({ directory: true });
@@ -502,10 +523,101 @@ export function Settings() {
         </div>
       </SettingsSection>
 
+      {/* Backups Section */}
+      <SettingsSection
+        title="Backups"
+        description="Back up the library database, settings and cookie files"
+        icon={<Archive className="h-5 w-5 text-emerald-400" />}
+        iconColor="bg-emerald-500/10"
+        index={7}
+        action={<BackupActions includeThumbnails={localSettings.backup_include_thumbnails} />}
+      >
+        <div className="space-y-4">
+          <div className="grid grid-cols-2 gap-4">
+            <div className="space-y-2">
+              <Label htmlFor="backup-interval" className="text-sm">Automatic Backups</Label>
+              <Select
+                value={String(localSettings.backup_interval_hours)}
+                onValueChange={(value) =>
+                  setLocalSettings((prev) =>
+                    prev ? { ...prev, backup_interval_hours: parseInt(value) } : null
+                  )
+                }
+              >
+                <SelectTrigger id="backup-interval" className="w-full bg-surface border-border/50">
+                  <SelectValue placeholder="Select interval" />
+                </SelectTrigger>
+                <SelectContent className="glass border-border/50">
+                  {BACKUP_INTERVAL_OPTIONS.map((option) => (
+                    <SelectItem key={option.value} value={String(option.value)}>
+                      {option.label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="backup-keep" className="text-sm">Backups to Keep</Label>
+              <Select
+                value={String(localSettings.backup_keep_count)}
+                onValueChange={(value) =>
+                  setLocalSettings((prev) =>
+                    prev ? { ...prev, backup_keep_count: parseInt(value) } : null
+                  )
+                }
+              >
+                <SelectTrigger id="backup-keep" className="w-full bg-surface border-border/50">
+                  <SelectValue placeholder="Select count" />
+                </SelectTrigger>
+                <SelectContent className="glass border-border/50">
+                  {BACKUP_KEEP_OPTIONS.map((count) => (
+                    <SelectItem key={count} value={String(count)}>
+                      {count}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          <div className="space-y-2">
+            <Label htmlFor="backup-directory" className="text-sm">Backup Folder</Label>
+            <div className="flex gap-2">
+              <Input
+                id="backup-directory"
+                value={localSettings.backup_directory}
+                readOnly
+                placeholder="App data folder"
+                className="flex-1 bg-surface border-border/50"
+              />
+              <Button variant="outline" size="icon" onClick={handleSelectBackupDirectory}>
+                <FolderOpen className="h-4 w-4" />
+              </Button>
+            </div>
+          </div>
+
+          <div className="flex items-center justify-between">
+            <div>
+              <Label htmlFor="backup-thumbnails" className="text-sm">Include Thumbnails</Label>
+              <p className="text-xs text-muted-foreground">Makes backups larger</p>
+            </div>
+            <Switch
+              id="backup-thumbnails"
+              checked={localSettings.backup_include_thumbnails}
+              onCheckedChange={(checked) =>
+                setLocalSettings((prev) =>
+                  prev ? { ...prev, backup_include_thumbnails: checked } : null
+                )
+              }
+            />
+          </div>
+        </div>
+      </SettingsSection>
+
       {/* Save Button */}
       <div
         className="flex justify-end pt-4 opacity-0 animate-fade-up"
-        style={{ animationDelay: "0.35s", animationFillMode: "forwards" }}
+        style={{ animationDelay: "0.4s", animationFillMode: "forwards" }}
       >
         <Button
           onClick={handleSave}
@@ -7,15 +7,17 @@ import { useSyncEvents, useSync } from "@/hooks/useSyncEvents";
 import { AddSourceDialog } from "@/components/sources/AddSourceDialog";
 import { SourcesTable } from "@/components/sources/SourcesTable";
 import type { SyncEvent } from "@/types/feed-item";
+import type { SourcePlatform } from "@/types/source";
 
 interface CreatorSettingsProps {
   creatorId: string;
 }
 
 export function CreatorSettings({ creatorId }: CreatorSettingsProps) {
-  const { sources, loading, error, createSource, deleteSource, refetch } = useSources(creatorId);
+  const { sources, loading, error, createSource, validateSource, deleteSource, refetch } = useSources(creatorId);
   const { syncSource, syncCreator } = useSync();
   const [syncingSourceIds, setSyncingSourceIds] = useState<Set<string>>(new Set());
+  const [validatingSourceIds, setValidatingSourceIds] = useState<Set<string>>(new Set());
 
   // Handle sync events
   useSyncEvents({
@@ -43,7 +45,7 @@ export function CreatorSettings({ creatorId }: CreatorSettingsProps) {
   });
 
   const handleAddSource = async (
-    platform: "youtube" | "patreon",
+    platform: SourcePlatform,
     channelUrl: string,
     credentialId?: string
   ) => {
@@ -52,10 +54,34 @@ export function CreatorSettings({ creatorId }: CreatorSettingsProps) {
       channel_url: channelUrl,
       credential_id: credentialId,
     });
+    if (source.status === "error") {
+      toast.error(`Source could not be validated: ${source.status_reason || "Unknown error"}`);
+      return;
+    }
     // Automatically sync the new source
     await syncSource(source.id);
   };
 
+  const handleValidateSource = async (id: string) => {
+    setValidatingSourceIds((prev) => new Set(prev).add(id));
+    try {
+      const source = await validateSource(id);
+      if (source.status === "error") {
+        toast.error(`Validation failed: ${source.status_reason || "Unknown error"}`);
+      } else {
+        toast.success(`Validated ${source.channel_name || source.channel_url}`);
+      }
+    } catch (err) {
+      toast.error(`Validation failed: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      setValidatingSourceIds((prev) => {
+        const next = new Set(prev);
+        next.delete(id);
+        return next;
+      });
+    }
+  };
+
   const handleDeleteSource = async (id: string) => {
     await deleteSource(id);
   };
@@ -91,7 +117,7 @@ export function CreatorSettings({ creatorId }: CreatorSettingsProps) {
         <div>
           <h2 className="text-xl font-semibold">Sources</h2>
           <p className="text-sm text-muted-foreground">
-            Manage YouTube and Patreon channels for this creator
+            Manage YouTube, Patreon and other yt-dlp channels for this creator
           </p>
         </div>
         <div className="flex items-center gap-2">
@@ -108,8 +134,10 @@ export function CreatorSettings({ creatorId }: CreatorSettingsProps) {
       <SourcesTable
         sources={sources}
         syncingSourceIds={syncingSourceIds}
+        validatingSourceIds={validatingSourceIds}
         onDelete={handleDeleteSource}
         onSync={handleSyncSource}
+        onValidate={handleValidateSource}
       />
     </div>
   );
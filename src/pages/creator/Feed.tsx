@@ -16,6 +16,7 @@ import type {
   DownloadProgressEvent,
   DownloadCompletedEvent,
   DownloadErrorEvent,
+  DownloadCancelledEvent,
 } from "@/types/download";
 
 export interface DownloadProgress {
@@ -37,6 +38,7 @@ export function Feed({ creatorId }: FeedProps) {
   // Filter state
   const [selectedSourceId, setSelectedSourceId] = useState<string | null>(null);
   const [selectedStatus, setSelectedStatus] = useState<string | null>(null);
+  const [selectedKind, setSelectedKind] = useState<string | null>(null);
   const [searchQuery, setSearchQuery] = useState("");
 
   // Selection state
@@ -80,7 +82,9 @@ export function Feed({ creatorId }: FeedProps) {
         next.delete(event.feed_item_id);
         return next;
       });
-    }, []),
+      // Refetch so the item's retry state is known before it's requested again
+      refetchFeed();
+    }, [refetchFeed]),
   });
 
   // Debounce ref for metadata fetching
@@ -92,11 +96,19 @@ export function Feed({ creatorId }: FeedProps) {
       clearTimeout(metadataFetchDebounceRef.current);
     }
 
-    // Find visible items that need metadata and aren't already loading
+    // Find visible items that need metadata and aren't already loading.
+    // Items that failed recently wait for their backoff; unavailable ones need an explicit retry.
+    const now = new Date();
     const incompleteVisibleIds: string[] = [];
     visibleIds.forEach((id) => {
       const item = feedItems.find((i) => i.id === id);
-      if (item && !item.metadata_complete && !loadingMetadataIds.has(id)) {
+      if (
+        item &&
+        !item.metadata_complete &&
+        !item.metadata_unavailable &&
+        (!item.metadata_next_attempt_at || new Date(item.metadata_next_attempt_at) <= now) &&
+        !loadingMetadataIds.has(id)
+      ) {
         incompleteVisibleIds.push(id);
       }
     });
@@ -111,7 +123,7 @@ export function Feed({ creatorId }: FeedProps) {
       setLoadingMetadataIds((prev) => new Set([...prev, ...incompleteVisibleIds]));
 
       try {
-        await fetchMetadata(incompleteVisibleIds);
+        await fetchMetadata(incompleteVisibleIds, "visible");
       } catch (err) {
         console.error("Failed to fetch metadata:", err);
         // On error, remove from loading so they can be retried
@@ -222,6 +234,14 @@ export function Feed({ creatorId }: FeedProps) {
       const title = item?.title || "Item";
       toast.error(`Download failed for "${title}": ${event.error}`);
     }, [refetchFeed, feedItems]),
+    onDownloadCancelled: useCallback((event: DownloadCancelledEvent) => {
+      setDownloadProgress((prev) => {
+        const next = new Map(prev);
+        next.delete(event.feed_item_id);
+        return next;
+      });
+      refetchFeed();
+    }, [refetchFeed]),
   });
 
   // Filter feed items
@@ -238,6 +258,11 @@ export function Feed({ creatorId }: FeedProps) {
       items = items.filter((item) => item.download_status === selectedStatus);
     }
 
+    // Filter by content kind (e.g. hide Shorts)
+    if (selectedKind !== null) {
+      items = items.filter((item) => item.content_kind === selectedKind);
+    }
+
     // Filter by search query using FTS results
     if (searchQuery.trim()) {
       if (searchResultIds !== null) {
@@ -251,7 +276,15 @@ export function Feed({ creatorId }: FeedProps) {
     }
 
     return items;
-  }, [feedItems, selectedSourceId, selectedStatus, searchQuery, searchResultIds]);
+  }, [feedItems, selectedSourceId, selectedStatus, selectedKind, searchQuery, searchResultIds]);
+
+  const contentKinds = useMemo(
+    () =>
+      Array.from(
+        new Set(feedItems.map((item) => item.content_kind).filter((kind): kind is string => kind !== null))
+      ).sort(),
+    [feedItems]
+  );
 
   // Handlers
   const handleToggleSelect = useCallback((id: string) => {
@@ -273,20 +306,35 @@ export function Feed({ creatorId }: FeedProps) {
   const handleClearFilters = useCallback(() => {
     setSelectedSourceId(null);
     setSelectedStatus(null);
+    setSelectedKind(null);
     setSearchQuery("");
   }, []);
 
   const handleDownloadSelected = useCallback(async () => {
-    // Filter to only items that are not_downloaded
-    const idsToDownload = Array.from(selectedIds).filter((id) => {
-      const item = feedItems.find((i) => i.id === id);
-      return item?.download_status === "not_downloaded";
-    });
+    // Filter to only items that are not_downloaded (or were cancelled or corrupt)
+    const downloadable = Array.from(selectedIds)
+      .map((id) => feedItems.find((i) => i.id === id))
+      .filter(
+        (item) =>
+          item?.download_status === "not_downloaded" ||
+          item?.download_status === "cancelled" ||
+          item?.download_status === "corrupt"
+      );
+    // Locked posts would only fail until the credential's tier unlocks them
+    const idsToDownload = downloadable.filter((item) => !item?.locked).map((item) => item!.id);
+    const lockedCount = downloadable.length - idsToDownload.length;
 
     if (idsToDownload.length === 0) {
-      toast.info("No items to download. Selected items may already be downloaded or downloading.");
+      toast.info(
+        lockedCount > 0
+          ? "No items to download. Selected posts are locked for this credential's tier."
+          : "No items to download. Selected items may already be downloaded or downloading."
+      );
       return;
     }
+    if (lockedCount > 0) {
+      toast.info(`Skipping ${lockedCount} locked post${lockedCount === 1 ? "" : "s"}.`);
+    }
 
     try {
       await downloadItems(idsToDownload);
@@ -337,6 +385,9 @@ export function Feed({ creatorId }: FeedProps) {
           onSourceChange={setSelectedSourceId}
           selectedStatus={selectedStatus}
           onStatusChange={setSelectedStatus}
+          contentKinds={contentKinds}
+          selectedKind={selectedKind}
+          onKindChange={setSelectedKind}
           searchQuery={searchQuery}
           onSearchChange={setSearchQuery}
           onClearFilters={handleClearFilters}
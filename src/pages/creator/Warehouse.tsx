@@ -74,9 +74,9 @@ export function Warehouse({ creatorId }: WarehouseProps) {
     if (selectedPlatform !== null) {
       items = items.filter((item) => {
         if (selectedPlatform === "other") {
-          // "other" matches items where platform is not youtube or patreon
+          // "other" matches items where platform is not youtube, patreon or twitch
           const platform = item.platform?.toLowerCase();
-          return platform !== "youtube" && platform !== "patreon";
+          return platform !== "youtube" && platform !== "patreon" && platform !== "twitch";
         }
         return item.platform?.toLowerCase() === selectedPlatform.toLowerCase();
       });
@@ -7,6 +7,22 @@ export interface AppSettings {
   notifications_enabled: boolean;
   bass_boost_preset: string;
   bass_boost_custom_gain: number;
+  sponsorblock_enabled: boolean;
+  sponsorblock_mark_categories: string;
+  sponsorblock_remove_categories: string;
+  sponsorblock_api_url: string;
+  embed_metadata: boolean;
+  write_nfo_files: boolean;
+  write_info_json: boolean;
+  partial_download_max_age_days: number;
+  metadata_requests_per_minute: string;
+  metadata_refresh_policy: string;
+  twitch_vod_retention_days: number;
+  backup_interval_hours: number;
+  backup_keep_count: number;
+  backup_include_thumbnails: boolean;
+  backup_directory: string;
+  hook_timeout_minutes: number;
 }
 
 export interface UpdateAppSettingsRequest {
@@ -18,4 +34,20 @@ export interface UpdateAppSettingsRequest {
   notifications_enabled?: boolean;
   bass_boost_preset?: string;
   bass_boost_custom_gain?: number;
+  sponsorblock_enabled?: boolean;
+  sponsorblock_mark_categories?: string;
+  sponsorblock_remove_categories?: string;
+  sponsorblock_api_url?: string;
+  embed_metadata?: boolean;
+  write_nfo_files?: boolean;
+  write_info_json?: boolean;
+  partial_download_max_age_days?: number;
+  metadata_requests_per_minute?: string;
+  metadata_refresh_policy?: string;
+  twitch_vod_retention_days?: number;
+  backup_interval_hours?: number;
+  backup_keep_count?: number;
+  backup_include_thumbnails?: boolean;
+  backup_directory?: string;
+  hook_timeout_minutes?: number;
 }
@@ -0,0 +1,20 @@
+/** A file stored in a backup besides the database */
+export interface BackupFile {
+  /** Credential or warehouse item the file belongs to */
+  owner_id: string;
+  /** Where the file was when the backup was made */
+  original_path: string;
+  archive_path: string;
+}
+
+/** manifest.json of a backup archive */
+export interface BackupManifest {
+  format_version: number;
+  app_version: string;
+  schema_version: number;
+  created_at: string;
+  database: string;
+  settings: string;
+  cookie_files: BackupFile[];
+  thumbnails: BackupFile[];
+}
@@ -17,3 +17,7 @@ export interface DownloadErrorEvent {
   feed_item_id: string;
   error: string;
 }
+
+export interface DownloadCancelledEvent {
+  feed_item_id: string;
+}
@@ -6,10 +6,59 @@ export interface FeedItem {
   thumbnail_url: string | null;
   published_at: string | null;
   duration: number | null;
-  download_status: "not_downloaded" | "downloading" | "downloaded" | "error";
+  download_status: "not_downloaded" | "downloading" | "downloaded" | "error" | "cancelled" | "corrupt";
   warehouse_item_id: string | null;
   metadata_complete: boolean;
   created_at: string;
+  /** Failed metadata fetches since the last success */
+  metadata_attempts: number;
+  metadata_last_error: string | null;
+  /** The background worker won't retry before this time */
+  metadata_next_attempt_at: string | null;
+  /** Retries exhausted or the item is gone for good */
+  metadata_unavailable: boolean;
+  description: string | null;
+  tags: string[];
+  categories: string[];
+  view_count: number | null;
+  like_count: number | null;
+  /** Available video heights, best first (e.g. "1080p") */
+  resolutions: string[];
+  /** Livestream or livestream recording */
+  is_live: boolean;
+  is_short: boolean;
+  /** Last successful metadata fetch, used by the refresh policy */
+  metadata_fetched_at: string | null;
+  /** Item URL recorded at sync time, for platforms that can't build one from the external ID */
+  webpage_url: string | null;
+  /** Platform-specific kind of item: "video", "short", "stream" or "podcast" on YouTube, "vod", "highlight" or "clip" on Twitch, the post type on Patreon */
+  content_kind: string | null;
+  /** Game or category the item was streamed under */
+  game: string | null;
+  /** When the platform takes the item down (Twitch VODs) */
+  expires_at: string | null;
+  /** Files attached to a post (Patreon audio, images and attachments) */
+  media: PostMedia[];
+  /** The source's credential doesn't unlock the post */
+  locked: boolean;
+  /** Lowest tier (or pledge) that unlocks a restricted post */
+  required_tier: string | null;
+}
+
+export interface PostMedia {
+  kind: "audio" | "image" | "file";
+  url: string;
+  file_name: string | null;
+  mime_type: string | null;
+}
+
+export interface FeedItemChapter {
+  id: string;
+  feed_item_id: string;
+  position: number;
+  title: string;
+  start_time: number;
+  end_time: number;
 }
 
 export interface CreateFeedItemRequest {
@@ -22,7 +71,7 @@ export interface CreateFeedItemRequest {
 }
 
 export interface UpdateFeedItemRequest {
-  download_status?: "not_downloaded" | "downloading" | "downloaded" | "error";
+  download_status?: "not_downloaded" | "downloading" | "downloaded" | "error" | "cancelled" | "corrupt";
   warehouse_item_id?: string | null;
 }
 
@@ -44,3 +93,16 @@ export interface MetadataEvent {
   status: "started" | "completed" | "error";
   message?: string;
 }
+
+/** Metadata queue tiers, highest priority first */
+export type MetadataPriority = "visible" | "requested" | "background" | "refresh";
+
+export interface MetadataQueueStatus {
+  visible: string[];
+  requested: string[];
+  background: string[];
+  refresh: string[];
+  in_flight: string[];
+  /** Background and refresh fetching is paused; visible and requested items still run */
+  paused: boolean;
+}
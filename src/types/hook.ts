@@ -0,0 +1,44 @@
+export type HookType = "command" | "ffmpeg";
+
+export interface Hook {
+  id: string;
+  name: string;
+  hook_type: HookType;
+  command: string;
+  run_on_download: boolean;
+  run_on_import: boolean;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreateHookRequest {
+  name: string;
+  hook_type: HookType;
+  command: string;
+  run_on_download?: boolean;
+  run_on_import?: boolean;
+  enabled?: boolean;
+}
+
+export interface UpdateHookRequest {
+  name?: string;
+  hook_type?: HookType;
+  command?: string;
+  run_on_download?: boolean;
+  run_on_import?: boolean;
+  enabled?: boolean;
+}
+
+export interface HookRun {
+  id: string;
+  hook_id: string;
+  hook_name: string;
+  warehouse_item_id: string;
+  trigger: "download" | "import";
+  success: boolean;
+  exit_code: number | null;
+  output: string;
+  started_at: string;
+  finished_at: string;
+}
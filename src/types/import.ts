@@ -0,0 +1,33 @@
+export type ImportFormat = "opml" | "takeout_csv" | "url_list";
+
+/** What an import does (or would do) with one entry of the list */
+export interface ImportEntry {
+  name: string;
+  platform: string;
+  channel_url: string;
+  action: "create" | "duplicate" | "invalid";
+  /** Existing creator with the same name, which the source is added to */
+  existing_creator_id: string | null;
+  /** Why the entry is skipped */
+  message: string | null;
+}
+
+export interface ImportReport {
+  format: ImportFormat;
+  /** True when nothing was written and the counts are what an import would create */
+  dry_run: boolean;
+  entries: ImportEntry[];
+  creators_created: number;
+  sources_created: number;
+  duplicates: number;
+  invalid: number;
+}
+
+export type ExportFormat = "opml" | "json" | "csv";
+
+export interface ExportRequest {
+  path: string;
+  format: ExportFormat;
+  include_feed_items?: boolean;
+  include_warehouse_items?: boolean;
+}
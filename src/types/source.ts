@@ -1,18 +1,31 @@
+/**
+ * "generic" covers any playlist or channel URL yt-dlp can extract;
+ * "rss" is an RSS/Atom feed whose media enclosures are downloaded directly
+ */
+export type SourcePlatform = "youtube" | "patreon" | "twitch" | "rss" | "generic";
+
 export interface Source {
   id: string;
   creator_id: string;
-  platform: "youtube" | "patreon";
+  platform: SourcePlatform;
   channel_url: string;
   channel_name: string | null;
+  /** Platform ID of the channel, resolved by validation */
+  channel_id: string | null;
+  avatar_url: string | null;
+  banner_url: string | null;
+  subscriber_count: number | null;
   credential_id: string | null;
   status: "pending" | "validated" | "error";
+  /** Why validation or the last sync failed */
+  status_reason: string | null;
   last_synced_at: string | null;
   created_at: string;
 }
 
 export interface CreateSourceRequest {
   creator_id: string;
-  platform: "youtube" | "patreon";
+  platform: SourcePlatform;
   channel_url: string;
   credential_id?: string | null;
 }
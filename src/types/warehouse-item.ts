@@ -12,6 +12,14 @@ export interface WarehouseItem {
   file_size: number;
   imported_at: string;
   is_manual_import: boolean;
+  width: number | null;
+  height: number | null;
+  video_codec: string | null;
+  audio_codec: string | null;
+  bitrate: number | null;
+  fps: number | null;
+  verification_status: "verified" | "corrupt" | "unverified";
+  verification_error: string | null;
 }
 
 export interface CreateWarehouseItemRequest {
@@ -27,3 +35,23 @@ export interface CreateWarehouseItemRequest {
   file_size: number;
   is_manual_import: boolean;
 }
+
+/** One file of an item downloaded from a post with several files */
+export interface WarehouseItemFile {
+  id: string;
+  warehouse_item_id: string;
+  position: number;
+  file_path: string;
+  kind: "video" | "audio" | "image" | "file";
+  mime_type: string | null;
+  file_size: number;
+}
+
+export interface SponsorSegment {
+  id: string;
+  warehouse_item_id: string;
+  category: string;
+  action_type: string;
+  start_time: number;
+  end_time: number;
+}